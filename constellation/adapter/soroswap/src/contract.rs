@@ -1,6 +1,6 @@
 use crate::error::Error;
 use crate::require::{require_factory, require_pair, require_router}; //, require_pair};
-use crate::router::router_pair_for;
+use crate::router::{router_get_amounts_in, router_get_amounts_out, router_pair_for};
 use crate::storage::{factory, router};
 use constellation_lib::traits::adapter::dex::IExchange;
 use constellation_lib::traits::adapter::{self, dex};
@@ -10,6 +10,7 @@ use soroban_sdk::{
     contract, contractimpl, panic_with_error, vec, Address, BytesN, Env, String, Symbol, Val, Vec,
 };
 static SWAP_EXACT_TOKENS_FOR_TOKENS: &'static str = "swap_exact_tokens_for_tokens";
+static SWAP_TOKENS_FOR_EXACT_TOKENS: &'static str = "swap_tokens_for_exact_tokens";
 static APPROVE: &'static str = "approve";
 static TRANSFER: &'static str = "transfer";
 
@@ -94,4 +95,76 @@ impl dex::Interface for SoroswapAdapter {
 
         sub_auth_vec
     }
+
+    fn get_path_amounts_out(e: &Env, amount_in: i128, path: Vec<Address>) -> Vec<i128> {
+        router_get_amounts_out(e, &require_router(e), amount_in, &path)
+    }
+
+    fn get_path_amounts_in(e: &Env, amount_out: i128, path: Vec<Address>) -> Vec<i128> {
+        router_get_amounts_in(e, &require_router(e), amount_out, &path)
+    }
+
+    fn get_path_swap_call_data(
+        e: &Env,
+        path: Vec<Address>,
+        amount_in: i128,
+        amount_out_min: i128,
+        to: Address,
+        deadline: u64,
+    ) -> (Symbol, Vec<Val>) {
+        let mut args: Vec<Val> = vec![e];
+        args.push_back(amount_in.into_val(e));
+        args.push_back(amount_out_min.into_val(e));
+        args.push_back(path.into_val(e));
+        args.push_back(to.into_val(e));
+        args.push_back(deadline.into_val(e));
+        (Symbol::new(e, SWAP_EXACT_TOKENS_FOR_TOKENS), args)
+    }
+
+    fn get_path_swap_out_call_data(
+        e: &Env,
+        path: Vec<Address>,
+        amount_out: i128,
+        amount_in_max: i128,
+        to: Address,
+        deadline: u64,
+    ) -> (Symbol, Vec<Val>) {
+        let mut args: Vec<Val> = vec![e];
+        args.push_back(amount_out.into_val(e));
+        args.push_back(amount_in_max.into_val(e));
+        args.push_back(path.into_val(e));
+        args.push_back(to.into_val(e));
+        args.push_back(deadline.into_val(e));
+        (Symbol::new(e, SWAP_TOKENS_FOR_EXACT_TOKENS), args)
+    }
+
+    /// Soroswap only transfers from `from` to the pair of the first hop,
+    /// the following hops are paid by the pairs themselves
+    fn create_path_sub_auth(
+        e: &Env,
+        amount_in: i128,
+        path: Vec<Address>,
+        from: Address,
+    ) -> Vec<InvokerContractAuthEntry> {
+        let router_id = require_router(e);
+        let token_in = path.get_unchecked(0);
+        let pair = router_pair_for(e, &router_id, &token_in, &path.get_unchecked(1));
+
+        let mut args: Vec<Val> = vec![e];
+        args.push_back(from.into_val(e));
+        args.push_back(pair.into_val(e));
+        args.push_back(amount_in.into_val(e));
+
+        vec![
+            e,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: token_in,
+                    fn_name: Symbol::new(e, TRANSFER),
+                    args,
+                },
+                sub_invocations: vec![e],
+            }),
+        ]
+    }
 }
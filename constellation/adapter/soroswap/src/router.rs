@@ -16,3 +16,23 @@ pub(crate) fn router_pair_for(
     let ctoken = router::Client::new(&e, router_id);
     ctoken.router_pair_for(token_a, token_b)
 }
+
+pub(crate) fn router_get_amounts_out(
+    e: &Env,
+    router_id: &Address,
+    amount_in: i128,
+    path: &Vec<Address>,
+) -> Vec<i128> {
+    let router = router::Client::new(e, router_id);
+    router.router_get_amounts_out(&amount_in, path)
+}
+
+pub(crate) fn router_get_amounts_in(
+    e: &Env,
+    router_id: &Address,
+    amount_out: i128,
+    path: &Vec<Address>,
+) -> Vec<i128> {
+    let router = router::Client::new(e, router_id);
+    router.router_get_amounts_in(&amount_out, path)
+}
@@ -0,0 +1,228 @@
+use crate::traits::adapter::dex;
+use crate::types::exchange::{SwapLeg, Venue};
+use soroban_sdk::{vec, Address, Env, Vec};
+
+/// Maximum number of parts an order can be split into
+pub const MAX_SPLIT_PARTS: u32 = 10;
+
+/// Returns the amount of the last token of `path` received for `amount_in` of its first token on `venue`
+/// Returns None if the adapter cannot quote the path, e.g. when the exchange has no pool for it
+pub fn quote_out(e: &Env, venue: &Venue, amount_in: i128, path: &Vec<Address>) -> Option<i128> {
+    let adapter = dex::Client::new(e, &venue.adapter_id);
+    match adapter.try_get_path_amounts_out(&amount_in, path) {
+        Ok(Ok(amounts)) => amounts.last().filter(|amount_out| *amount_out > 0),
+        _ => None,
+    }
+}
+
+/// Returns the amount of the first token of `path` required for `amount_out` of its last token on `venue`
+/// Returns None if the adapter cannot quote the path, e.g. when the exchange has no pool for it
+pub fn quote_in(e: &Env, venue: &Venue, amount_out: i128, path: &Vec<Address>) -> Option<i128> {
+    let adapter = dex::Client::new(e, &venue.adapter_id);
+    match adapter.try_get_path_amounts_in(&amount_out, path) {
+        Ok(Ok(amounts)) => amounts.first().filter(|amount_in| *amount_in > 0),
+        _ => None,
+    }
+}
+
+/// Returns the venue giving the most of the last token of `path` for `amount_in` of its first token
+/// Returns None if no venue can quote the path
+pub fn best_quote_out(
+    e: &Env,
+    venues: &Vec<Venue>,
+    amount_in: i128,
+    path: &Vec<Address>,
+) -> Option<SwapLeg> {
+    let mut best: Option<SwapLeg> = None;
+    for venue in venues.iter() {
+        if let Some(amount_out) = quote_out(e, &venue, amount_in, path) {
+            let better = match &best {
+                Some(leg) => amount_out > leg.amount_out,
+                None => true,
+            };
+            if better {
+                best = Some(SwapLeg {
+                    exchange_id: venue.exchange_id,
+                    amount_in,
+                    amount_out,
+                });
+            }
+        }
+    }
+    best
+}
+
+/// Returns the venue requiring the least of the first token of `path` for `amount_out` of its last token
+/// Returns None if no venue can quote the path
+pub fn best_quote_in(
+    e: &Env,
+    venues: &Vec<Venue>,
+    amount_out: i128,
+    path: &Vec<Address>,
+) -> Option<SwapLeg> {
+    let mut best: Option<SwapLeg> = None;
+    for venue in venues.iter() {
+        if let Some(amount_in) = quote_in(e, &venue, amount_out, path) {
+            let better = match &best {
+                Some(leg) => amount_in < leg.amount_in,
+                None => true,
+            };
+            if better {
+                best = Some(SwapLeg {
+                    exchange_id: venue.exchange_id,
+                    amount_in,
+                    amount_out,
+                });
+            }
+        }
+    }
+    best
+}
+
+/// Splits `amount_in` of the first token of `path` across `venues` to receive the most of its last token
+/// Returns one leg per venue used, or an empty vector if no venue can quote the path
+///
+/// The order is cut into `parts` equal parts, the last one taking the remainder, and each part
+/// goes to the venue with the best marginal output given the parts it already holds
+///
+/// # Arguments
+/// - `e` - The runtime environment.
+/// - `venues` - Exchanges to split the order across
+/// - `amount_in` - Amount of the first token of `path` to swap
+/// - `path` - Tokens swapped through
+/// - `parts` - Number of parts, from 1 to `MAX_SPLIT_PARTS`
+pub fn split_quote_out(
+    e: &Env,
+    venues: &Vec<Venue>,
+    amount_in: i128,
+    path: &Vec<Address>,
+    parts: u32,
+) -> Vec<SwapLeg> {
+    let mut legs: Vec<SwapLeg> = vec![e];
+    for venue in venues.iter() {
+        legs.push_back(SwapLeg {
+            exchange_id: venue.exchange_id,
+            amount_in: 0,
+            amount_out: 0,
+        });
+    }
+
+    let part = amount_in / parts as i128;
+    let mut allocated = 0;
+    for i in 0..parts {
+        let amount = if i == parts - 1 {
+            amount_in - allocated
+        } else {
+            part
+        };
+        if amount <= 0 {
+            continue;
+        }
+
+        // (venue index, output of the venue with this part added)
+        let mut best: Option<(u32, i128)> = None;
+        let mut best_gain = 0;
+        for (index, venue) in venues.iter().enumerate() {
+            let leg = legs.get_unchecked(index as u32);
+            if let Some(amount_out) = quote_out(e, &venue, leg.amount_in + amount, path) {
+                let gain = amount_out - leg.amount_out;
+                if best.is_none() || gain > best_gain {
+                    best = Some((index as u32, amount_out));
+                    best_gain = gain;
+                }
+            }
+        }
+        match best {
+            Some((index, amount_out)) => {
+                let mut leg = legs.get_unchecked(index);
+                leg.amount_in += amount;
+                leg.amount_out = amount_out;
+                legs.set(index, leg);
+                allocated += amount;
+            }
+            None => return vec![e],
+        }
+    }
+
+    let mut filled: Vec<SwapLeg> = vec![e];
+    for leg in legs.iter() {
+        if leg.amount_in > 0 {
+            filled.push_back(leg);
+        }
+    }
+    filled
+}
+
+/// Splits `amount_out` of the last token of `path` across `venues` to spend the least of its first token
+/// Returns one leg per venue used, or an empty vector if no venue can quote the path
+///
+/// The order is cut into `parts` equal parts, the last one taking the remainder, and each part
+/// goes to the venue with the lowest marginal input given the parts it already holds
+///
+/// # Arguments
+/// - `e` - The runtime environment.
+/// - `venues` - Exchanges to split the order across
+/// - `amount_out` - Amount of the last token of `path` to buy
+/// - `path` - Tokens swapped through
+/// - `parts` - Number of parts, from 1 to `MAX_SPLIT_PARTS`
+pub fn split_quote_in(
+    e: &Env,
+    venues: &Vec<Venue>,
+    amount_out: i128,
+    path: &Vec<Address>,
+    parts: u32,
+) -> Vec<SwapLeg> {
+    let mut legs: Vec<SwapLeg> = vec![e];
+    for venue in venues.iter() {
+        legs.push_back(SwapLeg {
+            exchange_id: venue.exchange_id,
+            amount_in: 0,
+            amount_out: 0,
+        });
+    }
+
+    let part = amount_out / parts as i128;
+    let mut allocated = 0;
+    for i in 0..parts {
+        let amount = if i == parts - 1 {
+            amount_out - allocated
+        } else {
+            part
+        };
+        if amount <= 0 {
+            continue;
+        }
+
+        // (venue index, input of the venue with this part added)
+        let mut best: Option<(u32, i128)> = None;
+        let mut best_cost = 0;
+        for (index, venue) in venues.iter().enumerate() {
+            let leg = legs.get_unchecked(index as u32);
+            if let Some(amount_in) = quote_in(e, &venue, leg.amount_out + amount, path) {
+                let cost = amount_in - leg.amount_in;
+                if best.is_none() || cost < best_cost {
+                    best = Some((index as u32, amount_in));
+                    best_cost = cost;
+                }
+            }
+        }
+        match best {
+            Some((index, amount_in)) => {
+                let mut leg = legs.get_unchecked(index);
+                leg.amount_in = amount_in;
+                leg.amount_out += amount;
+                legs.set(index, leg);
+                allocated += amount;
+            }
+            None => return vec![e],
+        }
+    }
+
+    let mut filled: Vec<SwapLeg> = vec![e];
+    for leg in legs.iter() {
+        if leg.amount_out > 0 {
+            filled.push_back(leg);
+        }
+    }
+    filled
+}
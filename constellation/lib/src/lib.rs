@@ -1,3 +1,10 @@
 #![no_std]
+pub mod aggregator;
+pub mod math;
+pub mod module;
+pub mod oracle;
+pub mod orders;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
 pub mod traits;
 pub mod types;
@@ -0,0 +1,75 @@
+/// Seconds in a year of 365 days, the period annual fee rates apply to
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+/// Denominator of rates in basis points
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Rounding direction applied when converting component units into token quantities
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// Returns the scaling factor of a unit for a token with `decimals` decimals
+/// Returns None if the factor does not fit in an i128
+pub fn unit_scale(decimals: u32) -> Option<i128> {
+    10i128.checked_pow(decimals)
+}
+
+/// Computes `a * b / denominator`, rounding the result in the given direction
+/// Returns None on overflow or when `denominator` is not positive
+pub fn mul_div(a: i128, b: i128, denominator: i128, rounding: Rounding) -> Option<i128> {
+    if denominator <= 0 {
+        return None;
+    }
+    let product = a.checked_mul(b)?;
+    let quotient = product.div_euclid(denominator);
+    match rounding {
+        Rounding::Down => Some(quotient),
+        Rounding::Up => {
+            if product.rem_euclid(denominator) > 0 {
+                quotient.checked_add(1)
+            } else {
+                Some(quotient)
+            }
+        }
+    }
+}
+
+/// Returns the quantity of a component backing `amount` constellation tokens
+///
+/// # Arguments
+/// - `amount` Amount of constellation tokens in base units
+/// - `unit` Component quantity per whole constellation token
+/// - `decimals` Decimals of the constellation token
+/// - `rounding` Rounding direction, `Up` when tokens are minted and `Down` when redeemed
+pub fn component_quantity(
+    amount: i128,
+    unit: i128,
+    decimals: u32,
+    rounding: Rounding,
+) -> Option<i128> {
+    mul_div(amount, unit, unit_scale(decimals)?, rounding)
+}
+
+/// Returns the amount of tokens to mint so the fee recipient receives `fee_bps`
+/// per year of the supply over `elapsed` seconds
+///
+/// Each period of at most a year mints supply * f / (1 - f), where f is the pro-rata
+/// fee, so the minted tokens own exactly f of the diluted supply
+/// Rounded down in favour of holders, returns None on overflow
+pub fn accrued_fee(supply: i128, fee_bps: u32, elapsed: u64) -> Option<i128> {
+    let year = SECONDS_PER_YEAR as i128;
+    let mut remaining = elapsed;
+    let mut diluted_supply = supply;
+    while remaining > 0 {
+        let period = remaining.min(SECONDS_PER_YEAR);
+        remaining -= period;
+
+        let fee = (fee_bps as i128).checked_mul(period as i128)?;
+        let denominator = BPS_DENOMINATOR.checked_mul(year)?.checked_sub(fee)?;
+        let minted = mul_div(diluted_supply, fee, denominator, Rounding::Down)?;
+        diluted_supply = diluted_supply.checked_add(minted)?;
+    }
+    diluted_supply.checked_sub(supply)
+}
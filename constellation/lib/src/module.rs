@@ -0,0 +1,285 @@
+use crate::math::{mul_div, unit_scale, Rounding, BPS_DENOMINATOR};
+use crate::oracle::{asset_value, config_price};
+use crate::traits::adapter::dex;
+use crate::traits::{constellation_token, registry};
+use crate::types::oracle::OracleConfig;
+use soroban_sdk::{
+    auth::InvokerContractAuthEntry, contracttype, token::TokenClient, vec, Address, Env, Symbol,
+    Val, Vec,
+};
+
+/// Deviation below the oracle rate accepted on module trades when the registry sets no ceiling, 5%
+pub const DEFAULT_MAX_PRICE_DEVIATION_BPS: u32 = 500;
+
+/// Failure of a check shared by the modules, each module reports it with its own error code
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ModuleError {
+    RequiresRegistry,
+    RequiresExchangeAdapter,
+    RequiresManage,
+    PairNotFound,
+    RequiresOracle,
+    StalePrice,
+    ValueTooLargeOverFlow,
+}
+
+#[derive(Clone)]
+#[contracttype(export = false)]
+enum DataKey {
+    Registry,
+}
+
+pub fn has_registry(e: &Env) -> bool {
+    let key = DataKey::Registry;
+    e.storage().instance().has(&key)
+}
+
+pub fn read_registry(e: &Env) -> Option<Address> {
+    let key = DataKey::Registry;
+    e.storage().instance().get(&key)
+}
+
+pub fn write_registry(e: &Env, id: &Address) {
+    let key = DataKey::Registry;
+    e.storage().instance().set(&key, id);
+}
+
+pub fn require_registry(e: &Env) -> Result<Address, ModuleError> {
+    read_registry(e).ok_or(ModuleError::RequiresRegistry)
+}
+
+/// Returns the adapter registered for the calling module to reach `exchange_id`
+pub fn require_adapter(
+    e: &Env,
+    registry_id: &Address,
+    exchange_id: &Address,
+) -> Result<Address, ModuleError> {
+    let client = registry::Client::new(e, registry_id);
+    client
+        .get_adapter_id(&e.current_contract_address(), exchange_id)
+        .ok_or(ModuleError::RequiresExchangeAdapter)
+}
+
+/// Returns the protocol price feed of the registry
+pub fn registry_oracle(e: &Env, registry_id: &Address) -> Option<OracleConfig> {
+    let client = registry::Client::new(e, registry_id);
+    client.get_oracle()
+}
+
+/// Returns the protocol ceiling on the deviation of module trades from the registry oracle rate,
+/// or `DEFAULT_MAX_PRICE_DEVIATION_BPS` if the registry does not set one
+pub fn max_price_deviation(e: &Env, registry_id: &Address) -> u32 {
+    let client = registry::Client::new(e, registry_id);
+    client
+        .get_max_price_deviation()
+        .unwrap_or(DEFAULT_MAX_PRICE_DEVIATION_BPS)
+}
+
+/// Returns the lowest amount of `token_out_id` the registry oracle rate accepts for `amount_in`
+/// of `token_in_id`, `max_deviation_bps` below the oracle value of the amount sold
+/// The oracle is set on the registry, the rate cannot be moved within the transaction trading
+/// Returns error if the registry has no oracle, or if a price is missing or stale
+///
+/// # Arguments
+///
+/// - `e` The runtime environment.
+/// - `registry_id` Registry of the protocol
+/// - `token_in_id` Token sold
+/// - `token_out_id` Token bought
+/// - `amount_in` Amount of `token_in_id` sold
+/// - `max_deviation_bps` Deviation below the oracle rate accepted
+pub fn oracle_amount_out_min(
+    e: &Env,
+    registry_id: &Address,
+    token_in_id: &Address,
+    token_out_id: &Address,
+    amount_in: i128,
+    max_deviation_bps: u32,
+) -> Result<i128, ModuleError> {
+    let oracle = registry_oracle(e, registry_id).ok_or(ModuleError::RequiresOracle)?;
+    let price_in = config_price(e, &oracle, token_in_id).ok_or(ModuleError::StalePrice)?;
+    let price_out = config_price(e, &oracle, token_out_id).ok_or(ModuleError::StalePrice)?;
+
+    let value_in = asset_value(
+        amount_in,
+        price_in,
+        TokenClient::new(e, token_in_id).decimals(),
+    )
+    .ok_or(ModuleError::ValueTooLargeOverFlow)?;
+    let min_value_out = mul_div(
+        value_in,
+        BPS_DENOMINATOR - max_deviation_bps as i128,
+        BPS_DENOMINATOR,
+        Rounding::Up,
+    )
+    .ok_or(ModuleError::ValueTooLargeOverFlow)?;
+    // rounded up, the amount out is worth at least the lowest value accepted
+    let scale_out = unit_scale(TokenClient::new(e, token_out_id).decimals())
+        .ok_or(ModuleError::ValueTooLargeOverFlow)?;
+    mul_div(min_value_out, scale_out, price_out, Rounding::Up)
+        .ok_or(ModuleError::ValueTooLargeOverFlow)
+}
+
+pub fn require_manager(e: &Env, constellation_token_id: &Address) -> Result<Address, ModuleError> {
+    let client = constellation_token::ModuleClient::new(e, constellation_token_id);
+    client.get_manager().ok_or(ModuleError::RequiresManage)
+}
+
+/// Returns the supply of the constellation token
+pub fn total_supply(e: &Env, constellation_token_id: &Address) -> i128 {
+    let client = constellation_token::ModuleClient::new(e, constellation_token_id);
+    client.total_supply()
+}
+
+/// Returns the decimals of the constellation token, units are expressed per whole token
+pub fn decimals(e: &Env, constellation_token_id: &Address) -> u32 {
+    let client = constellation_token::ModuleClient::new(e, constellation_token_id);
+    client.decimals()
+}
+
+/// Returns the amount of the last token of `path` the adapter quotes for `amount_in` of its first token
+/// Returns error if the exchange has no pool for one of the hops
+pub fn require_path_quote(
+    e: &Env,
+    adapter_id: &Address,
+    path: &Vec<Address>,
+    amount_in: i128,
+) -> Result<i128, ModuleError> {
+    let exchange_adapter = dex::Client::new(e, adapter_id);
+    match exchange_adapter.try_get_path_amounts_out(&amount_in, path) {
+        Ok(Ok(amounts)) => amounts.last().ok_or(ModuleError::PairNotFound),
+        _ => Err(ModuleError::PairNotFound),
+    }
+}
+
+/// Returns the amount of the first token of `path` the adapter quotes for `amount_out` of its last token
+/// Returns error if the exchange has no pool for one of the hops
+pub fn require_path_quote_in(
+    e: &Env,
+    adapter_id: &Address,
+    path: &Vec<Address>,
+    amount_out: i128,
+) -> Result<i128, ModuleError> {
+    let exchange_adapter = dex::Client::new(e, adapter_id);
+    match exchange_adapter.try_get_path_amounts_in(&amount_out, path) {
+        Ok(Ok(amounts)) => amounts.first().ok_or(ModuleError::PairNotFound),
+        _ => Err(ModuleError::PairNotFound),
+    }
+}
+
+/// Returns the amount of `token_out_id` the adapter quotes for `amount_in` of `token_in_id`
+/// Returns error if the exchange has no pool for the pair
+pub fn require_pair_quote(
+    e: &Env,
+    adapter_id: &Address,
+    token_in_id: &Address,
+    token_out_id: &Address,
+    amount_in: i128,
+) -> Result<i128, ModuleError> {
+    let path = vec![e, token_in_id.clone(), token_out_id.clone()];
+    require_path_quote(e, adapter_id, &path, amount_in)
+}
+
+/// Invokes `target_id` on behalf of the constellation token
+///
+/// # Arguments
+///
+/// - `e` The runtime environment.
+/// - `constellation_token_id` Target constellation token id
+/// - `target_id` Contract invoked by the constellation token
+/// - `call_data` Function name and arguments
+/// - `auth_entries` Sub-invocations authorized by the constellation token
+pub fn invoke(
+    e: &Env,
+    constellation_token_id: &Address,
+    target_id: &Address,
+    call_data: &(Symbol, Vec<Val>),
+    auth_entries: &Vec<InvokerContractAuthEntry>,
+) {
+    let client = constellation_token::ModuleClient::new(e, constellation_token_id);
+    client.invoke(
+        &e.current_contract_address(),
+        target_id,
+        call_data,
+        auth_entries,
+    );
+}
+
+/// Asks the constellation token to record its balances of the tokens traded by the calling module
+pub fn snapshot_balances(e: &Env, constellation_token_id: &Address, tokens: &Vec<Address>) {
+    let client = constellation_token::ModuleClient::new(e, constellation_token_id);
+    client.snapshot_balances(&e.current_contract_address(), tokens);
+}
+
+/// Asks the constellation token to update the units of the tokens in its balance snapshot
+pub fn update_units(e: &Env, constellation_token_id: &Address) {
+    let client = constellation_token::ModuleClient::new(e, constellation_token_id);
+    client.update_units(&e.current_contract_address());
+}
+
+/// Activates the calling module on the constellation token once its settings are stored
+pub fn initialize_module(e: &Env, constellation_token_id: &Address) {
+    let client = constellation_token::ModuleClient::new(e, constellation_token_id);
+    client.initialize_module(&e.current_contract_address());
+}
+
+/// Approves `exchange_id` and swaps `amount_in` of the first token of `path` held by the constellation token
+/// for at least `amount_out_min` of its last token through the exchange adapter
+///
+/// # Arguments
+///
+/// - `e` The runtime environment.
+/// - `constellation_token_id` Constellation token trading its components
+/// - `adapter_id` Adapter of the exchange
+/// - `exchange_id` Exchange swapped on
+/// - `path` Tokens swapped through, from the token sold to the token bought
+/// - `amount_in` Amount of the first token of `path` sold
+/// - `amount_out_min` Minimum amount of the last token of `path` received
+/// - `deadline` Swap deadline
+/// - `expiration_ledger` Expiration ledger of the exchange approval
+#[allow(clippy::too_many_arguments)]
+pub fn swap(
+    e: &Env,
+    constellation_token_id: &Address,
+    adapter_id: &Address,
+    exchange_id: &Address,
+    path: &Vec<Address>,
+    amount_in: i128,
+    amount_out_min: i128,
+    deadline: u64,
+    expiration_ledger: u32,
+) {
+    let exchange_adapter = dex::Client::new(e, adapter_id);
+
+    let approve_call_data = exchange_adapter.get_approve_call_data(
+        constellation_token_id,
+        exchange_id,
+        &amount_in,
+        &expiration_ledger,
+    );
+    invoke(
+        e,
+        constellation_token_id,
+        &path.get_unchecked(0),
+        &approve_call_data,
+        &vec![e],
+    );
+
+    let swap_call_data = exchange_adapter.get_path_swap_call_data(
+        path,
+        &amount_in,
+        &amount_out_min,
+        constellation_token_id,
+        &deadline,
+    );
+    // the exchange only pulls the first token of the path from the constellation token
+    let auth_entries =
+        exchange_adapter.create_path_sub_auth(&amount_in, path, constellation_token_id);
+    invoke(
+        e,
+        constellation_token_id,
+        exchange_id,
+        &swap_call_data,
+        &auth_entries,
+    );
+}
@@ -0,0 +1,52 @@
+use crate::math::{mul_div, unit_scale, Rounding};
+use crate::traits::oracle;
+use crate::types::oracle::{Asset, OracleConfig};
+use soroban_sdk::{Address, Env};
+
+/// Decimals of the prices returned by `fresh_price`, whatever the decimals of the feed
+pub const PRICE_DECIMALS: u32 = 7;
+
+/// Returns the price of the Stellar asset `token_id` quoted by `oracle_id`,
+/// rescaled from the decimals of the feed to `PRICE_DECIMALS`
+/// Returns None if the feed does not quote the token, or if its price is older than `max_age` seconds
+pub fn fresh_price(e: &Env, oracle_id: &Address, token_id: &Address, max_age: u64) -> Option<i128> {
+    let client = oracle::Client::new(e, oracle_id);
+    let price = client.lastprice(&Asset::Stellar(token_id.clone()))?;
+    if e.ledger().timestamp().saturating_sub(price.timestamp) > max_age {
+        return None;
+    }
+    let price = normalize_price(price.price, client.decimals())?;
+    if price <= 0 {
+        return None;
+    }
+    Some(price)
+}
+
+/// Rescales `price` quoted with `decimals` decimals to `PRICE_DECIMALS`, rounding down
+/// Returns None on overflow
+fn normalize_price(price: i128, decimals: u32) -> Option<i128> {
+    if decimals >= PRICE_DECIMALS {
+        price.checked_div(unit_scale(decimals - PRICE_DECIMALS)?)
+    } else {
+        price.checked_mul(unit_scale(PRICE_DECIMALS - decimals)?)
+    }
+}
+
+/// Returns the price of `token_id` quoted by the feed of `config`, if it is not stale
+pub fn config_price(e: &Env, config: &OracleConfig, token_id: &Address) -> Option<i128> {
+    fresh_price(e, &config.oracle_id, token_id, config.max_price_age)
+}
+
+/// Returns the value of `amount` base units of a token with `decimals` decimals,
+/// priced at `price` per whole token
+/// Rounded down; returns None on overflow
+pub fn asset_value(amount: i128, price: i128, decimals: u32) -> Option<i128> {
+    mul_div(amount, price, unit_scale(decimals)?, Rounding::Down)
+}
+
+/// Returns the amount of base units of a token with `decimals` decimals worth `value`,
+/// priced at `price` per whole token
+/// Rounded down; returns None on overflow or when `price` is not positive
+pub fn value_amount(value: i128, price: i128, decimals: u32) -> Option<i128> {
+    mul_div(value, unit_scale(decimals)?, price, Rounding::Down)
+}
@@ -0,0 +1,105 @@
+use soroban_sdk::{contracttype, vec, Address, Env, IntoVal, TryFromVal, Val, Vec};
+
+const DAY_IN_LEDGERS: u32 = 17280;
+const PERSISTENT_LEDGER_LIFE: u32 = 90 * DAY_IN_LEDGERS; // ~90 days.
+const PERSISTENT_LEDGER_TTL_THRESHOLD: u32 = PERSISTENT_LEDGER_LIFE - DAY_IN_LEDGERS;
+
+/// Maximum number of open orders a constellation token keeps on a module
+pub const MAX_OPEN_ORDERS: u32 = 20;
+
+#[derive(Clone)]
+#[contracttype(export = false)]
+enum DataKey {
+    Order(Address, u32),
+    OrderCount(Address),
+    OpenOrders(Address),
+}
+
+fn extend_ttl(e: &Env, key: &DataKey) {
+    e.storage().persistent().extend_ttl(
+        key,
+        PERSISTENT_LEDGER_TTL_THRESHOLD,
+        PERSISTENT_LEDGER_LIFE,
+    );
+}
+
+pub fn read_order<T: TryFromVal<Env, Val>>(
+    e: &Env,
+    constellation_token_id: &Address,
+    order_id: u32,
+) -> Option<T> {
+    let key = DataKey::Order(constellation_token_id.clone(), order_id);
+    let order = e.storage().persistent().get(&key);
+    if order.is_some() {
+        extend_ttl(e, &key);
+    }
+    order
+}
+
+/// Updates an open order of `constellation_token_id`
+pub fn write_order<T: IntoVal<Env, Val>>(
+    e: &Env,
+    constellation_token_id: &Address,
+    order_id: u32,
+    order: &T,
+) {
+    let key = DataKey::Order(constellation_token_id.clone(), order_id);
+    e.storage().persistent().set(&key, order);
+    extend_ttl(e, &key);
+}
+
+/// Returns the ids of the open orders of `constellation_token_id`
+pub fn read_open_orders(e: &Env, constellation_token_id: &Address) -> Vec<u32> {
+    let key = DataKey::OpenOrders(constellation_token_id.clone());
+    e.storage().persistent().get(&key).unwrap_or(vec![e])
+}
+
+fn write_open_orders(e: &Env, constellation_token_id: &Address, order_ids: &Vec<u32>) {
+    let key = DataKey::OpenOrders(constellation_token_id.clone());
+    e.storage().persistent().set(&key, order_ids);
+    extend_ttl(e, &key);
+}
+
+/// Stores `order` under the next id of `constellation_token_id` and returns the id
+/// Returns None if the constellation token already has `MAX_OPEN_ORDERS` open orders
+pub fn add_order<T: IntoVal<Env, Val>>(
+    e: &Env,
+    constellation_token_id: &Address,
+    order: &T,
+) -> Option<u32> {
+    let mut open_orders = read_open_orders(e, constellation_token_id);
+    if open_orders.len() >= MAX_OPEN_ORDERS {
+        return None;
+    }
+
+    let key = DataKey::OrderCount(constellation_token_id.clone());
+    let order_id: u32 = e.storage().persistent().get(&key).unwrap_or(0);
+    e.storage().persistent().set(&key, &(order_id + 1));
+    extend_ttl(e, &key);
+
+    write_order(e, constellation_token_id, order_id, order);
+    open_orders.push_back(order_id);
+    write_open_orders(e, constellation_token_id, &open_orders);
+    Some(order_id)
+}
+
+pub fn remove_order(e: &Env, constellation_token_id: &Address, order_id: u32) {
+    let key = DataKey::Order(constellation_token_id.clone(), order_id);
+    e.storage().persistent().remove(&key);
+
+    let mut open_orders = read_open_orders(e, constellation_token_id);
+    if let Some(index) = open_orders.first_index_of(order_id) {
+        open_orders.remove(index);
+        write_open_orders(e, constellation_token_id, &open_orders);
+    }
+}
+
+/// Removes the open orders of `constellation_token_id`, order ids keep increasing
+pub fn remove_orders(e: &Env, constellation_token_id: &Address) {
+    for order_id in read_open_orders(e, constellation_token_id).iter() {
+        let key = DataKey::Order(constellation_token_id.clone(), order_id);
+        e.storage().persistent().remove(&key);
+    }
+    let key = DataKey::OpenOrders(constellation_token_id.clone());
+    e.storage().persistent().remove(&key);
+}
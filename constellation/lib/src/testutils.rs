@@ -0,0 +1,52 @@
+use crate::traits::oracle::PriceFeed;
+use crate::types::oracle::{Asset, PriceData};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol};
+
+#[derive(Clone)]
+#[contracttype]
+enum DataKey {
+    Price(Address),
+    Decimals,
+}
+
+/// SEP-40 price feed quoting the prices set by the test in USD, with 7 decimals unless set otherwise
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    /// Sets the price of `token` at the current ledger timestamp
+    pub fn set_price(e: Env, token: Address, price: i128) {
+        let timestamp = e.ledger().timestamp();
+        Self::set_price_at(e, token, price, timestamp);
+    }
+
+    /// Sets the price of `token` recorded at `timestamp`
+    pub fn set_price_at(e: Env, token: Address, price: i128, timestamp: u64) {
+        let data = PriceData { price, timestamp };
+        e.storage().instance().set(&DataKey::Price(token), &data);
+    }
+
+    /// Sets the number of decimals of the quoted prices
+    pub fn set_decimals(e: Env, decimals: u32) {
+        e.storage().instance().set(&DataKey::Decimals, &decimals);
+    }
+}
+
+#[contractimpl]
+impl PriceFeed for MockOracle {
+    fn base(e: Env) -> Asset {
+        Asset::Other(Symbol::new(&e, "USD"))
+    }
+
+    fn decimals(e: Env) -> u32 {
+        e.storage().instance().get(&DataKey::Decimals).unwrap_or(7)
+    }
+
+    fn lastprice(e: Env, asset: Asset) -> Option<PriceData> {
+        match asset {
+            Asset::Stellar(token) => e.storage().instance().get(&DataKey::Price(token)),
+            Asset::Other(_) => None,
+        }
+    }
+}
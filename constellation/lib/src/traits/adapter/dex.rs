@@ -34,6 +34,44 @@ pub trait IExchange {
         token_out: Address,
         constellation_token_id: Address,
     ) -> Vec<InvokerContractAuthEntry>;
+
+    /// Returns the amounts received at each step of `path` when swapping `amount_in` of its first token
+    fn get_path_amounts_out(e: &Env, amount_in: i128, path: Vec<Address>) -> Vec<i128>;
+
+    /// Returns the amounts required at each step of `path` to receive `amount_out` of its last token
+    fn get_path_amounts_in(e: &Env, amount_out: i128, path: Vec<Address>) -> Vec<i128>;
+
+    /// Call data swapping exactly `amount_in` of the first token of `path`
+    /// for at least `amount_out_min` of its last token
+    fn get_path_swap_call_data(
+        e: &Env,
+        path: Vec<Address>,
+        amount_in: i128,
+        amount_out_min: i128,
+        to: Address,
+        deadline: u64,
+    ) -> (Symbol, Vec<Val>);
+
+    /// Call data swapping at most `amount_in_max` of the first token of `path`
+    /// for exactly `amount_out` of its last token
+    fn get_path_swap_out_call_data(
+        e: &Env,
+        path: Vec<Address>,
+        amount_out: i128,
+        amount_in_max: i128,
+        to: Address,
+        deadline: u64,
+    ) -> (Symbol, Vec<Val>);
+
+    /// Authorization entries `from` must sign for the exchange to move `amount_in`
+    /// of the first token of `path`
+    /// For exact output swaps, `amount_in` is the amount the exchange pulls, as quoted by `get_path_amounts_in`
+    fn create_path_sub_auth(
+        e: &Env,
+        amount_in: i128,
+        path: Vec<Address>,
+        from: Address,
+    ) -> Vec<InvokerContractAuthEntry>;
 }
 
 /// Spec contains the contract spec of iExchange contracts, including the general
@@ -46,9 +84,25 @@ pub(crate) const SPEC_XDR_INPUT: &[&[u8]] = &[
     &IExchangeSpec::spec_xdr_get_swap_call_data(),
     &IExchangeSpec::spec_xdr_get_approve_call_data(),
     &IExchangeSpec::spec_xdr_create_sub_auth(),
+    &IExchangeSpec::spec_xdr_get_path_amounts_out(),
+    &IExchangeSpec::spec_xdr_get_path_amounts_in(),
+    &IExchangeSpec::spec_xdr_get_path_swap_call_data(),
+    &IExchangeSpec::spec_xdr_get_path_swap_out_call_data(),
+    &IExchangeSpec::spec_xdr_create_path_sub_auth(),
 ];
 
-pub(crate) const SPEC_XDR_LEN: usize = 5336;
+pub(crate) const SPEC_XDR_LEN: usize = spec_xdr_len(SPEC_XDR_INPUT);
+
+/// Returns the total length of the XDR of the items of the spec
+const fn spec_xdr_len(input: &[&[u8]]) -> usize {
+    let mut len = 0;
+    let mut input_i = 0;
+    while input_i < input.len() {
+        len += input[input_i].len();
+        input_i += 1;
+    }
+    len
+}
 
 impl IExchangeSpec {
     /// Returns the XDR spec for the Token contract.
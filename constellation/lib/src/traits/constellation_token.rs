@@ -46,3 +46,34 @@
 //         auth_entries: Vec<InvokerContractAuthEntry>,
 //     ) -> Result<(), Error>;
 // }
+
+use soroban_sdk::auth::InvokerContractAuthEntry;
+use soroban_sdk::{contractclient, Address, Env, Symbol, Val, Vec};
+
+pub use ConstellationTokenModuleClient as ModuleClient;
+
+/// Functions of the constellation token its modules call
+#[contractclient(name = "ConstellationTokenModuleClient")]
+pub trait ConstellationTokenModule {
+    fn get_manager(e: Env) -> Option<Address>;
+
+    fn total_supply(e: Env) -> i128;
+
+    fn decimals(e: Env) -> u32;
+
+    fn snapshot_balances(e: Env, module_id: Address, tokens: Vec<Address>);
+
+    fn update_units(e: Env, module_id: Address);
+
+    fn initialize_module(e: Env, module_id: Address);
+
+    fn mint_fee(e: Env, module_id: Address, to: Address, amount: i128);
+
+    fn invoke(
+        e: Env,
+        module_id: Address,
+        target_id: Address,
+        call_data: (Symbol, Vec<Val>),
+        auth_entries: Vec<InvokerContractAuthEntry>,
+    );
+}
@@ -0,0 +1,10 @@
+use soroban_sdk::{contractclient, Env};
+
+pub use FactoryClient as Client;
+
+/// Factory queries constellation tokens make
+#[contractclient(name = "FactoryClient")]
+pub trait Factory {
+    /// Returns the maximum number of components of a constellation token, if set
+    fn get_max_components(e: Env) -> Option<u32>;
+}
@@ -1,2 +1,6 @@
 pub mod adapter;
 pub mod constellation_token;
+pub mod factory;
+pub mod module;
+pub mod oracle;
+pub mod registry;
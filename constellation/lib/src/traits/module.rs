@@ -0,0 +1,11 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+pub use ModuleHooksClient as Client;
+
+/// Callbacks the constellation token makes to its modules
+#[contractclient(name = "ModuleHooksClient")]
+pub trait ModuleHooks {
+    /// Called by the constellation token once the module is removed from it,
+    /// so the module can clear the state it keeps for the token
+    fn on_remove(e: Env, constellation_token_id: Address);
+}
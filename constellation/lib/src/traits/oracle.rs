@@ -0,0 +1,17 @@
+use crate::types::oracle::{Asset, PriceData};
+use soroban_sdk::{contractclient, Env};
+
+pub use PriceFeedClient as Client;
+
+/// Subset of the SEP-40 price feed interface, as implemented by Reflector
+#[contractclient(name = "PriceFeedClient")]
+pub trait PriceFeed {
+    /// Returns the asset prices are quoted in
+    fn base(e: Env) -> Asset;
+
+    /// Returns the number of decimals of the quoted prices
+    fn decimals(e: Env) -> u32;
+
+    /// Returns the most recent price of `asset`, if the feed quotes it
+    fn lastprice(e: Env, asset: Asset) -> Option<PriceData>;
+}
@@ -0,0 +1,17 @@
+use crate::types::oracle::OracleConfig;
+use soroban_sdk::{contractclient, Address, Env};
+
+pub use RegistryClient as Client;
+
+/// Registry queries the modules of constellation tokens make
+#[contractclient(name = "RegistryClient")]
+pub trait Registry {
+    /// Returns the adapter registered for `module_id` to reach `target_id`
+    fn get_adapter_id(e: Env, module_id: Address, target_id: Address) -> Option<Address>;
+
+    /// Returns the protocol price feed, which managers of constellation tokens cannot change
+    fn get_oracle(e: Env) -> Option<OracleConfig>;
+
+    /// Returns the protocol ceiling on the deviation of trade rates from oracle prices, in basis points
+    fn get_max_price_deviation(e: Env) -> Option<u32>;
+}
@@ -1,7 +1,7 @@
 use soroban_sdk::{contracttype, Address};
 
 #[derive(Clone, Debug)]
-#[contracttype]
+#[contracttype(export = false)]
 pub struct Component {
     pub address: Address,
     pub unit: i128,
@@ -0,0 +1,18 @@
+use soroban_sdk::{contracttype, Address};
+
+/// Exchange and the adapter a module reaches it through
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Venue {
+    pub exchange_id: Address,
+    pub adapter_id: Address,
+}
+
+/// Part of a swap routed to one exchange
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SwapLeg {
+    pub exchange_id: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+}
@@ -1 +1,3 @@
 pub mod constellation_token;
+pub mod exchange;
+pub mod oracle;
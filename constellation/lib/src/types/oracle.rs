@@ -0,0 +1,28 @@
+use soroban_sdk::{contracttype, Address, Symbol};
+
+/// Asset quoted by a SEP-40 price feed
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// Price of an asset in the base asset of the feed, scaled by the feed decimals
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PriceData {
+    pub price: i128,
+    /// Ledger timestamp the price was recorded at
+    pub timestamp: u64,
+}
+
+/// Price feed used to value the components of a constellation token
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct OracleConfig {
+    /// SEP-40 price feed
+    pub oracle_id: Address,
+    /// Maximum age of a price, in seconds
+    pub max_price_age: u64,
+}
@@ -0,0 +1,256 @@
+use crate::error::Error;
+use crate::event;
+use crate::helpers::{bid_amount_in, price_at};
+use crate::storage::auction::{read_auction, remove_auction, write_auction};
+use crate::storage::types::{Auction, AuctionComponent};
+use crate::validation::{require_auction, require_component, require_components};
+use constellation_lib::math::{component_quantity, Rounding};
+use constellation_lib::module::{
+    decimals, initialize_module, invoke, require_manager, snapshot_balances, total_supply,
+    update_units,
+};
+use constellation_lib::traits::module::ModuleHooks;
+use soroban_sdk::token::TokenClient;
+use soroban_sdk::{contract, contractimpl, vec, Address, Env, IntoVal, Symbol, Val, Vec};
+
+#[contract]
+pub struct AuctionRebalance {}
+
+#[contractimpl]
+impl AuctionRebalance {
+    /// Activates the module on a constellation token it was added to
+    /// Auctions carry their own settings, the module keeps none for the token
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token rebalanced
+    pub fn initialize_token(e: Env, constellation_token_id: Address) -> Result<(), Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        initialize_module(&e, &constellation_token_id);
+        Ok(())
+    }
+
+    /// Starts an auction moving the units of a constellation token towards target units,
+    /// replacing any auction in progress
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token rebalanced
+    /// - `components` Target unit and price curve of each component traded
+    /// - `duration_ledgers` Number of ledgers the prices move from their start to their end price over
+    pub fn start_auction(
+        e: Env,
+        constellation_token_id: Address,
+        components: Vec<AuctionComponent>,
+        duration_ledgers: u32,
+    ) -> Result<(), Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        require_components(&components)?;
+        if duration_ledgers == 0 {
+            return Err(Error::InvalidAuction);
+        }
+
+        let start_ledger = e.ledger().sequence();
+        let end_ledger = start_ledger
+            .checked_add(duration_ledgers)
+            .ok_or(Error::InvalidAuction)?;
+        write_auction(
+            &e,
+            &constellation_token_id,
+            &Auction {
+                components: components.clone(),
+                start_ledger,
+                end_ledger,
+            },
+        );
+        event::start_auction(
+            &e,
+            constellation_token_id,
+            components,
+            start_ledger,
+            end_ledger,
+        );
+        Ok(())
+    }
+
+    /// Ends the auction of a constellation token
+    pub fn end_auction(e: Env, constellation_token_id: Address) -> Result<(), Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        require_auction(&e, &constellation_token_id)?;
+        remove_auction(&e, &constellation_token_id);
+        event::end_auction(&e, constellation_token_id);
+        Ok(())
+    }
+
+    pub fn get_auction(e: Env, constellation_token_id: Address) -> Option<Auction> {
+        read_auction(&e, &constellation_token_id)
+    }
+
+    /// Returns the amount of `token_in_id` a bid for `amount_out` of `token_out_id` pays at the current ledger
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token rebalanced
+    /// - `token_in_id` Component the constellation token buys
+    /// - `token_out_id` Component the constellation token sells
+    /// - `amount_out` Amount of `token_out_id` bought by the bidder
+    pub fn quote_bid(
+        e: Env,
+        constellation_token_id: Address,
+        token_in_id: Address,
+        token_out_id: Address,
+        amount_out: i128,
+    ) -> Result<i128, Error> {
+        Self::price_bid(
+            &e,
+            &constellation_token_id,
+            &token_in_id,
+            &token_out_id,
+            amount_out,
+        )
+    }
+
+    /// Sells `amount_out` of `token_out_id` held by the constellation token to `bidder`
+    /// for `token_in_id` at the current auction prices, and updates the units
+    /// Returns the amount of `token_in_id` paid by the bidder
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `bidder` Address paying `token_in_id` and receiving `token_out_id`
+    /// - `constellation_token_id` Constellation token rebalanced
+    /// - `token_in_id` Component the constellation token buys
+    /// - `token_out_id` Component the constellation token sells
+    /// - `amount_out` Amount of `token_out_id` bought by the bidder
+    /// - `amount_in_max` Maximum amount of `token_in_id` paid by the bidder
+    ///
+    /// Returns error if the auction has ended, if the bid moves a component past its target unit,
+    /// or if it costs more than `amount_in_max`
+    pub fn bid(
+        e: Env,
+        bidder: Address,
+        constellation_token_id: Address,
+        token_in_id: Address,
+        token_out_id: Address,
+        amount_out: i128,
+        amount_in_max: i128,
+    ) -> Result<i128, Error> {
+        bidder.require_auth();
+
+        let amount_in = Self::price_bid(
+            &e,
+            &constellation_token_id,
+            &token_in_id,
+            &token_out_id,
+            amount_out,
+        )?;
+        if amount_in > amount_in_max {
+            return Err(Error::ExcessiveInputAmount);
+        }
+
+        snapshot_balances(
+            &e,
+            &constellation_token_id,
+            &vec![&e, token_in_id.clone(), token_out_id.clone()],
+        );
+
+        TokenClient::new(&e, &token_in_id).transfer(&bidder, &constellation_token_id, &amount_in);
+
+        // the component sold leaves through the constellation token itself
+        let args: Vec<Val> = vec![
+            &e,
+            constellation_token_id.into_val(&e),
+            bidder.into_val(&e),
+            amount_out.into_val(&e),
+        ];
+        invoke(
+            &e,
+            &constellation_token_id,
+            &token_out_id,
+            &(Symbol::new(&e, "transfer"), args),
+            &vec![&e],
+        );
+
+        update_units(&e, &constellation_token_id);
+
+        event::bid(
+            &e,
+            constellation_token_id,
+            bidder,
+            token_in_id,
+            token_out_id,
+            amount_in,
+            amount_out,
+        );
+        Ok(amount_in)
+    }
+
+    /// Returns the amount of `token_in_id` paid for `amount_out` of `token_out_id`
+    /// Returns error if the auction has ended, or if the bid moves a component past its target unit
+    fn price_bid(
+        e: &Env,
+        constellation_token_id: &Address,
+        token_in_id: &Address,
+        token_out_id: &Address,
+        amount_out: i128,
+    ) -> Result<i128, Error> {
+        let auction = require_auction(e, constellation_token_id)?;
+        let ledger = e.ledger().sequence();
+        if ledger > auction.end_ledger {
+            return Err(Error::AuctionEnded);
+        }
+        if amount_out <= 0 || token_in_id == token_out_id {
+            return Err(Error::InvalidBidAmount);
+        }
+        let component_in = require_component(&auction, token_in_id)?;
+        let component_out = require_component(&auction, token_out_id)?;
+
+        let amount_in = bid_amount_in(
+            amount_out,
+            price_at(&auction, &component_out, ledger)?,
+            price_at(&auction, &component_in, ledger)?,
+        )?;
+
+        let supply = total_supply(e, constellation_token_id);
+        let decimals = decimals(e, constellation_token_id);
+        // targets are rounded up, the constellation token never sells below its target
+        let target_out =
+            component_quantity(supply, component_out.target_unit, decimals, Rounding::Up)
+                .ok_or(Error::ValueTooLargeOverFlow)?;
+        let target_in =
+            component_quantity(supply, component_in.target_unit, decimals, Rounding::Up)
+                .ok_or(Error::ValueTooLargeOverFlow)?;
+        let balance_out = TokenClient::new(e, token_out_id).balance(constellation_token_id);
+        let balance_in = TokenClient::new(e, token_in_id).balance(constellation_token_id);
+        if amount_out > balance_out - target_out || amount_in > target_in - balance_in {
+            return Err(Error::BidExceedsTarget);
+        }
+        Ok(amount_in)
+    }
+}
+
+#[contractimpl]
+impl ModuleHooks for AuctionRebalance {
+    /// Ends the auction of a constellation token the module was removed from
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token the module was removed from
+    fn on_remove(e: Env, constellation_token_id: Address) {
+        constellation_token_id.require_auth();
+        if read_auction(&e, &constellation_token_id).is_some() {
+            remove_auction(&e, &constellation_token_id);
+            event::end_auction(&e, constellation_token_id);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use constellation_lib::module::ModuleError;
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    RequiresManage = 101,
+    RequiresAuction = 102,
+    InvalidAuction = 103,
+    AuctionEnded = 104,
+    NotInAuction = 105,
+    BidExceedsTarget = 106,
+    InvalidBidAmount = 107,
+    ExcessiveInputAmount = 108,
+    ValueTooLargeOverFlow = 109,
+}
+
+impl From<ModuleError> for Error {
+    fn from(error: ModuleError) -> Self {
+        match error {
+            // auctions settle against bidders, the module neither reads a registry nor trades
+            ModuleError::RequiresManage
+            | ModuleError::RequiresRegistry
+            | ModuleError::RequiresExchangeAdapter
+            | ModuleError::PairNotFound
+            | ModuleError::RequiresOracle
+            | ModuleError::StalePrice => Error::RequiresManage,
+            ModuleError::ValueTooLargeOverFlow => Error::ValueTooLargeOverFlow,
+        }
+    }
+}
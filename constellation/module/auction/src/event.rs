@@ -0,0 +1,75 @@
+use crate::storage::types::AuctionComponent;
+use soroban_sdk::{contracttype, Address, Env, Symbol, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StartAuction {
+    constellation_token_id: Address,
+    components: Vec<AuctionComponent>,
+    start_ledger: u32,
+    end_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bid {
+    constellation_token_id: Address,
+    bidder: Address,
+    token_in_id: Address,
+    token_out_id: Address,
+    amount_in: i128,
+    amount_out: i128,
+}
+
+pub(crate) fn start_auction(
+    e: &Env,
+    constellation_token_id: Address,
+    components: Vec<AuctionComponent>,
+    start_ledger: u32,
+    end_ledger: u32,
+) {
+    let topics = (
+        Symbol::new(e, "start_auction"),
+        constellation_token_id.clone(),
+    );
+    e.events().publish(
+        topics,
+        StartAuction {
+            constellation_token_id,
+            components,
+            start_ledger,
+            end_ledger,
+        },
+    );
+}
+
+pub(crate) fn end_auction(e: &Env, constellation_token_id: Address) {
+    let topics = (
+        Symbol::new(e, "end_auction"),
+        constellation_token_id.clone(),
+    );
+    e.events().publish(topics, constellation_token_id);
+}
+
+pub(crate) fn bid(
+    e: &Env,
+    constellation_token_id: Address,
+    bidder: Address,
+    token_in_id: Address,
+    token_out_id: Address,
+    amount_in: i128,
+    amount_out: i128,
+) {
+    let topics = (Symbol::new(e, "bid"), constellation_token_id.clone());
+    e.events().publish(
+        topics,
+        Bid {
+            constellation_token_id,
+            bidder,
+            token_in_id,
+            token_out_id,
+            amount_in,
+            amount_out,
+        },
+    );
+}
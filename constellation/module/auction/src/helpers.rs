@@ -0,0 +1,28 @@
+use crate::error::Error;
+use crate::storage::types::{Auction, AuctionComponent};
+use constellation_lib::math::{mul_div, Rounding};
+
+/// Returns the price of `component` at `ledger`, moving linearly from its start price
+/// to its end price over the auction
+pub fn price_at(
+    auction: &Auction,
+    component: &AuctionComponent,
+    ledger: u32,
+) -> Result<i128, Error> {
+    let duration = (auction.end_ledger - auction.start_ledger) as i128;
+    let elapsed = ledger.clamp(auction.start_ledger, auction.end_ledger) - auction.start_ledger;
+    let change = mul_div(
+        component.end_price - component.start_price,
+        elapsed as i128,
+        duration,
+        Rounding::Down,
+    )
+    .ok_or(Error::ValueTooLargeOverFlow)?;
+    Ok(component.start_price + change)
+}
+
+/// Returns the amount of the component priced at `price_in` a bidder pays
+/// for `amount_out` of the component priced at `price_out`, rounded up in favour of the fund
+pub fn bid_amount_in(amount_out: i128, price_out: i128, price_in: i128) -> Result<i128, Error> {
+    mul_div(amount_out, price_out, price_in, Rounding::Up).ok_or(Error::ValueTooLargeOverFlow)
+}
@@ -0,0 +1,10 @@
+#![no_std]
+pub mod contract;
+mod error;
+mod event;
+mod helpers;
+mod storage;
+mod validation;
+
+#[cfg(test)]
+mod test;
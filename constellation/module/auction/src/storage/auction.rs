@@ -0,0 +1,32 @@
+use soroban_sdk::{Address, Env};
+
+use super::keys::DataKey;
+use super::types::{Auction, PERSISTENT_LEDGER_LIFE, PERSISTENT_LEDGER_TTL_THRESHOLD};
+
+pub fn read_auction(e: &Env, constellation_token_id: &Address) -> Option<Auction> {
+    let key = DataKey::Auction(constellation_token_id.clone());
+    let auction = e.storage().persistent().get(&key);
+    if auction.is_some() {
+        e.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LEDGER_TTL_THRESHOLD,
+            PERSISTENT_LEDGER_LIFE,
+        );
+    }
+    auction
+}
+
+pub fn write_auction(e: &Env, constellation_token_id: &Address, auction: &Auction) {
+    let key = DataKey::Auction(constellation_token_id.clone());
+    e.storage().persistent().set(&key, auction);
+    e.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LEDGER_TTL_THRESHOLD,
+        PERSISTENT_LEDGER_LIFE,
+    );
+}
+
+pub fn remove_auction(e: &Env, constellation_token_id: &Address) {
+    let key = DataKey::Auction(constellation_token_id.clone());
+    e.storage().persistent().remove(&key);
+}
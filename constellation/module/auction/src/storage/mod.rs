@@ -0,0 +1,3 @@
+pub mod auction;
+pub mod keys;
+pub mod types;
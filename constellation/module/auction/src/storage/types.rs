@@ -0,0 +1,27 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+pub(crate) const DAY_IN_LEDGERS: u32 = 17280;
+pub(crate) const PERSISTENT_LEDGER_LIFE: u32 = 90 * DAY_IN_LEDGERS; // ~90 days.
+pub(crate) const PERSISTENT_LEDGER_TTL_THRESHOLD: u32 = PERSISTENT_LEDGER_LIFE - DAY_IN_LEDGERS;
+
+/// Component traded by an auction, with the price curve bids are settled at
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AuctionComponent {
+    pub token_id: Address,
+    /// Unit the auction moves the component towards, per whole constellation token
+    pub target_unit: i128,
+    /// Price of one base unit of the component when the auction starts, in a numeraire shared by the auction
+    pub start_price: i128,
+    /// Price of one base unit of the component when the auction ends
+    pub end_price: i128,
+}
+
+/// Auction rebalancing a constellation token towards target units
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Auction {
+    pub components: Vec<AuctionComponent>,
+    pub start_ledger: u32,
+    pub end_ledger: u32,
+}
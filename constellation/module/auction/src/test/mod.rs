@@ -0,0 +1,3 @@
+mod clients;
+mod setup;
+mod test;
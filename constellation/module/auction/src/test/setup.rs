@@ -0,0 +1,71 @@
+use super::clients::{
+    create_auction_module, create_constellation_token, create_registry, create_token_contract,
+    ConstellationTokenClient, TokenClient,
+};
+use crate::contract::AuctionRebalanceClient;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, IntoVal};
+
+/// Constellation tokens minted, backed by 10_000 base units of each component
+pub const SUPPLY: i128 = 10;
+pub const UNIT: i128 = 1_000 * 1_000_000;
+/// Auction price of one base unit of a component
+pub const PRICE: i128 = 10_000_000;
+
+pub struct AuctionTest<'a> {
+    pub env: Env,
+    pub bidder: Address,
+    pub constellation_token: ConstellationTokenClient<'a>,
+    pub auction_module: AuctionRebalanceClient<'a>,
+    pub tokens: (TokenClient<'a>, TokenClient<'a>, TokenClient<'a>),
+}
+
+impl<'a> AuctionTest<'a> {
+    pub fn setup() -> AuctionTest<'a> {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.budget().reset_unlimited();
+        let user = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let manager = Address::generate(&env);
+        let bidder = Address::generate(&env);
+        let registry = create_registry(&env);
+        let constellation_token = create_constellation_token(&env);
+        let auction_module = create_auction_module(&env);
+        let tokens = (
+            create_token_contract(&env, &admin),
+            create_token_contract(&env, &admin),
+            create_token_contract(&env, &admin),
+        );
+
+        constellation_token.initialize(
+            &6u32,
+            &vec![&env, tokens.0.address.clone(), tokens.1.address.clone()],
+            &vec![&env, UNIT, UNIT],
+            &"c_token".into_val(&env),
+            &"token_symbol".into_val(&env),
+            &user,
+            &manager,
+        );
+        for token in [&tokens.0, &tokens.1] {
+            token.mint(&user, &10_000);
+            token.approve(&user, &constellation_token.address, &10_000, &1000u32);
+        }
+        constellation_token.mint(&user, &SUPPLY);
+        tokens.1.mint(&bidder, &100_000);
+        tokens.2.mint(&bidder, &100_000);
+
+        registry.initialize(&admin);
+        registry.add_module(&auction_module.address);
+        constellation_token.set_registry(&registry.address);
+        constellation_token.add_module(&auction_module.address, &None);
+        auction_module.initialize_token(&constellation_token.address);
+
+        Self {
+            env,
+            bidder,
+            constellation_token,
+            auction_module,
+            tokens,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use super::setup::{AuctionTest, PRICE, UNIT};
+use crate::error::Error;
+use crate::storage::types::AuctionComponent;
+use soroban_sdk::{testutils::Ledger, vec, Address};
+
+fn component(
+    token_id: &Address,
+    target_unit: i128,
+    start_price: i128,
+    end_price: i128,
+) -> AuctionComponent {
+    AuctionComponent {
+        token_id: token_id.clone(),
+        target_unit,
+        start_price,
+        end_price,
+    }
+}
+
+#[test]
+fn start_auction_fails_with_invalid_auction() {
+    let test = AuctionTest::setup();
+    let (t0, t1) = (&test.tokens.0.address, &test.tokens.1.address);
+    let ct = &test.constellation_token.address;
+
+    let result = test.auction_module.try_start_auction(
+        ct,
+        &vec![
+            &test.env,
+            component(t0, UNIT, PRICE, 0),
+            component(t1, UNIT, PRICE, PRICE),
+        ],
+        &100,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidAuction)));
+
+    let result = test.auction_module.try_start_auction(
+        ct,
+        &vec![
+            &test.env,
+            component(t0, UNIT, PRICE, PRICE),
+            component(t0, UNIT, PRICE, PRICE),
+        ],
+        &100,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidAuction)));
+
+    let result = test.auction_module.try_quote_bid(ct, t1, t0, &1_000);
+    assert_eq!(result, Err(Ok(Error::RequiresAuction)));
+}
+
+#[test]
+fn bid_settles_at_decaying_price_and_updates_units() {
+    let test = AuctionTest::setup();
+    let (t0, t1) = (&test.tokens.0.address, &test.tokens.1.address);
+    let ct = &test.constellation_token;
+    let bidder = &test.bidder;
+
+    // sell half of token 0 for token 1, token 0 starts 20% above token 1 and ends 20% below
+    test.auction_module.start_auction(
+        &ct.address,
+        &vec![
+            &test.env,
+            component(t0, UNIT / 2, PRICE * 12 / 10, PRICE * 8 / 10),
+            component(t1, UNIT * 3 / 2, PRICE, PRICE),
+        ],
+        &100,
+    );
+
+    let amount_in = test
+        .auction_module
+        .bid(bidder, &ct.address, t1, t0, &1_000, &1_200);
+    assert_eq!(amount_in, 1_200);
+    assert_eq!(test.tokens.0.balance(bidder), 1_000);
+    assert_eq!(test.tokens.0.balance(&ct.address), 9_000);
+    assert_eq!(test.tokens.1.balance(&ct.address), 11_200);
+    assert_eq!(ct.get_component(t0).unwrap().unit, UNIT * 9 / 10);
+    assert_eq!(ct.get_component(t1).unwrap().unit, UNIT * 112 / 100);
+
+    // halfway through, both components trade at par
+    test.env.ledger().with_mut(|li| li.sequence_number += 50);
+    assert_eq!(
+        test.auction_module.quote_bid(&ct.address, t1, t0, &1_000),
+        1_000
+    );
+    let result = test
+        .auction_module
+        .try_bid(bidder, &ct.address, t1, t0, &1_000, &999);
+    assert_eq!(result, Err(Ok(Error::ExcessiveInputAmount)));
+
+    // the constellation token only sells token 0 down to its target
+    let result = test
+        .auction_module
+        .try_bid(bidder, &ct.address, t1, t0, &4_001, &4_001);
+    assert_eq!(result, Err(Ok(Error::BidExceedsTarget)));
+    let result = test
+        .auction_module
+        .try_bid(bidder, &ct.address, t0, t1, &1_000, &1_000);
+    assert_eq!(result, Err(Ok(Error::BidExceedsTarget)));
+    let result = test.auction_module.try_bid(
+        bidder,
+        &ct.address,
+        &test.tokens.2.address,
+        t0,
+        &1_000,
+        &1_000,
+    );
+    assert_eq!(result, Err(Ok(Error::NotInAuction)));
+
+    test.auction_module
+        .bid(bidder, &ct.address, t1, t0, &3_800, &3_800);
+    assert_eq!(test.tokens.0.balance(&ct.address), 5_200);
+    assert_eq!(test.tokens.1.balance(&ct.address), 15_000);
+    assert_eq!(ct.get_component(t1).unwrap().unit, UNIT * 3 / 2);
+
+    test.env.ledger().with_mut(|li| li.sequence_number += 51);
+    let result = test
+        .auction_module
+        .try_bid(bidder, &ct.address, t1, t0, &100, &100);
+    assert_eq!(result, Err(Ok(Error::AuctionEnded)));
+
+    test.auction_module.end_auction(&ct.address);
+    assert!(test.auction_module.get_auction(&ct.address).is_none());
+}
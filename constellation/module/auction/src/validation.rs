@@ -0,0 +1,41 @@
+use crate::error::Error;
+use crate::storage::auction::read_auction;
+use crate::storage::types::{Auction, AuctionComponent};
+use soroban_sdk::{Address, Env, Vec};
+
+pub fn require_auction(e: &Env, constellation_token_id: &Address) -> Result<Auction, Error> {
+    let auction = match read_auction(e, constellation_token_id) {
+        Some(auction) => auction,
+        None => return Err(Error::RequiresAuction),
+    };
+    Ok(auction)
+}
+
+/// Returns the auctioned component `token_id`
+/// Returns error if the auction does not trade the token
+pub fn require_component(auction: &Auction, token_id: &Address) -> Result<AuctionComponent, Error> {
+    match auction.components.iter().find(|c| c.token_id == *token_id) {
+        Some(component) => Ok(component),
+        None => Err(Error::NotInAuction),
+    }
+}
+
+/// Returns error unless `components` lists distinct tokens with non-negative targets and positive prices
+pub fn require_components(components: &Vec<AuctionComponent>) -> Result<(), Error> {
+    if components.len() < 2 {
+        return Err(Error::InvalidAuction);
+    }
+    for (i, component) in components.iter().enumerate() {
+        if component.target_unit < 0 || component.start_price <= 0 || component.end_price <= 0 {
+            return Err(Error::InvalidAuction);
+        }
+        if components
+            .iter()
+            .skip(i + 1)
+            .any(|other| other.token_id == component.token_id)
+        {
+            return Err(Error::InvalidAuction);
+        }
+    }
+    Ok(())
+}
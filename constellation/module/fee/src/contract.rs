@@ -0,0 +1,133 @@
+use crate::error::Error;
+use crate::event;
+use crate::storage::fee::{read_fee, remove_fee, write_fee};
+use crate::storage::types::{FeeConfig, MAX_FEE_BPS};
+use crate::token::mint_fee;
+use crate::validation::require_fee;
+use constellation_lib::math::accrued_fee;
+use constellation_lib::module::{initialize_module, require_manager, total_supply};
+use constellation_lib::traits::module::ModuleHooks;
+use soroban_sdk::{contract, contractimpl, Address, Env};
+
+#[contract]
+pub struct StreamingFee {}
+
+#[contractimpl]
+impl StreamingFee {
+    /// Sets the fee of a constellation token the module was added to, and activates the module on it
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token charged the fee
+    /// - `recipient` Address receiving the minted fee tokens
+    /// - `fee_bps` Annual fee in basis points of the supply, capped at `MAX_FEE_BPS`
+    pub fn initialize_token(
+        e: Env,
+        constellation_token_id: Address,
+        recipient: Address,
+        fee_bps: u32,
+    ) -> Result<(), Error> {
+        Self::set_fee(
+            e.clone(),
+            constellation_token_id.clone(),
+            recipient,
+            fee_bps,
+        )?;
+        initialize_module(&e, &constellation_token_id);
+        Ok(())
+    }
+
+    /// Sets the annual management fee of a constellation token
+    /// Fees accrued under the previous configuration are minted first
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token charged the fee
+    /// - `recipient` Address receiving the minted fee tokens
+    /// - `fee_bps` Annual fee in basis points of the supply, capped at `MAX_FEE_BPS`
+    pub fn set_fee(
+        e: Env,
+        constellation_token_id: Address,
+        recipient: Address,
+        fee_bps: u32,
+    ) -> Result<(), Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        if fee_bps > MAX_FEE_BPS {
+            return Err(Error::FeeExceedsCap);
+        }
+
+        if read_fee(&e, &constellation_token_id).is_some() {
+            Self::accrue_fee(e.clone(), constellation_token_id.clone())?;
+        }
+
+        write_fee(
+            &e,
+            &constellation_token_id,
+            &FeeConfig {
+                recipient: recipient.clone(),
+                fee_bps,
+                last_accrual: e.ledger().timestamp(),
+            },
+        );
+        event::set_fee(&e, constellation_token_id, recipient, fee_bps);
+        Ok(())
+    }
+
+    /// Mints the management fee accrued since the last accrual to the fee recipient
+    /// Can be called by anyone, returns the amount of constellation tokens minted
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token charged the fee
+    pub fn accrue_fee(e: Env, constellation_token_id: Address) -> Result<i128, Error> {
+        let mut fee = require_fee(&e, &constellation_token_id)?;
+
+        let now = e.ledger().timestamp();
+        let elapsed = now.saturating_sub(fee.last_accrual);
+        let supply = total_supply(&e, &constellation_token_id);
+
+        let amount =
+            accrued_fee(supply, fee.fee_bps, elapsed).ok_or(Error::ValueTooLargeOverFlow)?;
+
+        // Time is only consumed once it yields tokens, so frequent calls cannot round the fee away
+        if amount > 0 {
+            mint_fee(&e, &constellation_token_id, &fee.recipient, amount);
+            event::accrue_fee(
+                &e,
+                constellation_token_id.clone(),
+                fee.recipient.clone(),
+                amount,
+            );
+            fee.last_accrual = now;
+        } else if supply == 0 || fee.fee_bps == 0 {
+            fee.last_accrual = now;
+        }
+        write_fee(&e, &constellation_token_id, &fee);
+
+        Ok(amount)
+    }
+
+    pub fn get_fee(e: Env, constellation_token_id: Address) -> Option<FeeConfig> {
+        read_fee(&e, &constellation_token_id)
+    }
+}
+
+#[contractimpl]
+impl ModuleHooks for StreamingFee {
+    /// Clears the fee of a constellation token the module was removed from
+    /// Fees not accrued before the removal are forfeited, the module can no longer mint them
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token the module was removed from
+    fn on_remove(e: Env, constellation_token_id: Address) {
+        constellation_token_id.require_auth();
+        remove_fee(&e, &constellation_token_id);
+    }
+}
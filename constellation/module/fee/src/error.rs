@@ -0,0 +1,27 @@
+use constellation_lib::module::ModuleError;
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    RequiresManage = 101,
+    RequiresFeeConfig = 102,
+    FeeExceedsCap = 103,
+    ValueTooLargeOverFlow = 104,
+}
+
+impl From<ModuleError> for Error {
+    fn from(error: ModuleError) -> Self {
+        match error {
+            // the fee module neither reads a registry nor trades, it only checks the manager
+            ModuleError::RequiresManage
+            | ModuleError::RequiresRegistry
+            | ModuleError::RequiresExchangeAdapter
+            | ModuleError::PairNotFound
+            | ModuleError::RequiresOracle
+            | ModuleError::StalePrice => Error::RequiresManage,
+            ModuleError::ValueTooLargeOverFlow => Error::ValueTooLargeOverFlow,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetFee {
+    constellation_token_id: Address,
+    recipient: Address,
+    fee_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccrueFee {
+    constellation_token_id: Address,
+    recipient: Address,
+    amount: i128,
+}
+
+pub(crate) fn set_fee(e: &Env, constellation_token_id: Address, recipient: Address, fee_bps: u32) {
+    let topics = (Symbol::new(e, "set_fee"),);
+    e.events().publish(
+        topics,
+        SetFee {
+            constellation_token_id,
+            recipient,
+            fee_bps,
+        },
+    );
+}
+
+pub(crate) fn accrue_fee(
+    e: &Env,
+    constellation_token_id: Address,
+    recipient: Address,
+    amount: i128,
+) {
+    let topics = (Symbol::new(e, "accrue_fee"),);
+    e.events().publish(
+        topics,
+        AccrueFee {
+            constellation_token_id,
+            recipient,
+            amount,
+        },
+    );
+}
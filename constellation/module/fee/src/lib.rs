@@ -0,0 +1,10 @@
+#![no_std]
+pub mod contract;
+mod error;
+mod event;
+mod storage;
+mod token;
+mod validation;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,32 @@
+use soroban_sdk::{Address, Env};
+
+use super::keys::DataKey;
+use super::types::{FeeConfig, PERSISTENT_LEDGER_LIFE, PERSISTENT_LEDGER_TTL_THRESHOLD};
+
+pub fn read_fee(e: &Env, constellation_token_id: &Address) -> Option<FeeConfig> {
+    let key = DataKey::Fee(constellation_token_id.clone());
+    let fee = e.storage().persistent().get(&key);
+    if fee.is_some() {
+        e.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LEDGER_TTL_THRESHOLD,
+            PERSISTENT_LEDGER_LIFE,
+        );
+    }
+    fee
+}
+
+pub fn write_fee(e: &Env, constellation_token_id: &Address, fee: &FeeConfig) {
+    let key = DataKey::Fee(constellation_token_id.clone());
+    e.storage().persistent().set(&key, fee);
+    e.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LEDGER_TTL_THRESHOLD,
+        PERSISTENT_LEDGER_LIFE,
+    );
+}
+
+pub fn remove_fee(e: &Env, constellation_token_id: &Address) {
+    let key = DataKey::Fee(constellation_token_id.clone());
+    e.storage().persistent().remove(&key);
+}
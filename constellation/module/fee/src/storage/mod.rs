@@ -0,0 +1,3 @@
+pub mod fee;
+pub mod keys;
+pub mod types;
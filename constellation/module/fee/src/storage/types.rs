@@ -0,0 +1,19 @@
+use soroban_sdk::{contracttype, Address};
+
+pub(crate) const DAY_IN_LEDGERS: u32 = 17280;
+pub(crate) const PERSISTENT_LEDGER_LIFE: u32 = 90 * DAY_IN_LEDGERS; // ~90 days.
+pub(crate) const PERSISTENT_LEDGER_TTL_THRESHOLD: u32 = PERSISTENT_LEDGER_LIFE - DAY_IN_LEDGERS;
+
+/// Hard cap on the annual management fee, 10%
+pub(crate) const MAX_FEE_BPS: u32 = 1_000;
+
+/// Management fee charged on a constellation token
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeConfig {
+    pub recipient: Address,
+    /// Annual fee in basis points of the token supply
+    pub fee_bps: u32,
+    /// Ledger timestamp the fee was last accrued at
+    pub last_accrual: u64,
+}
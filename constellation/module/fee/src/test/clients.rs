@@ -0,0 +1,44 @@
+use crate::contract::{StreamingFee, StreamingFeeClient};
+use soroban_sdk::{Address, Env};
+
+pub use constellation_token::ConstellationTokenClient;
+pub use registry::RegistryClient;
+pub use token::TokenClient;
+
+mod constellation_token {
+    use soroban_sdk::auth::InvokerContractAuthEntry;
+    soroban_sdk::contractimport!(
+        file = "../../../target/wasm32-unknown-unknown/release/constellation_token.wasm"
+    );
+    pub type ConstellationTokenClient<'a> = Client<'a>;
+}
+
+pub mod registry {
+    soroban_sdk::contractimport!(
+        file = "../../../target/wasm32-unknown-unknown/release/constellation_registry.wasm"
+    );
+    pub type RegistryClient<'a> = Client<'a>;
+}
+
+pub mod token {
+    soroban_sdk::contractimport!(file = "../../../libs/soroban_token_contract.wasm");
+    pub type TokenClient<'a> = Client<'a>;
+}
+
+pub fn create_fee_module<'a>(e: &Env) -> StreamingFeeClient<'a> {
+    let contract_id = &e.register_contract(None, StreamingFee {});
+    StreamingFeeClient::new(e, contract_id)
+}
+
+pub fn create_constellation_token<'a>(e: &Env) -> ConstellationTokenClient<'a> {
+    let contract_id = &e.register_contract_wasm(None, constellation_token::WASM);
+    ConstellationTokenClient::new(e, contract_id)
+}
+
+pub fn create_registry<'a>(e: &Env) -> RegistryClient<'a> {
+    RegistryClient::new(e, &e.register_contract_wasm(None, registry::WASM))
+}
+
+pub fn create_token_contract<'a>(e: &Env, admin: &Address) -> TokenClient<'a> {
+    TokenClient::new(e, &e.register_stellar_asset_contract(admin.clone()))
+}
@@ -0,0 +1,72 @@
+use super::clients::{
+    create_constellation_token, create_fee_module, create_registry, create_token_contract,
+    ConstellationTokenClient, TokenClient,
+};
+use crate::contract::StreamingFeeClient;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, IntoVal};
+
+/// 100 whole constellation tokens
+pub const SUPPLY: i128 = 100_000_000;
+/// 1 component token per whole constellation token
+pub const UNIT: i128 = 1_000_000;
+
+pub struct FeeTest<'a> {
+    pub env: Env,
+    pub user: Address,
+    pub recipient: Address,
+    pub constellation_token: ConstellationTokenClient<'a>,
+    pub fee_module: StreamingFeeClient<'a>,
+    pub tokens: (TokenClient<'a>, TokenClient<'a>),
+}
+
+impl<'a> FeeTest<'a> {
+    pub fn setup() -> FeeTest<'a> {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.budget().reset_unlimited();
+        let user = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let manager = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let registry = create_registry(&env);
+        let constellation_token = create_constellation_token(&env);
+        let fee_module = create_fee_module(&env);
+        let tokens = (
+            create_token_contract(&env, &admin),
+            create_token_contract(&env, &admin),
+        );
+
+        constellation_token.initialize(
+            &6u32,
+            &vec![&env, tokens.0.address.clone(), tokens.1.address.clone()],
+            &vec![&env, UNIT, UNIT],
+            &"c_token".into_val(&env),
+            &"token_symbol".into_val(&env),
+            &user,
+            &manager,
+        );
+        tokens.0.mint(&user, &SUPPLY);
+        tokens.1.mint(&user, &SUPPLY);
+        tokens
+            .0
+            .approve(&user, &constellation_token.address, &SUPPLY, &1000u32);
+        tokens
+            .1
+            .approve(&user, &constellation_token.address, &SUPPLY, &1000u32);
+        constellation_token.mint(&user, &SUPPLY);
+
+        registry.initialize(&admin);
+        registry.add_module(&fee_module.address);
+        constellation_token.set_registry(&registry.address);
+        constellation_token.add_module(&fee_module.address, &None);
+
+        Self {
+            env,
+            user,
+            recipient,
+            constellation_token,
+            fee_module,
+            tokens,
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use super::setup::{FeeTest, SUPPLY, UNIT};
+use crate::error::Error;
+use constellation_lib::math::{accrued_fee, component_quantity, Rounding, SECONDS_PER_YEAR};
+use soroban_sdk::testutils::Ledger;
+
+#[test]
+fn set_fee_fails_with_fee_exceeds_cap() {
+    let test = FeeTest::setup();
+    let result = test.fee_module.try_set_fee(
+        &test.constellation_token.address,
+        &test.recipient,
+        &1_001u32,
+    );
+    assert_eq!(result, Err(Ok(Error::FeeExceedsCap)));
+}
+
+#[test]
+fn accrue_fee_fails_with_requires_fee_config() {
+    let test = FeeTest::setup();
+    let result = test
+        .fee_module
+        .try_accrue_fee(&test.constellation_token.address);
+    assert_eq!(result, Err(Ok(Error::RequiresFeeConfig)));
+}
+
+#[test]
+fn accrue_fee_mints_to_recipient_and_dilutes_units() {
+    let test = FeeTest::setup();
+    // 10% per year
+    test.fee_module.initialize_token(
+        &test.constellation_token.address,
+        &test.recipient,
+        &1_000u32,
+    );
+    test.env
+        .ledger()
+        .with_mut(|li| li.timestamp += SECONDS_PER_YEAR / 2);
+
+    // recipient owns 5% of the diluted supply: 100 * 0.05 / 0.95
+    let minted = test
+        .fee_module
+        .accrue_fee(&test.constellation_token.address);
+    assert_eq!(minted, 5_263_157);
+    assert_eq!(
+        minted,
+        accrued_fee(SUPPLY, 1_000, SECONDS_PER_YEAR / 2).unwrap()
+    );
+    assert_eq!(test.constellation_token.balance(&test.recipient), minted);
+    assert_eq!(test.constellation_token.total_supply(), SUPPLY + minted);
+
+    let unit = UNIT * SUPPLY / (SUPPLY + minted);
+    for component in test.constellation_token.get_components().iter() {
+        assert_eq!(component.unit, unit);
+    }
+
+    // nothing accrues twice for the same period
+    assert_eq!(
+        test.fee_module
+            .accrue_fee(&test.constellation_token.address),
+        0
+    );
+
+    // the user can still redeem every token they hold, diluted by the accrued fee
+    test.constellation_token
+        .redeem_in_kind(&test.user, &test.user, &SUPPLY);
+    let redeemed = component_quantity(SUPPLY, unit, 6, Rounding::Down).unwrap();
+    // the fee recipient owns 5% of the components
+    assert_eq!(redeemed, SUPPLY * 95 / 100);
+    assert_eq!(test.tokens.0.balance(&test.user), redeemed);
+}
+
+#[test]
+fn remove_module_clears_fee_config() {
+    let test = FeeTest::setup();
+    test.fee_module
+        .initialize_token(&test.constellation_token.address, &test.recipient, &100u32);
+    assert!(test
+        .fee_module
+        .get_fee(&test.constellation_token.address)
+        .is_some());
+
+    test.constellation_token
+        .remove_module(&test.fee_module.address);
+    assert_eq!(
+        test.fee_module.get_fee(&test.constellation_token.address),
+        None
+    );
+}
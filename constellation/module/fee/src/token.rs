@@ -0,0 +1,15 @@
+use constellation_lib::traits::constellation_token;
+use soroban_sdk::{Address, Env};
+
+/// Mints fee tokens through the constellation token, diluting its units
+///
+/// # Arguments
+///
+/// - `e` The runtime environment.
+/// - `constellation_token_id` Target constellation token id
+/// - `to` Fee recipient
+/// - `amount` Amount of constellation tokens to mint
+pub(crate) fn mint_fee(e: &Env, constellation_token_id: &Address, to: &Address, amount: i128) {
+    let client = constellation_token::ModuleClient::new(e, constellation_token_id);
+    client.mint_fee(&e.current_contract_address(), to, &amount);
+}
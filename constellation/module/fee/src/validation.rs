@@ -0,0 +1,12 @@
+use crate::error::Error;
+use crate::storage::fee::read_fee;
+use crate::storage::types::FeeConfig;
+use soroban_sdk::{Address, Env};
+
+pub fn require_fee(e: &Env, constellation_token_id: &Address) -> Result<FeeConfig, Error> {
+    let fee = match read_fee(e, constellation_token_id) {
+        Some(fee) => fee,
+        None => return Err(Error::RequiresFeeConfig),
+    };
+    Ok(fee)
+}
@@ -0,0 +1,207 @@
+use crate::error::Error;
+use crate::event;
+use crate::storage::types::LimitOrder;
+use crate::validation::{require_oracle_limit, require_order, require_valid_order};
+use constellation_lib::module::{
+    has_registry, initialize_module, require_adapter, require_manager, require_pair_quote,
+    require_registry, snapshot_balances, swap, update_units, write_registry,
+};
+use constellation_lib::orders::{add_order, read_order, remove_order, remove_orders};
+use constellation_lib::traits::module::ModuleHooks;
+use soroban_sdk::token::TokenClient;
+use soroban_sdk::{contract, contractimpl, panic_with_error, vec, Address, Env};
+
+#[contract]
+pub struct LimitOrderBook {}
+
+#[contractimpl]
+impl LimitOrderBook {
+    pub fn initialize(e: Env, registry_id: Address) {
+        if has_registry(&e) {
+            panic_with_error!(&e, Error::AlreadyInitalized);
+        }
+
+        write_registry(&e, &registry_id);
+    }
+
+    /// Activates the module on a constellation token it was added to
+    /// Orders carry their own settings, the module keeps none for the token
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token trading its components
+    pub fn initialize_token(e: Env, constellation_token_id: Address) -> Result<(), Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        initialize_module(&e, &constellation_token_id);
+        Ok(())
+    }
+
+    /// Places an order selling `amount_in` of `token_in_id` held by a constellation token
+    /// for at least `min_amount_out` of `token_out_id`
+    /// Returns the id of the order
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token trading its components
+    /// - `token_in_id` Token sold
+    /// - `token_out_id` Token bought
+    /// - `amount_in` Amount of `token_in_id` sold
+    /// - `min_amount_out` Minimum amount of `token_out_id` received
+    /// - `valid_until_ledger` Last ledger the order can be executed at
+    ///
+    /// Returns error if the order is invalid, if its minimum amount out is further below the oracle rate
+    /// than the registry ceiling allows, or if the constellation token already has `MAX_OPEN_ORDERS` open orders
+    pub fn place_order(
+        e: Env,
+        constellation_token_id: Address,
+        token_in_id: Address,
+        token_out_id: Address,
+        amount_in: i128,
+        min_amount_out: i128,
+        valid_until_ledger: u32,
+    ) -> Result<u32, Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        let order = LimitOrder {
+            token_in_id: token_in_id.clone(),
+            token_out_id: token_out_id.clone(),
+            amount_in,
+            min_amount_out,
+            valid_until_ledger,
+        };
+        require_valid_order(&e, &order)?;
+        require_oracle_limit(&e, &require_registry(&e)?, &order)?;
+
+        let order_id =
+            add_order(&e, &constellation_token_id, &order).ok_or(Error::TooManyOrders)?;
+        event::place_order(
+            &e,
+            constellation_token_id,
+            order_id,
+            token_in_id,
+            token_out_id,
+            amount_in,
+            min_amount_out,
+            valid_until_ledger,
+        );
+        Ok(order_id)
+    }
+
+    /// Cancels an order of a constellation token
+    pub fn cancel_order(
+        e: Env,
+        constellation_token_id: Address,
+        order_id: u32,
+    ) -> Result<(), Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        require_order(&e, &constellation_token_id, order_id)?;
+        remove_order(&e, &constellation_token_id, order_id);
+        event::cancel_order(&e, constellation_token_id, order_id);
+        Ok(())
+    }
+
+    pub fn get_order(e: Env, constellation_token_id: Address, order_id: u32) -> Option<LimitOrder> {
+        read_order(&e, &constellation_token_id, order_id)
+    }
+
+    /// Executes an order on `exchange_id` once the exchange quotes at least its minimum amount out,
+    /// and updates the units. Can be called by anyone
+    /// Returns the amount of `token_out_id` received
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token trading its components
+    /// - `order_id` Order executed
+    /// - `exchange_id` Exchange to trade on, with an adapter registered for the limit order module
+    /// - `deadline` Swap deadline
+    /// - `expiration_ledger` Expiration ledger of the exchange approval
+    ///
+    /// Returns error if the order has expired, if the oracle rate has moved so that its minimum amount out
+    /// falls below the registry ceiling, or if the exchange quotes less than its minimum amount out
+    pub fn execute_order(
+        e: Env,
+        constellation_token_id: Address,
+        order_id: u32,
+        exchange_id: Address,
+        deadline: u64,
+        expiration_ledger: u32,
+    ) -> Result<i128, Error> {
+        let order = require_order(&e, &constellation_token_id, order_id)?;
+        if e.ledger().sequence() > order.valid_until_ledger {
+            return Err(Error::OrderExpired);
+        }
+        let registry_id = require_registry(&e)?;
+        let adapter_id = require_adapter(&e, &registry_id, &exchange_id)?;
+        require_oracle_limit(&e, &registry_id, &order)?;
+
+        let expected_amount_out = require_pair_quote(
+            &e,
+            &adapter_id,
+            &order.token_in_id,
+            &order.token_out_id,
+            order.amount_in,
+        )?;
+        if expected_amount_out < order.min_amount_out {
+            return Err(Error::LimitNotReached);
+        }
+
+        snapshot_balances(
+            &e,
+            &constellation_token_id,
+            &vec![&e, order.token_in_id.clone(), order.token_out_id.clone()],
+        );
+
+        let token_out = TokenClient::new(&e, &order.token_out_id);
+        let balance_before = token_out.balance(&constellation_token_id);
+        swap(
+            &e,
+            &constellation_token_id,
+            &adapter_id,
+            &exchange_id,
+            &vec![&e, order.token_in_id.clone(), order.token_out_id.clone()],
+            order.amount_in,
+            order.min_amount_out,
+            deadline,
+            expiration_ledger,
+        );
+        let amount_out = token_out.balance(&constellation_token_id) - balance_before;
+        if amount_out < order.min_amount_out {
+            return Err(Error::InsufficientOutputAmount);
+        }
+
+        update_units(&e, &constellation_token_id);
+        remove_order(&e, &constellation_token_id, order_id);
+
+        event::execute_order(
+            &e,
+            constellation_token_id,
+            order_id,
+            exchange_id,
+            order.amount_in,
+            amount_out,
+        );
+        Ok(amount_out)
+    }
+}
+
+#[contractimpl]
+impl ModuleHooks for LimitOrderBook {
+    /// Cancels the open orders of a constellation token the module was removed from
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token the module was removed from
+    fn on_remove(e: Env, constellation_token_id: Address) {
+        constellation_token_id.require_auth();
+        remove_orders(&e, &constellation_token_id);
+    }
+}
@@ -0,0 +1,37 @@
+use constellation_lib::module::ModuleError;
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitalized = 101,
+    RequiresRegistry = 102,
+    RequiresExchangeAdapter = 103,
+    RequiresManage = 104,
+    RequiresOrder = 105,
+    InvalidOrder = 106,
+    OrderExpired = 107,
+    LimitNotReached = 108,
+    PairNotFound = 109,
+    InsufficientOutputAmount = 110,
+    TooManyOrders = 111,
+    RequiresOracle = 112,
+    StalePrice = 113,
+    PriceDeviationExceedsLimit = 114,
+    ValueTooLargeOverFlow = 115,
+}
+
+impl From<ModuleError> for Error {
+    fn from(error: ModuleError) -> Self {
+        match error {
+            ModuleError::RequiresRegistry => Error::RequiresRegistry,
+            ModuleError::RequiresExchangeAdapter => Error::RequiresExchangeAdapter,
+            ModuleError::RequiresManage => Error::RequiresManage,
+            ModuleError::PairNotFound => Error::PairNotFound,
+            ModuleError::RequiresOracle => Error::RequiresOracle,
+            ModuleError::StalePrice => Error::StalePrice,
+            ModuleError::ValueTooLargeOverFlow => Error::ValueTooLargeOverFlow,
+        }
+    }
+}
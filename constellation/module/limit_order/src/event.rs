@@ -0,0 +1,81 @@
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlaceOrder {
+    constellation_token_id: Address,
+    order_id: u32,
+    token_in_id: Address,
+    token_out_id: Address,
+    amount_in: i128,
+    min_amount_out: i128,
+    valid_until_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecuteOrder {
+    constellation_token_id: Address,
+    order_id: u32,
+    exchange_id: Address,
+    amount_in: i128,
+    amount_out: i128,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn place_order(
+    e: &Env,
+    constellation_token_id: Address,
+    order_id: u32,
+    token_in_id: Address,
+    token_out_id: Address,
+    amount_in: i128,
+    min_amount_out: i128,
+    valid_until_ledger: u32,
+) {
+    let topics = (
+        Symbol::new(e, "place_order"),
+        constellation_token_id.clone(),
+    );
+    e.events().publish(
+        topics,
+        PlaceOrder {
+            constellation_token_id,
+            order_id,
+            token_in_id,
+            token_out_id,
+            amount_in,
+            min_amount_out,
+            valid_until_ledger,
+        },
+    );
+}
+
+pub(crate) fn cancel_order(e: &Env, constellation_token_id: Address, order_id: u32) {
+    let topics = (Symbol::new(e, "cancel_order"), constellation_token_id);
+    e.events().publish(topics, order_id);
+}
+
+pub(crate) fn execute_order(
+    e: &Env,
+    constellation_token_id: Address,
+    order_id: u32,
+    exchange_id: Address,
+    amount_in: i128,
+    amount_out: i128,
+) {
+    let topics = (
+        Symbol::new(e, "execute_order"),
+        constellation_token_id.clone(),
+    );
+    e.events().publish(
+        topics,
+        ExecuteOrder {
+            constellation_token_id,
+            order_id,
+            exchange_id,
+            amount_in,
+            amount_out,
+        },
+    );
+}
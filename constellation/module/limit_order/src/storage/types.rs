@@ -0,0 +1,15 @@
+use soroban_sdk::{contracttype, Address};
+
+/// Sale of a component of a constellation token, executable once an exchange quotes the limit
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LimitOrder {
+    pub token_in_id: Address,
+    pub token_out_id: Address,
+    /// Amount of `token_in_id` sold
+    pub amount_in: i128,
+    /// Minimum amount of `token_out_id` received, the limit price of the order
+    pub min_amount_out: i128,
+    /// Last ledger the order can be executed at
+    pub valid_until_ledger: u32,
+}
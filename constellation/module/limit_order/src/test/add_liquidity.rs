@@ -0,0 +1,31 @@
+use soroban_sdk::{testutils::Ledger, Address, Env};
+
+use super::clients::SoroswapRouterClient;
+
+pub fn add_liquidity(
+    env: &Env,
+    router: &SoroswapRouterClient,
+    user: &Address,
+    token_0: &Address,
+    token_1: &Address,
+    amount_0: &i128,
+    amount_1: &i128,
+) -> (i128, i128, i128) {
+    let ledger_timestamp = 100;
+    let desired_deadline = 1000;
+    env.ledger().with_mut(|li| {
+        li.timestamp = ledger_timestamp;
+    });
+
+    env.budget().reset_unlimited();
+    router.add_liquidity(
+        token_0,
+        token_1,
+        amount_0,
+        amount_1,
+        &0,
+        &0,
+        user,
+        &desired_deadline,
+    )
+}
@@ -0,0 +1,149 @@
+use super::setup::{LimitOrderTest, PRICE, UNIT};
+use crate::error::Error;
+use constellation_lib::orders::MAX_OPEN_ORDERS;
+use soroban_sdk::testutils::Ledger;
+
+#[test]
+fn place_order_fails_with_invalid_order() {
+    let test = LimitOrderTest::setup();
+    let (t0, t1) = (&test.tokens.0.address, &test.tokens.1.address);
+    let ct = &test.constellation_token.address;
+
+    let result = test
+        .limit_order_module
+        .try_place_order(ct, t0, t0, &1_000, &990, &100);
+    assert_eq!(result, Err(Ok(Error::InvalidOrder)));
+    let result = test
+        .limit_order_module
+        .try_place_order(ct, t0, t1, &0, &990, &100);
+    assert_eq!(result, Err(Ok(Error::InvalidOrder)));
+}
+
+#[test]
+fn place_and_execute_order_fail_with_price_deviation_exceeds_limit() {
+    let test = LimitOrderTest::setup();
+    let (t0, t1) = (&test.tokens.0.address, &test.tokens.1.address);
+    let ct = &test.constellation_token.address;
+    let router = &test.router.address;
+    let deadline = test.env.ledger().timestamp() + 1000;
+
+    // the registry sets no ceiling, orders accept at most 5% below the oracle rate
+    let result = test
+        .limit_order_module
+        .try_place_order(ct, t0, t1, &1_000, &949, &100);
+    assert_eq!(result, Err(Ok(Error::PriceDeviationExceedsLimit)));
+    let order_id = test
+        .limit_order_module
+        .place_order(ct, t0, t1, &1_000, &950, &100);
+
+    // token 1 falls 10% against the oracle, the order now sells below the ceiling
+    test.oracle.set_price(t1, &(PRICE * 9 / 10));
+    let result = test
+        .limit_order_module
+        .try_execute_order(ct, &order_id, router, &deadline, &1000u32);
+    assert_eq!(result, Err(Ok(Error::PriceDeviationExceedsLimit)));
+    assert!(test.limit_order_module.get_order(ct, &order_id).is_some());
+}
+
+#[test]
+fn execute_order_trades_once_limit_is_reached() {
+    let test = LimitOrderTest::setup();
+    let (t0, t1) = (&test.tokens.0.address, &test.tokens.1.address);
+    let ct = &test.constellation_token;
+    let router = &test.router.address;
+    let deadline = test.env.ledger().timestamp() + 1000;
+
+    // the pool trades at par minus its fee
+    let order_id = test
+        .limit_order_module
+        .place_order(&ct.address, t0, t1, &1_000, &990, &100);
+    let amount_out =
+        test.limit_order_module
+            .execute_order(&ct.address, &order_id, router, &deadline, &1000u32);
+    assert!(amount_out >= 990);
+    assert!(test
+        .limit_order_module
+        .get_order(&ct.address, &order_id)
+        .is_none());
+    assert_eq!(test.tokens.0.balance(&ct.address), 9_000);
+    assert_eq!(test.tokens.1.balance(&ct.address), 10_000 + amount_out);
+    assert_eq!(ct.get_component(t0).unwrap().unit, UNIT * 9 / 10);
+    assert_eq!(
+        ct.get_component(t1).unwrap().unit,
+        (10_000 + amount_out) * 10i128.pow(6) / 10
+    );
+
+    let result = test.limit_order_module.try_execute_order(
+        &ct.address,
+        &order_id,
+        router,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(result, Err(Ok(Error::RequiresOrder)));
+}
+
+#[test]
+fn execute_order_fails_before_limit_and_after_expiry() {
+    let test = LimitOrderTest::setup();
+    let (t0, t1) = (&test.tokens.0.address, &test.tokens.1.address);
+    let ct = &test.constellation_token.address;
+    let router = &test.router.address;
+    let deadline = test.env.ledger().timestamp() + 1000;
+
+    let order_id = test
+        .limit_order_module
+        .place_order(ct, t0, t1, &1_000, &1_000, &100);
+    let result = test
+        .limit_order_module
+        .try_execute_order(ct, &order_id, router, &deadline, &1000u32);
+    assert_eq!(result, Err(Ok(Error::LimitNotReached)));
+
+    test.env.ledger().with_mut(|li| li.sequence_number = 101);
+    let result = test
+        .limit_order_module
+        .try_execute_order(ct, &order_id, router, &deadline, &1000u32);
+    assert_eq!(result, Err(Ok(Error::OrderExpired)));
+
+    test.limit_order_module.cancel_order(ct, &order_id);
+    assert!(test.limit_order_module.get_order(ct, &order_id).is_none());
+
+    // removing the module from the token cancels its open orders
+    let order_id = test
+        .limit_order_module
+        .place_order(ct, t0, t1, &1_000, &1_000, &200);
+    test.constellation_token
+        .remove_module(&test.limit_order_module.address);
+    assert!(test.limit_order_module.get_order(ct, &order_id).is_none());
+}
+
+#[test]
+fn place_order_fails_with_too_many_orders() {
+    let test = LimitOrderTest::setup();
+    let (t0, t1) = (&test.tokens.0.address, &test.tokens.1.address);
+    let ct = &test.constellation_token.address;
+
+    for order_id in 0..MAX_OPEN_ORDERS {
+        let placed = test
+            .limit_order_module
+            .place_order(ct, t0, t1, &100, &100, &100);
+        assert_eq!(placed, order_id);
+    }
+    let result = test
+        .limit_order_module
+        .try_place_order(ct, t0, t1, &100, &100, &100);
+    assert_eq!(result, Err(Ok(Error::TooManyOrders)));
+
+    // a cancelled order frees its slot, ids keep increasing
+    test.limit_order_module.cancel_order(ct, &3);
+    let order_id = test
+        .limit_order_module
+        .place_order(ct, t0, t1, &100, &100, &100);
+    assert_eq!(order_id, MAX_OPEN_ORDERS);
+
+    test.constellation_token
+        .remove_module(&test.limit_order_module.address);
+    for order_id in 0..=MAX_OPEN_ORDERS {
+        assert!(test.limit_order_module.get_order(ct, &order_id).is_none());
+    }
+}
@@ -0,0 +1,50 @@
+use crate::error::Error;
+use crate::storage::types::LimitOrder;
+use constellation_lib::module::{max_price_deviation, oracle_amount_out_min};
+use constellation_lib::orders::read_order;
+use soroban_sdk::{Address, Env};
+
+pub fn require_order(
+    e: &Env,
+    constellation_token_id: &Address,
+    order_id: u32,
+) -> Result<LimitOrder, Error> {
+    let order = match read_order(e, constellation_token_id, order_id) {
+        Some(order) => order,
+        None => return Err(Error::RequiresOrder),
+    };
+    Ok(order)
+}
+
+/// Returns error if `order` trades a token for itself, sells or buys nothing, or has already expired
+pub fn require_valid_order(e: &Env, order: &LimitOrder) -> Result<(), Error> {
+    if order.token_in_id == order.token_out_id
+        || order.amount_in <= 0
+        || order.min_amount_out <= 0
+        || order.valid_until_ledger < e.ledger().sequence()
+    {
+        return Err(Error::InvalidOrder);
+    }
+    Ok(())
+}
+
+/// Returns error if the minimum amount out of `order` is further below the rate of the protocol oracle
+/// than the deviation ceiling of the registry, or if the oracle has no fresh price for the pair
+pub fn require_oracle_limit(
+    e: &Env,
+    registry_id: &Address,
+    order: &LimitOrder,
+) -> Result<(), Error> {
+    let amount_out_min = oracle_amount_out_min(
+        e,
+        registry_id,
+        &order.token_in_id,
+        &order.token_out_id,
+        order.amount_in,
+        max_price_deviation(e, registry_id),
+    )?;
+    if order.min_amount_out < amount_out_min {
+        return Err(Error::PriceDeviationExceedsLimit);
+    }
+    Ok(())
+}
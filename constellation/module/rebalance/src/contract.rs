@@ -0,0 +1,257 @@
+use crate::error::Error;
+use crate::event;
+use crate::helpers::{holdings, next_trade, token_value, value_amount};
+use crate::storage::targets::{read_targets, remove_targets, write_targets};
+use crate::storage::types::{RebalanceConfig, RebalanceTrade, TargetWeight, BPS_DENOMINATOR};
+use crate::validation::{require_bounds, require_oracle, require_targets, require_weights};
+use constellation_lib::math::{mul_div, Rounding};
+use constellation_lib::module::{
+    has_registry, initialize_module, max_price_deviation, require_adapter, require_manager,
+    require_pair_quote, require_registry, snapshot_balances, swap, update_units, write_registry,
+};
+use constellation_lib::traits::module::ModuleHooks;
+use soroban_sdk::token::TokenClient;
+use soroban_sdk::{contract, contractimpl, panic_with_error, vec, Address, Env, Vec};
+
+#[contract]
+pub struct Rebalance {}
+
+#[contractimpl]
+impl Rebalance {
+    pub fn initialize(e: Env, registry_id: Address) {
+        if has_registry(&e) {
+            panic_with_error!(&e, Error::AlreadyInitalized);
+        }
+
+        write_registry(&e, &registry_id);
+    }
+
+    /// Sets the target weights keepers rebalance a constellation token towards
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token rebalanced
+    /// - `targets` Target weight of each token, summing to 100%. Components without a target are sold
+    /// - `tolerance_bps` Distance from its target weight within which a component is not traded
+    /// - `max_deviation_bps` Maximum shortfall of a trade below the price of the registry oracle,
+    ///   capped at the protocol ceiling of the registry
+    pub fn set_targets(
+        e: Env,
+        constellation_token_id: Address,
+        targets: Vec<TargetWeight>,
+        tolerance_bps: u32,
+        max_deviation_bps: u32,
+    ) -> Result<(), Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        require_weights(&targets)?;
+        let registry_id = require_registry(&e)?;
+        require_bounds(
+            tolerance_bps,
+            max_deviation_bps,
+            max_price_deviation(&e, &registry_id),
+        )?;
+
+        write_targets(
+            &e,
+            &constellation_token_id,
+            &RebalanceConfig {
+                targets: targets.clone(),
+                tolerance_bps,
+                max_deviation_bps,
+            },
+        );
+        event::set_targets(&e, constellation_token_id, targets, tolerance_bps);
+        Ok(())
+    }
+
+    /// Sets the target weights of a constellation token the module was added to, and activates
+    /// the module on it
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token rebalanced
+    /// - `targets` Target weight of each token, summing to 100%. Components without a target are sold
+    /// - `tolerance_bps` Distance from its target weight within which a component is not traded
+    /// - `max_deviation_bps` Maximum shortfall of a trade below the price of the registry oracle,
+    ///   capped at the protocol ceiling of the registry
+    pub fn initialize_token(
+        e: Env,
+        constellation_token_id: Address,
+        targets: Vec<TargetWeight>,
+        tolerance_bps: u32,
+        max_deviation_bps: u32,
+    ) -> Result<(), Error> {
+        Self::set_targets(
+            e.clone(),
+            constellation_token_id.clone(),
+            targets,
+            tolerance_bps,
+            max_deviation_bps,
+        )?;
+        initialize_module(&e, &constellation_token_id);
+        Ok(())
+    }
+
+    pub fn get_targets(e: Env, constellation_token_id: Address) -> Option<RebalanceConfig> {
+        read_targets(&e, &constellation_token_id)
+    }
+
+    /// Trades the most overweight component of a constellation token into the most underweight one,
+    /// until the closest of the two reaches its target weight. Can be called by anyone
+    /// Returns the executed trade
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token rebalanced
+    /// - `exchange_id` Exchange to trade on, with an adapter registered for the rebalance module
+    /// - `deadline` Swap deadline
+    /// - `expiration_ledger` Expiration ledger of the exchange approval
+    ///
+    /// Returns error if every component is within its tolerance band, if an oracle price is stale,
+    /// or if the exchange quotes less than the oracle price allows
+    pub fn rebalance_step(
+        e: Env,
+        constellation_token_id: Address,
+        exchange_id: Address,
+        deadline: u64,
+        expiration_ledger: u32,
+    ) -> Result<RebalanceTrade, Error> {
+        let config = require_targets(&e, &constellation_token_id)?;
+        let registry_id = require_registry(&e)?;
+        let adapter_id = require_adapter(&e, &registry_id, &exchange_id)?;
+
+        let (trade, amount_out_min) = Self::plan_step(
+            &e,
+            &constellation_token_id,
+            &config,
+            &registry_id,
+            &adapter_id,
+        )?;
+
+        snapshot_balances(
+            &e,
+            &constellation_token_id,
+            &vec![&e, trade.token_in_id.clone(), trade.token_out_id.clone()],
+        );
+
+        let token_out = TokenClient::new(&e, &trade.token_out_id);
+        let balance_before = token_out.balance(&constellation_token_id);
+        swap(
+            &e,
+            &constellation_token_id,
+            &adapter_id,
+            &exchange_id,
+            &vec![&e, trade.token_in_id.clone(), trade.token_out_id.clone()],
+            trade.amount_in,
+            amount_out_min,
+            deadline,
+            expiration_ledger,
+        );
+        let amount_out = token_out.balance(&constellation_token_id) - balance_before;
+        if amount_out < amount_out_min {
+            return Err(Error::InsufficientOutputAmount);
+        }
+
+        update_units(&e, &constellation_token_id);
+
+        let trade = RebalanceTrade {
+            amount_out,
+            ..trade
+        };
+        event::rebalance_step(&e, constellation_token_id, exchange_id, trade.clone());
+        Ok(trade)
+    }
+
+    /// Returns the trade `rebalance_step` would execute on `exchange_id`, with its quoted amount out
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token rebalanced
+    /// - `exchange_id` Exchange to trade on
+    pub fn quote_rebalance_step(
+        e: Env,
+        constellation_token_id: Address,
+        exchange_id: Address,
+    ) -> Result<RebalanceTrade, Error> {
+        let config = require_targets(&e, &constellation_token_id)?;
+        let registry_id = require_registry(&e)?;
+        let adapter_id = require_adapter(&e, &registry_id, &exchange_id)?;
+        let (trade, _) = Self::plan_step(
+            &e,
+            &constellation_token_id,
+            &config,
+            &registry_id,
+            &adapter_id,
+        )?;
+        Ok(trade)
+    }
+
+    /// Returns the next rebalance trade with its quoted amount out, and the lowest amount out
+    /// the oracle prices allow
+    fn plan_step(
+        e: &Env,
+        constellation_token_id: &Address,
+        config: &RebalanceConfig,
+        registry_id: &Address,
+        adapter_id: &Address,
+    ) -> Result<(RebalanceTrade, i128), Error> {
+        let oracle = require_oracle(e, registry_id)?;
+        let (holdings, total_value) = holdings(e, constellation_token_id, config, &oracle)?;
+        let (sell, buy, value) = next_trade(&holdings, total_value, config.tolerance_bps)?;
+
+        let amount_in = value_amount(value, sell.price, sell.scale)?;
+        if amount_in <= 0 {
+            return Err(Error::WithinTolerance);
+        }
+        let oracle_amount_out = value_amount(
+            token_value(amount_in, sell.price, sell.scale)?,
+            buy.price,
+            buy.scale,
+        )?;
+        // the registry may have lowered its ceiling since the targets were set
+        let max_deviation_bps = config
+            .max_deviation_bps
+            .min(max_price_deviation(e, registry_id));
+        let amount_out_min = mul_div(
+            oracle_amount_out,
+            (BPS_DENOMINATOR - max_deviation_bps) as i128,
+            BPS_DENOMINATOR as i128,
+            Rounding::Up,
+        )
+        .ok_or(Error::ValueTooLargeOverFlow)?;
+
+        let amount_out =
+            require_pair_quote(e, adapter_id, &sell.token_id, &buy.token_id, amount_in)?;
+        if amount_out < amount_out_min {
+            return Err(Error::PriceDeviationExceedsLimit);
+        }
+
+        let trade = RebalanceTrade {
+            token_in_id: sell.token_id,
+            token_out_id: buy.token_id,
+            amount_in,
+            amount_out,
+        };
+        Ok((trade, amount_out_min))
+    }
+}
+
+#[contractimpl]
+impl ModuleHooks for Rebalance {
+    /// Clears the target weights of a constellation token the module was removed from
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token the module was removed from
+    fn on_remove(e: Env, constellation_token_id: Address) {
+        constellation_token_id.require_auth();
+        remove_targets(&e, &constellation_token_id);
+    }
+}
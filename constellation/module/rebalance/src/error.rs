@@ -0,0 +1,37 @@
+use constellation_lib::module::ModuleError;
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitalized = 101,
+    RequiresRegistry = 102,
+    RequiresExchangeAdapter = 103,
+    RequiresManage = 104,
+    RequiresTargets = 105,
+    InvalidWeights = 106,
+    InvalidTolerance = 107,
+    InvalidDeviation = 108,
+    StalePrice = 109,
+    WithinTolerance = 110,
+    PairNotFound = 111,
+    PriceDeviationExceedsLimit = 112,
+    InsufficientOutputAmount = 113,
+    ValueTooLargeOverFlow = 114,
+    RequiresOracle = 115,
+}
+
+impl From<ModuleError> for Error {
+    fn from(error: ModuleError) -> Self {
+        match error {
+            ModuleError::RequiresRegistry => Error::RequiresRegistry,
+            ModuleError::RequiresExchangeAdapter => Error::RequiresExchangeAdapter,
+            ModuleError::RequiresManage => Error::RequiresManage,
+            ModuleError::PairNotFound => Error::PairNotFound,
+            ModuleError::RequiresOracle => Error::RequiresOracle,
+            ModuleError::StalePrice => Error::StalePrice,
+            ModuleError::ValueTooLargeOverFlow => Error::ValueTooLargeOverFlow,
+        }
+    }
+}
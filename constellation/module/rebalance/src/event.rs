@@ -0,0 +1,58 @@
+use crate::storage::types::{RebalanceTrade, TargetWeight};
+use soroban_sdk::{contracttype, Address, Env, Symbol, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetTargets {
+    constellation_token_id: Address,
+    targets: Vec<TargetWeight>,
+    tolerance_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebalanceStep {
+    constellation_token_id: Address,
+    exchange_id: Address,
+    trade: RebalanceTrade,
+}
+
+pub(crate) fn set_targets(
+    e: &Env,
+    constellation_token_id: Address,
+    targets: Vec<TargetWeight>,
+    tolerance_bps: u32,
+) {
+    let topics = (
+        Symbol::new(e, "set_targets"),
+        constellation_token_id.clone(),
+    );
+    e.events().publish(
+        topics,
+        SetTargets {
+            constellation_token_id,
+            targets,
+            tolerance_bps,
+        },
+    );
+}
+
+pub(crate) fn rebalance_step(
+    e: &Env,
+    constellation_token_id: Address,
+    exchange_id: Address,
+    trade: RebalanceTrade,
+) {
+    let topics = (
+        Symbol::new(e, "rebalance_step"),
+        constellation_token_id.clone(),
+    );
+    e.events().publish(
+        topics,
+        RebalanceStep {
+            constellation_token_id,
+            exchange_id,
+            trade,
+        },
+    );
+}
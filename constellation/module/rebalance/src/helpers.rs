@@ -0,0 +1,135 @@
+use crate::error::Error;
+use crate::storage::types::{RebalanceConfig, BPS_DENOMINATOR};
+use crate::token::get_components;
+use crate::validation::require_price;
+use constellation_lib::math::{mul_div, unit_scale, Rounding};
+use constellation_lib::types::oracle::OracleConfig;
+use soroban_sdk::{contracttype, token::TokenClient, vec, Address, Env, Vec};
+
+/// Balance of a token held or targeted by a constellation token, valued in the base asset of the oracle
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Holding {
+    pub token_id: Address,
+    /// Oracle price of one whole token
+    pub price: i128,
+    /// Base units per whole token
+    pub scale: i128,
+    pub value: i128,
+    pub target_value: i128,
+}
+
+/// Returns the value of `amount` base units of a token
+pub fn token_value(amount: i128, price: i128, scale: i128) -> Result<i128, Error> {
+    mul_div(amount, price, scale, Rounding::Down).ok_or(Error::ValueTooLargeOverFlow)
+}
+
+/// Returns the amount of base units of a token worth `value`
+pub fn value_amount(value: i128, price: i128, scale: i128) -> Result<i128, Error> {
+    mul_div(value, scale, price, Rounding::Down).ok_or(Error::ValueTooLargeOverFlow)
+}
+
+/// Returns the components and target tokens of a constellation token, with the value of its balance
+/// and the value its target weight amounts to
+///
+/// # Arguments
+///
+/// - `e` The runtime environment.
+/// - `constellation_token_id` Constellation token rebalanced
+/// - `config` Target weights of the constellation token
+/// - `oracle` Protocol oracle valuing the tokens
+pub fn holdings(
+    e: &Env,
+    constellation_token_id: &Address,
+    config: &RebalanceConfig,
+    oracle: &OracleConfig,
+) -> Result<(Vec<Holding>, i128), Error> {
+    // components without a target weight are sold entirely
+    let mut tokens: Vec<(Address, u32)> = vec![e];
+    for target in config.targets.iter() {
+        tokens.push_back((target.token_id, target.weight_bps));
+    }
+    for component in get_components(e, constellation_token_id).iter() {
+        if !config
+            .targets
+            .iter()
+            .any(|t| t.token_id == component.address)
+        {
+            tokens.push_back((component.address, 0));
+        }
+    }
+
+    let mut holdings: Vec<Holding> = vec![e];
+    let mut total_value = 0i128;
+    for (token_id, _) in tokens.iter() {
+        let token = TokenClient::new(e, &token_id);
+        let price = require_price(e, oracle, &token_id)?;
+        let scale = unit_scale(token.decimals()).ok_or(Error::ValueTooLargeOverFlow)?;
+        let value = token_value(token.balance(constellation_token_id), price, scale)?;
+        total_value = total_value
+            .checked_add(value)
+            .ok_or(Error::ValueTooLargeOverFlow)?;
+        holdings.push_back(Holding {
+            token_id,
+            price,
+            scale,
+            value,
+            target_value: 0,
+        });
+    }
+    for (i, (_, weight_bps)) in tokens.iter().enumerate() {
+        let mut holding = holdings.get_unchecked(i as u32);
+        holding.target_value = mul_div(
+            total_value,
+            weight_bps as i128,
+            BPS_DENOMINATOR as i128,
+            Rounding::Down,
+        )
+        .ok_or(Error::ValueTooLargeOverFlow)?;
+        holdings.set(i as u32, holding);
+    }
+    Ok((holdings, total_value))
+}
+
+/// Returns the most overweight and the most underweight holdings outside the tolerance band,
+/// and the value traded from one into the other to bring the closest of the two back to its target
+pub fn next_trade(
+    holdings: &Vec<Holding>,
+    total_value: i128,
+    tolerance_bps: u32,
+) -> Result<(Holding, Holding, i128), Error> {
+    let tolerance = mul_div(
+        total_value,
+        tolerance_bps as i128,
+        BPS_DENOMINATOR as i128,
+        Rounding::Down,
+    )
+    .ok_or(Error::ValueTooLargeOverFlow)?;
+
+    let mut sell: Option<Holding> = None;
+    let mut buy: Option<Holding> = None;
+    for holding in holdings.iter() {
+        let excess = holding.value - holding.target_value;
+        let sells_more = match &sell {
+            Some(s) => excess > s.value - s.target_value,
+            None => true,
+        };
+        if excess > tolerance && sells_more {
+            sell = Some(holding.clone());
+        }
+        let buys_more = match &buy {
+            Some(b) => -excess > b.target_value - b.value,
+            None => true,
+        };
+        if -excess > tolerance && buys_more {
+            buy = Some(holding);
+        }
+    }
+    match (sell, buy) {
+        (Some(sell), Some(buy)) => {
+            let value = (sell.value - sell.target_value).min(buy.target_value - buy.value);
+            Ok((sell, buy, value))
+        }
+        _ => Err(Error::WithinTolerance),
+    }
+}
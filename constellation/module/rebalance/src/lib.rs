@@ -0,0 +1,11 @@
+#![no_std]
+pub mod contract;
+mod error;
+mod event;
+mod helpers;
+mod storage;
+mod token;
+mod validation;
+
+#[cfg(test)]
+mod test;
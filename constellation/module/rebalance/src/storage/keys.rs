@@ -0,0 +1,7 @@
+use soroban_sdk::{contracttype, Address};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Targets(Address),
+}
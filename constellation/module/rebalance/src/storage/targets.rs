@@ -0,0 +1,32 @@
+use soroban_sdk::{Address, Env};
+
+use super::keys::DataKey;
+use super::types::{RebalanceConfig, PERSISTENT_LEDGER_LIFE, PERSISTENT_LEDGER_TTL_THRESHOLD};
+
+pub fn read_targets(e: &Env, constellation_token_id: &Address) -> Option<RebalanceConfig> {
+    let key = DataKey::Targets(constellation_token_id.clone());
+    let config = e.storage().persistent().get(&key);
+    if config.is_some() {
+        e.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LEDGER_TTL_THRESHOLD,
+            PERSISTENT_LEDGER_LIFE,
+        );
+    }
+    config
+}
+
+pub fn write_targets(e: &Env, constellation_token_id: &Address, config: &RebalanceConfig) {
+    let key = DataKey::Targets(constellation_token_id.clone());
+    e.storage().persistent().set(&key, config);
+    e.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LEDGER_TTL_THRESHOLD,
+        PERSISTENT_LEDGER_LIFE,
+    );
+}
+
+pub fn remove_targets(e: &Env, constellation_token_id: &Address) {
+    let key = DataKey::Targets(constellation_token_id.clone());
+    e.storage().persistent().remove(&key);
+}
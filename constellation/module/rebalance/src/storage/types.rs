@@ -0,0 +1,39 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+pub(crate) const DAY_IN_LEDGERS: u32 = 17280;
+pub(crate) const PERSISTENT_LEDGER_LIFE: u32 = 90 * DAY_IN_LEDGERS; // ~90 days.
+pub(crate) const PERSISTENT_LEDGER_TTL_THRESHOLD: u32 = PERSISTENT_LEDGER_LIFE - DAY_IN_LEDGERS;
+
+pub(crate) const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Target weight of one component of a constellation token
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TargetWeight {
+    pub token_id: Address,
+    /// Share of the value of the constellation token, in basis points
+    pub weight_bps: u32,
+}
+
+/// Target weights of a constellation token and the bounds keepers rebalance it within
+/// The components are valued by the protocol oracle set on the registry
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RebalanceConfig {
+    /// Target weights, summing to 100%
+    pub targets: Vec<TargetWeight>,
+    /// Distance from its target weight, in basis points, within which a component is not traded
+    pub tolerance_bps: u32,
+    /// Maximum shortfall of a trade below the amount out implied by the oracle prices, in basis points
+    pub max_deviation_bps: u32,
+}
+
+/// Trade executed by one rebalance step
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RebalanceTrade {
+    pub token_in_id: Address,
+    pub token_out_id: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+}
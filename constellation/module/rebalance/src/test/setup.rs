@@ -0,0 +1,112 @@
+use super::add_liquidity::add_liquidity;
+use super::clients::{
+    create_adapter, create_constellation_token, create_mock_oracle, create_rebalance_module,
+    create_registry, create_soroswap_factory, create_soroswap_router, create_token_contract,
+    pair_contract_wasm, ConstellationTokenClient, SoroswapRouterClient, TokenClient,
+};
+use crate::contract::RebalanceClient;
+use constellation_lib::testutils::MockOracleClient;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, IntoVal};
+
+/// Constellation tokens minted, backed by 10_000 base units of each component
+pub const SUPPLY: i128 = 10;
+pub const UNIT: i128 = 1_000 * 1_000_000;
+/// Oracle price of one whole token, 1 USD
+pub const PRICE: i128 = 10_000_000;
+/// Maximum age of a price of the protocol oracle, in seconds
+pub const MAX_PRICE_AGE: u64 = 3_600;
+
+pub struct RebalanceTest<'a> {
+    pub env: Env,
+    pub router: SoroswapRouterClient<'a>,
+    pub oracle: MockOracleClient<'a>,
+    pub constellation_token: ConstellationTokenClient<'a>,
+    pub rebalance_module: RebalanceClient<'a>,
+    pub tokens: (TokenClient<'a>, TokenClient<'a>, TokenClient<'a>),
+}
+
+impl<'a> RebalanceTest<'a> {
+    /// Constellation token backed by 10_000 base units of tokens 0 and 1,
+    /// with soroswap pools between tokens 0, 1 and 2 and every token priced at 1 USD by the protocol oracle
+    pub fn setup() -> RebalanceTest<'a> {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.budget().reset_unlimited();
+        let user = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let manager = Address::generate(&env);
+        let adapter = create_adapter(&env);
+        let router = create_soroswap_router(&env);
+        let factory = create_soroswap_factory(&env);
+        let constellation_token = create_constellation_token(&env);
+        let registry = create_registry(&env);
+        let rebalance_module = create_rebalance_module(&env);
+        let oracle = create_mock_oracle(&env);
+
+        adapter.initialize(&router.address, &factory.address);
+        router.initialize(&factory.address);
+        registry.initialize(&admin);
+        registry.set_oracle(&oracle.address, &MAX_PRICE_AGE);
+        factory.initialize(&admin, &pair_contract_wasm(&env));
+        rebalance_module.initialize(&registry.address);
+
+        let tokens = (
+            create_token_contract(&env, &admin),
+            create_token_contract(&env, &admin),
+            create_token_contract(&env, &admin),
+        );
+        let liquidity = 4_000_000_000i128;
+        for token in [&tokens.0, &tokens.1, &tokens.2] {
+            token.mint(&user, &(2 * liquidity + 10_000));
+            oracle.set_price(&token.address, &PRICE);
+        }
+        for (token_a, token_b) in [
+            (&tokens.0, &tokens.1),
+            (&tokens.0, &tokens.2),
+            (&tokens.1, &tokens.2),
+        ] {
+            add_liquidity(
+                &env,
+                &router,
+                &user,
+                &token_a.address,
+                &token_b.address,
+                &liquidity,
+                &liquidity,
+            );
+        }
+
+        constellation_token.initialize(
+            &6u32,
+            &vec![&env, tokens.0.address.clone(), tokens.1.address.clone()],
+            &vec![&env, UNIT, UNIT],
+            &"c_token".into_val(&env),
+            &"token_symbol".into_val(&env),
+            &user,
+            &manager,
+        );
+        tokens
+            .0
+            .approve(&user, &constellation_token.address, &10_000, &1000u32);
+        tokens
+            .1
+            .approve(&user, &constellation_token.address, &10_000, &1000u32);
+        constellation_token.mint(&user, &SUPPLY);
+
+        registry.add_module(&rebalance_module.address);
+        registry.add_adapter(&rebalance_module.address, &router.address, &adapter.address);
+        constellation_token.set_registry(&registry.address);
+        constellation_token.add_module(&rebalance_module.address, &None);
+
+        env.budget().reset_unlimited();
+
+        Self {
+            env,
+            router,
+            oracle,
+            constellation_token,
+            rebalance_module,
+            tokens,
+        }
+    }
+}
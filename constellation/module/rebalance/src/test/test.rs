@@ -0,0 +1,151 @@
+use super::setup::{RebalanceTest, MAX_PRICE_AGE, PRICE, UNIT};
+use crate::error::Error;
+use crate::storage::types::TargetWeight;
+use constellation_lib::module::DEFAULT_MAX_PRICE_DEVIATION_BPS;
+use soroban_sdk::{testutils::Ledger, vec, Address, Vec};
+
+fn targets(test: &RebalanceTest, weights: [(&Address, u32); 2]) -> Vec<TargetWeight> {
+    let mut targets = vec![&test.env];
+    for (token_id, weight_bps) in weights {
+        targets.push_back(TargetWeight {
+            token_id: token_id.clone(),
+            weight_bps,
+        });
+    }
+    targets
+}
+
+#[test]
+fn set_targets_fails_with_invalid_weights() {
+    let test = RebalanceTest::setup();
+    let (t0, t1) = (&test.tokens.0.address, &test.tokens.1.address);
+    let ct = &test.constellation_token.address;
+
+    let result = test.rebalance_module.try_set_targets(
+        ct,
+        &targets(&test, [(t0, 7_000), (t1, 2_000)]),
+        &100,
+        &100,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidWeights)));
+
+    let result = test.rebalance_module.try_set_targets(
+        ct,
+        &targets(&test, [(t0, 5_000), (t0, 5_000)]),
+        &100,
+        &100,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidWeights)));
+
+    // the registry sets no ceiling, the deviation is capped at the default one
+    let result = test.rebalance_module.try_set_targets(
+        ct,
+        &targets(&test, [(t0, 7_000), (t1, 3_000)]),
+        &100,
+        &(DEFAULT_MAX_PRICE_DEVIATION_BPS + 1),
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidDeviation)));
+}
+
+#[test]
+fn rebalance_step_trades_overweight_into_underweight_component() {
+    let test = RebalanceTest::setup();
+    let (t0, t1, t2) = (
+        &test.tokens.0.address,
+        &test.tokens.1.address,
+        &test.tokens.2.address,
+    );
+    let ct = &test.constellation_token;
+    let deadline = test.env.ledger().timestamp() + 1000;
+
+    let result = test.rebalance_module.try_rebalance_step(
+        &ct.address,
+        &test.router.address,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(result, Err(Ok(Error::RequiresTargets)));
+
+    // token 1 is sold for the new component token 2
+    test.rebalance_module.initialize_token(
+        &ct.address,
+        &targets(&test, [(t0, 7_000), (t2, 3_000)]),
+        &100,
+        &100,
+    );
+
+    // 20_000 USD: token 1 holds 10_000 over its target, token 0 misses 4_000 and token 2 6_000
+    let quote = test
+        .rebalance_module
+        .quote_rebalance_step(&ct.address, &test.router.address);
+    assert_eq!(quote.token_in_id, *t1);
+    assert_eq!(quote.token_out_id, *t2);
+    assert_eq!(quote.amount_in, 6_000);
+
+    let trade = test.rebalance_module.rebalance_step(
+        &ct.address,
+        &test.router.address,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(trade, quote);
+    assert_eq!(test.tokens.1.balance(&ct.address), 4_000);
+    assert_eq!(test.tokens.2.balance(&ct.address), trade.amount_out);
+    assert_eq!(ct.get_component(t1).unwrap().unit, UNIT * 4 / 10);
+    assert_eq!(
+        ct.get_component(t2).unwrap().unit,
+        trade.amount_out * 10i128.pow(6) / 10
+    );
+
+    let trade = test.rebalance_module.rebalance_step(
+        &ct.address,
+        &test.router.address,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(trade.token_in_id, *t1);
+    assert_eq!(trade.token_out_id, *t0);
+    // token 0 reaches its target first, the dust of token 1 left is within the tolerance band
+    let dust = 4_000 - trade.amount_in;
+    assert!(dust > 0 && dust < 200);
+    assert_eq!(test.tokens.1.balance(&ct.address), dust);
+
+    let result = test.rebalance_module.try_rebalance_step(
+        &ct.address,
+        &test.router.address,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(result, Err(Ok(Error::WithinTolerance)));
+}
+
+#[test]
+fn rebalance_step_fails_outside_oracle_bounds() {
+    let test = RebalanceTest::setup();
+    let (t0, t1) = (&test.tokens.0.address, &test.tokens.1.address);
+    let ct = &test.constellation_token.address;
+    let deadline = test.env.ledger().timestamp() + 1000;
+
+    test.rebalance_module.initialize_token(
+        ct,
+        &targets(&test, [(t0, 7_000), (t1, 3_000)]),
+        &100,
+        &100,
+    );
+
+    // the pool prices token 1 20% below the oracle
+    test.oracle.set_price(t1, &(PRICE * 12 / 10));
+    let result =
+        test.rebalance_module
+            .try_rebalance_step(ct, &test.router.address, &deadline, &1000u32);
+    assert_eq!(result, Err(Ok(Error::PriceDeviationExceedsLimit)));
+
+    test.oracle.set_price(t1, &PRICE);
+    test.env
+        .ledger()
+        .with_mut(|li| li.timestamp += MAX_PRICE_AGE + 1);
+    let result =
+        test.rebalance_module
+            .try_rebalance_step(ct, &test.router.address, &deadline, &1000u32);
+    assert_eq!(result, Err(Ok(Error::StalePrice)));
+}
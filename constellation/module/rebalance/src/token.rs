@@ -0,0 +1,15 @@
+use soroban_sdk::{Address, Env, Vec};
+pub(crate) mod constellation_token {
+    use soroban_sdk::auth::InvokerContractAuthEntry;
+    soroban_sdk::contractimport!(
+        file = "../../../target/wasm32-unknown-unknown/release/constellation_token.wasm"
+    );
+}
+
+pub(crate) fn get_components(
+    e: &Env,
+    constellation_token_id: &Address,
+) -> Vec<constellation_token::Component> {
+    let client = constellation_token::Client::new(e, constellation_token_id);
+    client.get_components()
+}
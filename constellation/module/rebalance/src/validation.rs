@@ -0,0 +1,69 @@
+use crate::error::Error;
+use crate::storage::targets::read_targets;
+use crate::storage::types::{RebalanceConfig, TargetWeight, BPS_DENOMINATOR};
+use constellation_lib::module::registry_oracle;
+use constellation_lib::oracle::config_price;
+use constellation_lib::types::oracle::OracleConfig;
+use soroban_sdk::{Address, Env, Vec};
+
+pub fn require_targets(
+    e: &Env,
+    constellation_token_id: &Address,
+) -> Result<RebalanceConfig, Error> {
+    let config = match read_targets(e, constellation_token_id) {
+        Some(config) => config,
+        None => return Err(Error::RequiresTargets),
+    };
+    Ok(config)
+}
+
+/// Returns error unless `targets` lists distinct tokens whose weights sum to 100%
+pub fn require_weights(targets: &Vec<TargetWeight>) -> Result<(), Error> {
+    let mut total = 0u32;
+    for (i, target) in targets.iter().enumerate() {
+        for other in targets.iter().skip(i + 1) {
+            if other.token_id == target.token_id {
+                return Err(Error::InvalidWeights);
+            }
+        }
+        total = total
+            .checked_add(target.weight_bps)
+            .ok_or(Error::InvalidWeights)?;
+    }
+    if total != BPS_DENOMINATOR {
+        return Err(Error::InvalidWeights);
+    }
+    Ok(())
+}
+
+/// Returns error if the tolerance band is out of bounds, or if the oracle deviation exceeds `ceiling_bps`
+pub fn require_bounds(
+    tolerance_bps: u32,
+    max_deviation_bps: u32,
+    ceiling_bps: u32,
+) -> Result<(), Error> {
+    if tolerance_bps >= BPS_DENOMINATOR {
+        return Err(Error::InvalidTolerance);
+    }
+    if max_deviation_bps > ceiling_bps {
+        return Err(Error::InvalidDeviation);
+    }
+    Ok(())
+}
+
+/// Returns the protocol oracle set on the registry
+pub fn require_oracle(e: &Env, registry_id: &Address) -> Result<OracleConfig, Error> {
+    match registry_oracle(e, registry_id) {
+        Some(oracle) => Ok(oracle),
+        None => Err(Error::RequiresOracle),
+    }
+}
+
+/// Returns the oracle price of `token_id`
+/// Returns error if the oracle does not quote the token or its price is older than the age the registry allows
+pub fn require_price(e: &Env, oracle: &OracleConfig, token_id: &Address) -> Result<i128, Error> {
+    match config_price(e, oracle, token_id) {
+        Some(price) => Ok(price),
+        None => Err(Error::StalePrice),
+    }
+}
@@ -1,11 +1,30 @@
 use crate::error::Error;
-use crate::token::{self, update_units};
+use crate::event;
+use crate::limits::TradeLimits;
+use crate::registry::get_venues;
+use crate::types::{RiskLimits, TradeOrder, MAX_BATCH_TRADES};
 use crate::{
-    storage::registry::{has_registry, write_registry},
-    validation::{require_adapter, require_administrator, require_manager, require_registry},
+    storage::deviation::{remove_max_price_deviation, write_max_price_deviation},
+    storage::limits::{read_risk_limits, remove_risk_limits, write_risk_limits},
+    storage::slippage::{read_max_slippage, remove_max_slippage, write_max_slippage},
+    storage::types::BPS_DENOMINATOR,
+    validation::{
+        max_amount_in, max_price_deviation, min_amount_out, require_administrator,
+        require_oracle_rate, require_path, require_risk_limits, require_slippage,
+        require_slippage_in, trade_balances,
+    },
+};
+use constellation_lib::aggregator::{best_quote_out, split_quote_out, MAX_SPLIT_PARTS};
+use constellation_lib::module::{
+    has_registry, initialize_module, invoke, max_price_deviation as price_deviation_ceiling,
+    read_registry, require_adapter, require_manager, require_path_quote, require_path_quote_in,
+    require_registry, snapshot_balances, swap, update_units, write_registry,
 };
 use constellation_lib::traits::adapter::dex;
+use constellation_lib::traits::module::ModuleHooks;
+use constellation_lib::types::exchange::{SwapLeg, Venue};
 use soroban_sdk::auth::InvokerContractAuthEntry;
+use soroban_sdk::token::TokenClient;
 use soroban_sdk::vec;
 use soroban_sdk::{
     contract,
@@ -13,7 +32,6 @@ use soroban_sdk::{
     contracttype,
     panic_with_error,
     // auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
-    token::TokenClient,
     Address,
     Env,
     Symbol,
@@ -32,6 +50,17 @@ impl Trade {
 
         write_registry(&e, &registry_id);
     }
+
+    /// Trades `amount_in` of `token_in_id` held by the constellation token for at least `amount_out` of `token_out_id`
+    ///
+    /// `path` optionally routes the trade through intermediate tokens, from `token_in_id` to `token_out_id`.
+    /// Only the balances of `token_in_id` and `token_out_id` are reconciled into the units,
+    /// intermediate tokens never become components
+    ///
+    /// Returns error if the exchange has no pool for a hop of the path, if `amount_out` allows more slippage
+    /// below the exchange quote than the constellation token accepts, or if the executed rate deviates
+    /// from the oracle rate by more than the constellation token accepts
+    #[allow(clippy::too_many_arguments)]
     pub fn trade(
         e: Env,
         constellation_token_id: Address,
@@ -40,55 +69,227 @@ impl Trade {
         token_out_id: Address,
         amount_in: i128,
         amount_out: i128,
+        path: Option<Vec<Address>>,
         deadline: u64,
         expiration_ledger: u32,
     ) -> Result<(), Error> {
         let manager = require_manager(&e, &constellation_token_id)?;
         manager.require_auth();
 
-        let mut args: Vec<Val> = vec![&e];
-
         let registry_id = require_registry(&e)?;
 
         let adapter_id = require_adapter(&e, &registry_id, &exchange_id)?;
 
-        let exchange_adapter = dex::Client::new(&e, &adapter_id);
+        let path = require_path(&e, &token_in_id, &token_out_id, path)?;
+        let expected_amount_out = require_path_quote(&e, &adapter_id, &path, amount_in)?;
+        require_slippage(&e, &constellation_token_id, expected_amount_out, amount_out)?;
 
-        let approve_call_data = exchange_adapter.get_approve_call_data(
+        let mut limits = TradeLimits::begin(&e, &constellation_token_id)?;
+        snapshot_balances(
+            &e,
             &constellation_token_id,
-            &exchange_id,
-            &amount_in,
-            &expiration_ledger,
+            &vec![&e, token_in_id.clone(), token_out_id.clone()],
         );
 
-        Self::approve_exchange(
+        let balances_before =
+            trade_balances(&e, &constellation_token_id, &token_in_id, &token_out_id);
+        swap(
+            &e,
+            &constellation_token_id,
+            &adapter_id,
+            &exchange_id,
+            &path,
+            amount_in,
+            amount_out,
+            deadline,
+            expiration_ledger,
+        );
+        let (amount_sold, _) = require_oracle_rate(
             &e,
+            &registry_id,
             &constellation_token_id,
             &token_in_id,
-            &approve_call_data,
+            &token_out_id,
+            balances_before,
+        )?;
+        limits.record_sale(&e, &token_in_id, amount_sold, balances_before.0)?;
+
+        update_units(&e, &constellation_token_id);
+        limits.end(&e);
+
+        Ok(())
+    }
+
+    /// Executes `trades` on the constellation token in one invocation, and updates the units once
+    /// after the last trade. Fails atomically if any trade fails
+    /// Returns the amount of `token_out_id` received by each trade
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token trading its components
+    /// - `trades` Trades executed in order, each quoted right before it executes
+    /// - `deadline` Swap deadline
+    /// - `expiration_ledger` Expiration ledger of the exchange approvals
+    ///
+    /// Returns error if the batch is empty or larger than `MAX_BATCH_TRADES`, or if any trade fails
+    /// the checks of `trade`
+    pub fn batch_trade(
+        e: Env,
+        constellation_token_id: Address,
+        trades: Vec<TradeOrder>,
+        deadline: u64,
+        expiration_ledger: u32,
+    ) -> Result<Vec<i128>, Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        if trades.is_empty() || trades.len() > MAX_BATCH_TRADES {
+            return Err(Error::InvalidBatch);
+        }
+        let registry_id = require_registry(&e)?;
+
+        // only the endpoints of each trade are reconciled, as in `trade`
+        let mut tokens: Vec<Address> = vec![&e];
+        for order in trades.iter() {
+            tokens.push_back(order.token_in_id);
+            tokens.push_back(order.token_out_id);
+        }
+        let mut limits = TradeLimits::begin(&e, &constellation_token_id)?;
+        snapshot_balances(&e, &constellation_token_id, &tokens);
+
+        let mut amounts_out: Vec<i128> = vec![&e];
+        for order in trades.iter() {
+            let adapter_id = require_adapter(&e, &registry_id, &order.exchange_id)?;
+            let path = match order.path.is_empty() {
+                true => None,
+                false => Some(order.path),
+            };
+            let path = require_path(&e, &order.token_in_id, &order.token_out_id, path)?;
+            let expected_amount_out = require_path_quote(&e, &adapter_id, &path, order.amount_in)?;
+            require_slippage(&e, &constellation_token_id, expected_amount_out, order.amount_out)?;
+
+            let balances_before = trade_balances(
+                &e,
+                &constellation_token_id,
+                &order.token_in_id,
+                &order.token_out_id,
+            );
+            swap(
+                &e,
+                &constellation_token_id,
+                &adapter_id,
+                &order.exchange_id,
+                &path,
+                order.amount_in,
+                order.amount_out,
+                deadline,
+                expiration_ledger,
+            );
+            let (amount_sold, amount_out) = require_oracle_rate(
+                &e,
+                &registry_id,
+                &constellation_token_id,
+                &order.token_in_id,
+                &order.token_out_id,
+                balances_before,
+            )?;
+            limits.record_sale(&e, &order.token_in_id, amount_sold, balances_before.0)?;
+
+            event::trade_leg(
+                &e,
+                constellation_token_id.clone(),
+                order.exchange_id,
+                order.token_in_id,
+                order.token_out_id,
+                order.amount_in,
+                amount_out,
+            );
+            amounts_out.push_back(amount_out);
+        }
+
+        update_units(&e, &constellation_token_id);
+        limits.end(&e);
+
+        Ok(amounts_out)
+    }
+
+    /// Trades at most `amount_in_max` of `token_in_id` held by the constellation token
+    /// for exactly `amount_out` of `token_out_id`
+    /// Returns the amount of `token_in_id` sold
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token trading its components
+    /// - `exchange_id` Exchange to trade on
+    /// - `token_in_id` Token sold
+    /// - `token_out_id` Token bought
+    /// - `amount_out` Amount of `token_out_id` bought
+    /// - `amount_in_max` Maximum amount of `token_in_id` sold
+    /// - `path` Optional path from `token_in_id` to `token_out_id`, the direct pair if not set
+    /// - `deadline` Swap deadline
+    /// - `expiration_ledger` Expiration ledger of the exchange approval
+    ///
+    /// Returns error if the exchange has no pool for a hop of the path, if the quoted amount in exceeds `amount_in_max`,
+    /// if `amount_in_max` allows more slippage above the exchange quote than the constellation token accepts,
+    /// or if the executed rate deviates from the oracle rate by more than the constellation token accepts
+    #[allow(clippy::too_many_arguments)]
+    pub fn trade_exact_out(
+        e: Env,
+        constellation_token_id: Address,
+        exchange_id: Address,
+        token_in_id: Address,
+        token_out_id: Address,
+        amount_out: i128,
+        amount_in_max: i128,
+        path: Option<Vec<Address>>,
+        deadline: u64,
+        expiration_ledger: u32,
+    ) -> Result<i128, Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        let registry_id = require_registry(&e)?;
+        let adapter_id = require_adapter(&e, &registry_id, &exchange_id)?;
+
+        let path = require_path(&e, &token_in_id, &token_out_id, path)?;
+        let amount_in = require_path_quote_in(&e, &adapter_id, &path, amount_out)?;
+        if amount_in > amount_in_max {
+            return Err(Error::ExcessiveInputAmount);
+        }
+        require_slippage_in(&e, &constellation_token_id, amount_in, amount_in_max)?;
+
+        let mut limits = TradeLimits::begin(&e, &constellation_token_id)?;
+        snapshot_balances(
+            &e,
+            &constellation_token_id,
+            &vec![&e, token_in_id.clone(), token_out_id.clone()],
         );
 
-        let swap_call_data = exchange_adapter.get_swap_call_data(
-            &token_in_id.clone(),
-            &token_out_id.clone(),
+        let exchange_adapter = dex::Client::new(&e, &adapter_id);
+
+        // the exchange pulls the quoted amount in, the approval and the sub-auth are sized to it
+        let approve_call_data = exchange_adapter.get_approve_call_data(
+            &constellation_token_id,
+            &exchange_id,
             &amount_in,
-            &amount_out,
-            &constellation_token_id.clone(),
-            &deadline,
+            &expiration_ledger,
         );
+        Self::approve_exchange(&e, &constellation_token_id, &token_in_id, &approve_call_data);
 
-        let auth_entries = exchange_adapter.create_sub_auth(
-            &amount_in,
-            &token_in_id,
-            &token_out_id,
+        let swap_call_data = exchange_adapter.get_path_swap_out_call_data(
+            &path,
+            &amount_out,
+            &amount_in_max,
             &constellation_token_id,
+            &deadline,
         );
+        let auth_entries =
+            exchange_adapter.create_path_sub_auth(&amount_in, &path, &constellation_token_id);
 
-        let balance_before_trade_token_in =
-            TokenClient::new(&e, &token_in_id).balance(&constellation_token_id);
-        let balance_before_trade_token_out =
-            TokenClient::new(&e, &token_out_id).balance(&constellation_token_id);
-
+        let balances_before =
+            trade_balances(&e, &constellation_token_id, &token_in_id, &token_out_id);
         Self::execute_trade(
             &e,
             &constellation_token_id,
@@ -96,26 +297,368 @@ impl Trade {
             &swap_call_data,
             &auth_entries,
         );
-
-        update_units(
+        let (amount_sold, _) = require_oracle_rate(
             &e,
-            balance_before_trade_token_in,
-            balance_before_trade_token_out,
+            &registry_id,
+            &constellation_token_id,
             &token_in_id,
             &token_out_id,
-            &constellation_token_id,
+            balances_before,
+        )?;
+        limits.record_sale(&e, &token_in_id, amount_sold, balances_before.0)?;
+
+        update_units(&e, &constellation_token_id);
+        limits.end(&e);
+
+        event::trade_leg(
+            &e,
+            constellation_token_id,
+            exchange_id,
+            token_in_id,
+            token_out_id,
+            amount_in,
+            amount_out,
         );
+        Ok(amount_in)
+    }
 
+    /// Returns the amount of `token_in_id` the exchange quotes for `amount_out` of `token_out_id`,
+    /// and the highest `amount_in_max` `trade_exact_out` accepts for it
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token trading its components
+    /// - `exchange_id` Exchange to trade on
+    /// - `token_in_id` Token sold
+    /// - `token_out_id` Token bought
+    /// - `amount_out` Amount of `token_out_id` bought
+    /// - `path` Optional path from `token_in_id` to `token_out_id`, the direct pair if not set
+    ///
+    /// Returns error if the exchange has no adapter or no pool for a hop of the path
+    pub fn quote_trade_exact_out(
+        e: Env,
+        constellation_token_id: Address,
+        exchange_id: Address,
+        token_in_id: Address,
+        token_out_id: Address,
+        amount_out: i128,
+        path: Option<Vec<Address>>,
+    ) -> Result<(i128, i128), Error> {
+        let registry_id = require_registry(&e)?;
+        let adapter_id = require_adapter(&e, &registry_id, &exchange_id)?;
+        let path = require_path(&e, &token_in_id, &token_out_id, path)?;
+        let expected_amount_in = require_path_quote_in(&e, &adapter_id, &path, amount_out)?;
+        let amount_in_max = max_amount_in(&e, &constellation_token_id, expected_amount_in)?;
+        Ok((expected_amount_in, amount_in_max))
+    }
+
+    /// Sets the slippage from the exchange quote the manager can accept when trading,
+    /// below the quoted amount out or above the quoted amount in
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token the limit applies to
+    /// - `max_slippage_bps` Maximum slippage in basis points of the quoted amount
+    pub fn set_max_slippage(
+        e: Env,
+        constellation_token_id: Address,
+        max_slippage_bps: u32,
+    ) -> Result<(), Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        if max_slippage_bps > BPS_DENOMINATOR {
+            return Err(Error::InvalidSlippage);
+        }
+        write_max_slippage(&e, &constellation_token_id, max_slippage_bps);
+        event::set_max_slippage(&e, constellation_token_id, max_slippage_bps);
+        Ok(())
+    }
+
+    /// Sets the slippage limit of a constellation token the module was added to, and activates
+    /// the module on it
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token trading through the module
+    /// - `max_slippage_bps` Maximum slippage in basis points of the quoted amount
+    pub fn initialize_token(
+        e: Env,
+        constellation_token_id: Address,
+        max_slippage_bps: u32,
+    ) -> Result<(), Error> {
+        Self::set_max_slippage(e.clone(), constellation_token_id.clone(), max_slippage_bps)?;
+        initialize_module(&e, &constellation_token_id);
+        Ok(())
+    }
+
+    pub fn get_max_slippage(e: Env, constellation_token_id: Address) -> u32 {
+        read_max_slippage(&e, &constellation_token_id)
+    }
+
+    /// Sets the deviation of trade rates below the oracle rate the manager can accept,
+    /// measured from the balances of the constellation token before and after each trade
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token the limit applies to
+    /// - `max_deviation_bps` Maximum deviation in basis points of the oracle value of the amount sold
+    ///
+    /// Returns error if the limit exceeds the protocol ceiling set in the registry, or its default
+    pub fn set_max_price_deviation(
+        e: Env,
+        constellation_token_id: Address,
+        max_deviation_bps: u32,
+    ) -> Result<(), Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        if max_deviation_bps > BPS_DENOMINATOR {
+            return Err(Error::InvalidDeviation);
+        }
+        let registry_id = require_registry(&e)?;
+        if max_deviation_bps > price_deviation_ceiling(&e, &registry_id) {
+            return Err(Error::DeviationExceedsCeiling);
+        }
+        write_max_price_deviation(&e, &constellation_token_id, max_deviation_bps);
+        event::set_max_price_deviation(&e, constellation_token_id, max_deviation_bps);
         Ok(())
     }
 
+    /// Returns the deviation below the oracle rate accepted on trades of the constellation token,
+    /// its own limit capped at the protocol ceiling, or None if the module has no registry
+    pub fn get_max_price_deviation(e: Env, constellation_token_id: Address) -> Option<u32> {
+        let registry_id = read_registry(&e)?;
+        Some(max_price_deviation(
+            &e,
+            &registry_id,
+            &constellation_token_id,
+        ))
+    }
+
+    /// Sets the risk controls applied to the trades of the constellation token
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token the limits apply to
+    /// - `limits` Notional traded per epoch, interval between trades and tokens trades may add as components
+    ///
+    /// Returns error if `max_notional_bps` exceeds 10_000, or is set without an epoch length
+    pub fn set_risk_limits(
+        e: Env,
+        constellation_token_id: Address,
+        limits: RiskLimits,
+    ) -> Result<(), Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        require_risk_limits(&limits)?;
+        write_risk_limits(&e, &constellation_token_id, &limits);
+        event::set_risk_limits(&e, constellation_token_id, limits);
+        Ok(())
+    }
+
+    pub fn get_risk_limits(e: Env, constellation_token_id: Address) -> Option<RiskLimits> {
+        read_risk_limits(&e, &constellation_token_id)
+    }
+
+    /// Returns the amount of `token_out_id` the exchange quotes for `amount_in` of `token_in_id`,
+    /// and the lowest `amount_out` `trade` accepts for it
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token trading its components
+    /// - `exchange_id` Exchange to trade on
+    /// - `token_in_id` Token sold
+    /// - `token_out_id` Token bought
+    /// - `amount_in` Amount of `token_in_id` sold
+    /// - `path` Optional path from `token_in_id` to `token_out_id`, the direct pair if not set
+    ///
+    /// Returns error if the exchange has no adapter or no pool for a hop of the path
+    pub fn quote_trade(
+        e: Env,
+        constellation_token_id: Address,
+        exchange_id: Address,
+        token_in_id: Address,
+        token_out_id: Address,
+        amount_in: i128,
+        path: Option<Vec<Address>>,
+    ) -> Result<(i128, i128), Error> {
+        let registry_id = require_registry(&e)?;
+        let adapter_id = require_adapter(&e, &registry_id, &exchange_id)?;
+        let path = require_path(&e, &token_in_id, &token_out_id, path)?;
+        let expected_amount_out = require_path_quote(&e, &adapter_id, &path, amount_in)?;
+        let amount_out_min = min_amount_out(&e, &constellation_token_id, expected_amount_out)?;
+        Ok((expected_amount_out, amount_out_min))
+    }
+
+    /// Trades `amount_in` of `token_in_id` held by the constellation token for `token_out_id`
+    /// on the registered exchanges quoting the best price, and updates the units once
+    /// Returns the exchange and amounts of each executed leg
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token trading its components
+    /// - `token_in_id` Token sold
+    /// - `token_out_id` Token bought
+    /// - `amount_in` Amount of `token_in_id` sold
+    /// - `amount_out_min` Minimum total amount of `token_out_id` received
+    /// - `parts` Number of parts the order is split into across exchanges, 1 trades on the single best exchange
+    /// - `deadline` Swap deadline
+    /// - `expiration_ledger` Expiration ledger of the exchange approvals
+    ///
+    /// Returns error if no exchange quotes the pair, if a leg receives more slippage below its quote than
+    /// the constellation token accepts, if less than `amount_out_min` is received, or if the
+    /// average rate deviates from the oracle rate by more than the constellation token accepts
+    #[allow(clippy::too_many_arguments)]
+    pub fn trade_best_price(
+        e: Env,
+        constellation_token_id: Address,
+        token_in_id: Address,
+        token_out_id: Address,
+        amount_in: i128,
+        amount_out_min: i128,
+        parts: u32,
+        deadline: u64,
+        expiration_ledger: u32,
+    ) -> Result<Vec<SwapLeg>, Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        let registry_id = require_registry(&e)?;
+        let venues = get_venues(&e, &registry_id);
+        let legs = Self::quote_legs(&e, &venues, &token_in_id, &token_out_id, amount_in, parts)?;
+
+        let mut limits = TradeLimits::begin(&e, &constellation_token_id)?;
+        snapshot_balances(
+            &e,
+            &constellation_token_id,
+            &vec![&e, token_in_id.clone(), token_out_id.clone()],
+        );
+
+        let balances_before =
+            trade_balances(&e, &constellation_token_id, &token_in_id, &token_out_id);
+        let token_out = TokenClient::new(&e, &token_out_id);
+        let mut executed_legs: Vec<SwapLeg> = vec![&e];
+        let mut total_amount_out = 0;
+        for leg in legs.iter() {
+            let adapter_id = require_adapter(&e, &registry_id, &leg.exchange_id)?;
+            // each leg is held to the slippage of the constellation token on its own quote
+            let leg_amount_out_min = min_amount_out(&e, &constellation_token_id, leg.amount_out)?;
+            let balance_before = token_out.balance(&constellation_token_id);
+            swap(
+                &e,
+                &constellation_token_id,
+                &adapter_id,
+                &leg.exchange_id,
+                &vec![&e, token_in_id.clone(), token_out_id.clone()],
+                leg.amount_in,
+                leg_amount_out_min,
+                deadline,
+                expiration_ledger,
+            );
+            let amount_out = token_out.balance(&constellation_token_id) - balance_before;
+            if amount_out < leg_amount_out_min {
+                return Err(Error::InsufficientOutputAmount);
+            }
+            total_amount_out += amount_out;
+
+            event::trade_leg(
+                &e,
+                constellation_token_id.clone(),
+                leg.exchange_id.clone(),
+                token_in_id.clone(),
+                token_out_id.clone(),
+                leg.amount_in,
+                amount_out,
+            );
+            executed_legs.push_back(SwapLeg {
+                exchange_id: leg.exchange_id,
+                amount_in: leg.amount_in,
+                amount_out,
+            });
+        }
+        if total_amount_out < amount_out_min {
+            return Err(Error::InsufficientOutputAmount);
+        }
+        // the legs are checked together, the order is valued at its average rate
+        let (amount_sold, _) = require_oracle_rate(
+            &e,
+            &registry_id,
+            &constellation_token_id,
+            &token_in_id,
+            &token_out_id,
+            balances_before,
+        )?;
+        limits.record_sale(&e, &token_in_id, amount_sold, balances_before.0)?;
+
+        update_units(&e, &constellation_token_id);
+        limits.end(&e);
+
+        Ok(executed_legs)
+    }
+
+    /// Returns the legs `trade_best_price` would execute, with their quoted amounts
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `token_in_id` Token sold
+    /// - `token_out_id` Token bought
+    /// - `amount_in` Amount of `token_in_id` sold
+    /// - `parts` Number of parts the order is split into across exchanges
+    pub fn quote_best_price(
+        e: Env,
+        token_in_id: Address,
+        token_out_id: Address,
+        amount_in: i128,
+        parts: u32,
+    ) -> Result<Vec<SwapLeg>, Error> {
+        let registry_id = require_registry(&e)?;
+        let venues = get_venues(&e, &registry_id);
+        Self::quote_legs(&e, &venues, &token_in_id, &token_out_id, amount_in, parts)
+    }
+
+    fn quote_legs(
+        e: &Env,
+        venues: &Vec<Venue>,
+        token_in_id: &Address,
+        token_out_id: &Address,
+        amount_in: i128,
+        parts: u32,
+    ) -> Result<Vec<SwapLeg>, Error> {
+        if parts == 0 || parts > MAX_SPLIT_PARTS {
+            return Err(Error::InvalidSplit);
+        }
+        let path = vec![e, token_in_id.clone(), token_out_id.clone()];
+        let legs = if parts == 1 {
+            match best_quote_out(e, venues, amount_in, &path) {
+                Some(leg) => vec![e, leg],
+                None => vec![e],
+            }
+        } else {
+            split_quote_out(e, venues, amount_in, &path, parts)
+        };
+        if legs.is_empty() {
+            return Err(Error::NoQuote);
+        }
+        Ok(legs)
+    }
+
     fn approve_exchange(
         e: &Env,
         constellation_token_id: &Address,
         token_id: &Address,
         call_data: &(Symbol /* function */, Vec<Val>),
     ) {
-        token::invoke(&e, constellation_token_id, token_id, &call_data, &vec![e]);
+        invoke(&e, constellation_token_id, token_id, &call_data, &vec![e]);
     }
     fn execute_trade(
         e: &Env,
@@ -125,7 +668,7 @@ impl Trade {
 
         auth_entries: &Vec<InvokerContractAuthEntry>,
     ) {
-        token::invoke(
+        invoke(
             &e,
             constellation_token_id,
             exchange_id,
@@ -134,3 +677,19 @@ impl Trade {
         );
     }
 }
+
+#[contractimpl]
+impl ModuleHooks for Trade {
+    /// Clears the trade limits of a constellation token the module was removed from
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token the module was removed from
+    fn on_remove(e: Env, constellation_token_id: Address) {
+        constellation_token_id.require_auth();
+        remove_max_slippage(&e, &constellation_token_id);
+        remove_max_price_deviation(&e, &constellation_token_id);
+        remove_risk_limits(&e, &constellation_token_id);
+    }
+}
@@ -1,3 +1,4 @@
+use constellation_lib::module::ModuleError;
 use soroban_sdk::{contracterror, contractimpl};
 
 #[contracterror]
@@ -10,4 +11,37 @@ pub enum Error {
     RequiresRegistry = 104,
     RequiresExchangeAdapter = 105,
     RequiresManage = 106,
+    NoQuote = 107,
+    InsufficientOutputAmount = 108,
+    InvalidSplit = 109,
+    PairNotFound = 110,
+    SlippageExceedsLimit = 111,
+    InvalidSlippage = 112,
+    ValueTooLargeOverFlow = 113,
+    ExcessiveInputAmount = 114,
+    InvalidPath = 115,
+    InvalidBatch = 116,
+    RequiresOracle = 117,
+    StalePrice = 118,
+    PriceDeviationExceedsLimit = 119,
+    InvalidDeviation = 120,
+    DeviationExceedsCeiling = 121,
+    InvalidRiskLimits = 122,
+    TradeCooldown = 123,
+    NotionalExceedsLimit = 124,
+    NavUnavailable = 125,
+}
+
+impl From<ModuleError> for Error {
+    fn from(error: ModuleError) -> Self {
+        match error {
+            ModuleError::RequiresRegistry => Error::RequiresRegistry,
+            ModuleError::RequiresExchangeAdapter => Error::RequiresExchangeAdapter,
+            ModuleError::RequiresManage => Error::RequiresManage,
+            ModuleError::PairNotFound => Error::PairNotFound,
+            ModuleError::RequiresOracle => Error::RequiresOracle,
+            ModuleError::StalePrice => Error::StalePrice,
+            ModuleError::ValueTooLargeOverFlow => Error::ValueTooLargeOverFlow,
+        }
+    }
 }
@@ -0,0 +1,101 @@
+use crate::types::RiskLimits;
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TradeLeg {
+    constellation_token_id: Address,
+    exchange_id: Address,
+    token_in_id: Address,
+    token_out_id: Address,
+    amount_in: i128,
+    amount_out: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetMaxSlippage {
+    constellation_token_id: Address,
+    max_slippage_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetMaxPriceDeviation {
+    constellation_token_id: Address,
+    max_deviation_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetRiskLimits {
+    constellation_token_id: Address,
+    limits: RiskLimits,
+}
+
+/// Emits the exchange and amounts of one leg of a trade
+pub(crate) fn trade_leg(
+    e: &Env,
+    constellation_token_id: Address,
+    exchange_id: Address,
+    token_in_id: Address,
+    token_out_id: Address,
+    amount_in: i128,
+    amount_out: i128,
+) {
+    let topics = (Symbol::new(e, "trade_leg"), constellation_token_id.clone());
+    e.events().publish(
+        topics,
+        TradeLeg {
+            constellation_token_id,
+            exchange_id,
+            token_in_id,
+            token_out_id,
+            amount_in,
+            amount_out,
+        },
+    );
+}
+
+pub(crate) fn set_max_slippage(e: &Env, constellation_token_id: Address, max_slippage_bps: u32) {
+    let topics = (Symbol::new(e, "set_max_slippage"), constellation_token_id.clone());
+    e.events().publish(
+        topics,
+        SetMaxSlippage {
+            constellation_token_id,
+            max_slippage_bps,
+        },
+    );
+}
+
+pub(crate) fn set_max_price_deviation(
+    e: &Env,
+    constellation_token_id: Address,
+    max_deviation_bps: u32,
+) {
+    let topics = (
+        Symbol::new(e, "set_max_price_deviation"),
+        constellation_token_id.clone(),
+    );
+    e.events().publish(
+        topics,
+        SetMaxPriceDeviation {
+            constellation_token_id,
+            max_deviation_bps,
+        },
+    );
+}
+
+pub(crate) fn set_risk_limits(e: &Env, constellation_token_id: Address, limits: RiskLimits) {
+    let topics = (
+        Symbol::new(e, "set_risk_limits"),
+        constellation_token_id.clone(),
+    );
+    e.events().publish(
+        topics,
+        SetRiskLimits {
+            constellation_token_id,
+            limits,
+        },
+    );
+}
@@ -1,9 +1,12 @@
 #![no_std]
 pub mod contract;
 mod error;
+mod event;
+mod limits;
 mod registry;
 mod storage;
 mod token;
+pub mod types;
 mod validation;
 
 #[cfg(test)]
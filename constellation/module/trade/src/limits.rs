@@ -0,0 +1,131 @@
+use crate::error::Error;
+use crate::storage::limits::{read_risk_limits, read_trade_activity, write_trade_activity};
+use crate::storage::types::BPS_DENOMINATOR;
+use crate::token::{get_nav, get_oracle};
+use crate::types::{NotionalBasis, RiskLimits, TradeActivity};
+use constellation_lib::math::{mul_div, Rounding};
+use constellation_lib::oracle::{asset_value, config_price};
+use soroban_sdk::{token::TokenClient, Address, Env, Map};
+
+/// Risk controls applied to one trade of a constellation token, from its start
+/// until its units are updated
+pub(crate) struct TradeLimits {
+    constellation_token_id: Address,
+    limits: Option<RiskLimits>,
+    activity: TradeActivity,
+}
+
+impl TradeLimits {
+    /// Starts a trade of `constellation_token_id`, rolling the trade activity over to the current epoch
+    /// Returns error if the previous trade is less than `min_trade_interval` ledgers old
+    pub fn begin(e: &Env, constellation_token_id: &Address) -> Result<Self, Error> {
+        let limits = read_risk_limits(e, constellation_token_id);
+        let previous = read_trade_activity(e, constellation_token_id);
+        let ledger = e.ledger().sequence();
+
+        if let (Some(limits), Some(previous)) = (&limits, &previous) {
+            let next_trade_ledger = previous
+                .last_trade_ledger
+                .saturating_add(limits.min_trade_interval);
+            if ledger < next_trade_ledger {
+                return Err(Error::TradeCooldown);
+            }
+        }
+
+        let epoch = match &limits {
+            Some(limits) if limits.epoch_ledgers > 0 => ledger / limits.epoch_ledgers,
+            _ => 0,
+        };
+        let mut activity = match previous {
+            Some(activity) if activity.epoch == epoch => activity,
+            _ => TradeActivity {
+                epoch,
+                last_trade_ledger: ledger,
+                opening_value: 0,
+                value_sold: 0,
+                sold: Map::new(e),
+            },
+        };
+        activity.last_trade_ledger = ledger;
+
+        // the fund value limit is measured against the value before the first trade of the epoch
+        if let Some(limits) = &limits {
+            if limits.max_notional_bps > 0
+                && limits.notional_basis == NotionalBasis::FundValue
+                && activity.opening_value == 0
+            {
+                activity.opening_value =
+                    get_nav(e, constellation_token_id).ok_or(Error::NavUnavailable)?;
+            }
+        }
+
+        Ok(Self {
+            constellation_token_id: constellation_token_id.clone(),
+            limits,
+            activity,
+        })
+    }
+
+    /// Records the sale of `amount_in` of `token_in_id`, out of `balance_in` held before it
+    /// Returns error if the notional sold in the epoch exceeds `max_notional_bps` of its basis
+    pub fn record_sale(
+        &mut self,
+        e: &Env,
+        token_in_id: &Address,
+        amount_in: i128,
+        balance_in: i128,
+    ) -> Result<(), Error> {
+        let limits = match &self.limits {
+            Some(limits) if limits.max_notional_bps > 0 => limits,
+            _ => return Ok(()),
+        };
+        let (sold, basis) = match limits.notional_basis {
+            NotionalBasis::FundValue => {
+                let oracle =
+                    get_oracle(e, &self.constellation_token_id).ok_or(Error::RequiresOracle)?;
+                let price = config_price(e, &oracle, token_in_id).ok_or(Error::StalePrice)?;
+                let decimals = TokenClient::new(e, token_in_id).decimals();
+                let value =
+                    asset_value(amount_in, price, decimals).ok_or(Error::ValueTooLargeOverFlow)?;
+                self.activity.value_sold = self
+                    .activity
+                    .value_sold
+                    .checked_add(value)
+                    .ok_or(Error::ValueTooLargeOverFlow)?;
+                (self.activity.value_sold, self.activity.opening_value)
+            }
+            NotionalBasis::ComponentBalance => {
+                let (opening_balance, sold) = self
+                    .activity
+                    .sold
+                    .get(token_in_id.clone())
+                    .unwrap_or((balance_in, 0));
+                let sold = sold
+                    .checked_add(amount_in)
+                    .ok_or(Error::ValueTooLargeOverFlow)?;
+                self.activity
+                    .sold
+                    .set(token_in_id.clone(), (opening_balance, sold));
+                (sold, opening_balance)
+            }
+        };
+        let max_notional = mul_div(
+            basis,
+            limits.max_notional_bps as i128,
+            BPS_DENOMINATOR as i128,
+            Rounding::Down,
+        )
+        .ok_or(Error::ValueTooLargeOverFlow)?;
+        if sold > max_notional {
+            return Err(Error::NotionalExceedsLimit);
+        }
+        Ok(())
+    }
+
+    /// Ends the trade once the units are updated, and records it in the trade activity
+    pub fn end(self, e: &Env) {
+        if self.limits.is_some() {
+            write_trade_activity(e, &self.constellation_token_id, &self.activity);
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use soroban_sdk::{Address, Env, String, Symbol, Val, Vec};
+use constellation_lib::types::exchange::Venue;
+use soroban_sdk::{vec, Address, Env, String, Symbol, Val, Vec};
 
 pub(crate) mod registry {
     soroban_sdk::contractimport!(
@@ -6,20 +7,23 @@ pub(crate) mod registry {
     );
 }
 
-/// Invokes the invoke function of the constellation token to trade / exchange tokens
+/// Returns the exchanges with an adapter registered for the trade module, with their adapters
 ///
 /// # Arguments
 ///
 /// - `e` The runtime environment.
-/// - `constellation_token_id` Target constellation token id
-/// - `target_exchange_id` Target exchange identifier
-/// - `function` Name of function to invoke on target exchange
-/// - `data` Function arguments
-pub(crate) fn get_adapter_id(
-    e: &Env,
-    registry_id: &Address,
-    target_exchange_id: &Address,
-) -> Option<Address> {
-    let client = registry::Client::new(&e, &registry_id);
-    client.get_adapter_id(&e.current_contract_address(), &target_exchange_id)
+/// - `registry_id` Registry of the protocol
+pub(crate) fn get_venues(e: &Env, registry_id: &Address) -> Vec<Venue> {
+    let client = registry::Client::new(e, registry_id);
+    let mut venues: Vec<Venue> = vec![e];
+    for exchange_id in client.get_exchanges(&e.current_contract_address()).iter() {
+        if let Some(adapter_id) = client.get_adapter_id(&e.current_contract_address(), &exchange_id)
+        {
+            venues.push_back(Venue {
+                exchange_id,
+                adapter_id,
+            });
+        }
+    }
+    venues
 }
@@ -0,0 +1,36 @@
+use soroban_sdk::{Address, Env};
+
+use super::keys::DataKey;
+use super::types::{PERSISTENT_LEDGER_LIFE, PERSISTENT_LEDGER_TTL_THRESHOLD};
+
+pub fn read_max_price_deviation(e: &Env, constellation_token_id: &Address) -> Option<u32> {
+    let key = DataKey::MaxPriceDeviation(constellation_token_id.clone());
+    let max_deviation_bps = e.storage().persistent().get(&key);
+    if max_deviation_bps.is_some() {
+        e.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LEDGER_TTL_THRESHOLD,
+            PERSISTENT_LEDGER_LIFE,
+        );
+    }
+    max_deviation_bps
+}
+
+pub fn write_max_price_deviation(
+    e: &Env,
+    constellation_token_id: &Address,
+    max_deviation_bps: u32,
+) {
+    let key = DataKey::MaxPriceDeviation(constellation_token_id.clone());
+    e.storage().persistent().set(&key, &max_deviation_bps);
+    e.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LEDGER_TTL_THRESHOLD,
+        PERSISTENT_LEDGER_LIFE,
+    );
+}
+
+pub fn remove_max_price_deviation(e: &Env, constellation_token_id: &Address) {
+    let key = DataKey::MaxPriceDeviation(constellation_token_id.clone());
+    e.storage().persistent().remove(&key);
+}
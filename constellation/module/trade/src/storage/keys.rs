@@ -4,6 +4,9 @@ use soroban_sdk::{contracttype, Address};
 #[contracttype]
 pub enum DataKey {
     Admin,
-    Registry,
     Adapter(Address),
+    MaxSlippage(Address),
+    MaxPriceDeviation(Address),
+    RiskLimits(Address),
+    TradeActivity(Address),
 }
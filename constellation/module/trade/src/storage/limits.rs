@@ -0,0 +1,52 @@
+use soroban_sdk::{Address, Env};
+
+use super::keys::DataKey;
+use super::types::{PERSISTENT_LEDGER_LIFE, PERSISTENT_LEDGER_TTL_THRESHOLD};
+use crate::types::{RiskLimits, TradeActivity};
+
+pub fn read_risk_limits(e: &Env, constellation_token_id: &Address) -> Option<RiskLimits> {
+    let key = DataKey::RiskLimits(constellation_token_id.clone());
+    let limits = e.storage().persistent().get(&key);
+    if limits.is_some() {
+        e.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LEDGER_TTL_THRESHOLD,
+            PERSISTENT_LEDGER_LIFE,
+        );
+    }
+    limits
+}
+
+pub fn write_risk_limits(e: &Env, constellation_token_id: &Address, limits: &RiskLimits) {
+    let key = DataKey::RiskLimits(constellation_token_id.clone());
+    e.storage().persistent().set(&key, limits);
+    e.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LEDGER_TTL_THRESHOLD,
+        PERSISTENT_LEDGER_LIFE,
+    );
+}
+
+pub fn read_trade_activity(e: &Env, constellation_token_id: &Address) -> Option<TradeActivity> {
+    let key = DataKey::TradeActivity(constellation_token_id.clone());
+    e.storage().persistent().get(&key)
+}
+
+pub fn write_trade_activity(e: &Env, constellation_token_id: &Address, activity: &TradeActivity) {
+    let key = DataKey::TradeActivity(constellation_token_id.clone());
+    e.storage().persistent().set(&key, activity);
+    e.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LEDGER_TTL_THRESHOLD,
+        PERSISTENT_LEDGER_LIFE,
+    );
+}
+
+pub fn remove_risk_limits(e: &Env, constellation_token_id: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::RiskLimits(constellation_token_id.clone()));
+    e.storage()
+        .persistent()
+        .remove(&DataKey::TradeActivity(constellation_token_id.clone()));
+}
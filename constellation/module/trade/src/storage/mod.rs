@@ -1,3 +1,6 @@
 pub mod admin;
+pub mod deviation;
 pub mod keys;
-pub mod registry;
+pub mod limits;
+pub mod slippage;
+pub mod types;
@@ -0,0 +1,36 @@
+use soroban_sdk::{Address, Env};
+
+use super::keys::DataKey;
+use super::types::{
+    DEFAULT_MAX_SLIPPAGE_BPS, PERSISTENT_LEDGER_LIFE, PERSISTENT_LEDGER_TTL_THRESHOLD,
+};
+
+pub fn read_max_slippage(e: &Env, constellation_token_id: &Address) -> u32 {
+    let key = DataKey::MaxSlippage(constellation_token_id.clone());
+    match e.storage().persistent().get(&key) {
+        Some(max_slippage_bps) => {
+            e.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LEDGER_TTL_THRESHOLD,
+                PERSISTENT_LEDGER_LIFE,
+            );
+            max_slippage_bps
+        }
+        None => DEFAULT_MAX_SLIPPAGE_BPS,
+    }
+}
+
+pub fn write_max_slippage(e: &Env, constellation_token_id: &Address, max_slippage_bps: u32) {
+    let key = DataKey::MaxSlippage(constellation_token_id.clone());
+    e.storage().persistent().set(&key, &max_slippage_bps);
+    e.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LEDGER_TTL_THRESHOLD,
+        PERSISTENT_LEDGER_LIFE,
+    );
+}
+
+pub fn remove_max_slippage(e: &Env, constellation_token_id: &Address) {
+    let key = DataKey::MaxSlippage(constellation_token_id.clone());
+    e.storage().persistent().remove(&key);
+}
@@ -0,0 +1,7 @@
+pub(crate) const DAY_IN_LEDGERS: u32 = 17280;
+pub(crate) const PERSISTENT_LEDGER_LIFE: u32 = 90 * DAY_IN_LEDGERS; // ~90 days.
+pub(crate) const PERSISTENT_LEDGER_TTL_THRESHOLD: u32 = PERSISTENT_LEDGER_LIFE - DAY_IN_LEDGERS;
+
+pub(crate) const BPS_DENOMINATOR: u32 = 10_000;
+/// Slippage allowed below the exchange quote when the manager has not set one, 1%
+pub(crate) const DEFAULT_MAX_SLIPPAGE_BPS: u32 = 100;
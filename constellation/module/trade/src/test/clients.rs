@@ -1,7 +1,10 @@
+use super::mock_exchange::{MockExchange, MockExchangeClient};
 use crate::contract::{Trade, TradeClient};
+use constellation_lib::testutils::{MockOracle, MockOracleClient};
 use soroban_sdk::{Address, BytesN, Env};
 
 pub use adapter::TradeAdapterClient;
+pub use constellation_factory::ConstellationFactoryClient;
 pub use constellation_token::ConstellationTokenClient;
 pub use factory::SoroswapFactoryClient;
 pub use registry::RegistryClient;
@@ -16,6 +19,13 @@ mod constellation_token {
     pub type ConstellationTokenClient<'a> = Client<'a>;
 }
 
+mod constellation_factory {
+    soroban_sdk::contractimport!(
+        file = "../../../target/wasm32-unknown-unknown/release/constellation_factory.wasm"
+    );
+    pub type ConstellationFactoryClient<'a> = Client<'a>;
+}
+
 pub mod adapter {
     use soroban_sdk::auth::InvokerContractAuthEntry;
     soroban_sdk::contractimport!(
@@ -57,6 +67,21 @@ pub fn create_trade_module<'a>(e: &Env) -> TradeClient<'a> {
     TradeClient::new(e, contract_id)
 }
 
+pub fn create_constellation_factory<'a>(e: &Env) -> ConstellationFactoryClient<'a> {
+    ConstellationFactoryClient::new(
+        e,
+        &e.register_contract_wasm(None, constellation_factory::WASM),
+    )
+}
+
+pub fn create_mock_exchange<'a>(e: &Env) -> MockExchangeClient<'a> {
+    MockExchangeClient::new(e, &e.register_contract(None, MockExchange {}))
+}
+
+pub fn create_mock_oracle<'a>(e: &Env) -> MockOracleClient<'a> {
+    MockOracleClient::new(e, &e.register_contract(None, MockOracle {}))
+}
+
 pub fn create_soroswap_router<'a>(e: &Env) -> SoroswapRouterClient<'a> {
     SoroswapRouterClient::new(e, &e.register_contract_wasm(None, router::WASM))
 }
@@ -0,0 +1,179 @@
+use constellation_lib::traits::adapter::dex::{self, IExchange};
+use soroban_sdk::{
+    auth::InvokerContractAuthEntry, contract, contractimpl, contracttype, token, vec, Address,
+    Env, IntoVal, Symbol, Val, Vec,
+};
+
+#[derive(Clone)]
+#[contracttype]
+enum DataKey {
+    Reserve(Address),
+}
+
+/// Constant product exchange without fee, acting as its own adapter
+/// Swaps pull the input token with `transfer_from`, so no sub-auth is needed
+#[contract]
+pub struct MockExchange;
+
+fn read_reserve(e: &Env, token: &Address) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::Reserve(token.clone()))
+        .unwrap_or(0)
+}
+
+fn write_reserve(e: &Env, token: &Address, reserve: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Reserve(token.clone()), &reserve);
+}
+
+fn amount_out(e: &Env, amount_in: i128, path: &Vec<Address>) -> i128 {
+    if path.len() != 2 {
+        panic!("no pool");
+    }
+    let reserve_in = read_reserve(e, &path.get_unchecked(0));
+    let reserve_out = read_reserve(e, &path.get_unchecked(1));
+    if reserve_in == 0 || reserve_out == 0 {
+        panic!("no pool");
+    }
+    reserve_out * amount_in / (reserve_in + amount_in)
+}
+
+fn amount_in(e: &Env, amount_out: i128, path: &Vec<Address>) -> i128 {
+    if path.len() != 2 {
+        panic!("no pool");
+    }
+    let reserve_in = read_reserve(e, &path.get_unchecked(0));
+    let reserve_out = read_reserve(e, &path.get_unchecked(1));
+    if reserve_in == 0 || reserve_out <= amount_out {
+        panic!("no pool");
+    }
+    reserve_in * amount_out / (reserve_out - amount_out) + 1
+}
+
+#[contractimpl]
+impl MockExchange {
+    /// Sets the reserve of `token`, which the exchange must hold
+    pub fn set_reserve(e: Env, token: Address, reserve: i128) {
+        write_reserve(&e, &token, reserve);
+    }
+
+    pub fn swap(
+        e: Env,
+        amount_in: i128,
+        amount_out_min: i128,
+        path: Vec<Address>,
+        to: Address,
+        _deadline: u64,
+    ) -> Vec<i128> {
+        let token_in = path.get_unchecked(0);
+        let token_out = path.get_unchecked(1);
+        let amount_out = amount_out(&e, amount_in, &path);
+        if amount_out < amount_out_min {
+            panic!("insufficient output amount");
+        }
+        let exchange = e.current_contract_address();
+        token::Client::new(&e, &token_in).transfer_from(&exchange, &to, &exchange, &amount_in);
+        token::Client::new(&e, &token_out).transfer(&exchange, &to, &amount_out);
+        write_reserve(&e, &token_in, read_reserve(&e, &token_in) + amount_in);
+        write_reserve(&e, &token_out, read_reserve(&e, &token_out) - amount_out);
+        vec![&e, amount_in, amount_out]
+    }
+}
+
+#[contractimpl]
+impl dex::Interface for MockExchange {
+    fn get_swap_call_data(
+        e: &Env,
+        token_in_id: Address,
+        token_out_id: Address,
+        amount_in: i128,
+        amount_out: i128,
+        to: Address,
+        deadline: u64,
+    ) -> (Symbol, Vec<Val>) {
+        Self::get_path_swap_call_data(
+            e,
+            vec![e, token_in_id, token_out_id],
+            amount_in,
+            amount_out,
+            to,
+            deadline,
+        )
+    }
+
+    fn get_approve_call_data(
+        e: &Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> (Symbol, Vec<Val>) {
+        let args: Vec<Val> = vec![
+            e,
+            from.into_val(e),
+            spender.into_val(e),
+            amount.into_val(e),
+            expiration_ledger.into_val(e),
+        ];
+        (Symbol::new(e, "approve"), args)
+    }
+
+    fn create_sub_auth(
+        e: &Env,
+        _amount_in: i128,
+        _token_in: Address,
+        _token_out: Address,
+        _constellation_token_id: Address,
+    ) -> Vec<InvokerContractAuthEntry> {
+        vec![e]
+    }
+
+    fn get_path_amounts_out(e: &Env, amount_in: i128, path: Vec<Address>) -> Vec<i128> {
+        vec![e, amount_in, amount_out(e, amount_in, &path)]
+    }
+
+    fn get_path_amounts_in(e: &Env, amount_out: i128, path: Vec<Address>) -> Vec<i128> {
+        vec![e, amount_in(e, amount_out, &path), amount_out]
+    }
+
+    fn get_path_swap_call_data(
+        e: &Env,
+        path: Vec<Address>,
+        amount_in: i128,
+        amount_out_min: i128,
+        to: Address,
+        deadline: u64,
+    ) -> (Symbol, Vec<Val>) {
+        let args: Vec<Val> = vec![
+            e,
+            amount_in.into_val(e),
+            amount_out_min.into_val(e),
+            path.into_val(e),
+            to.into_val(e),
+            deadline.into_val(e),
+        ];
+        (Symbol::new(e, "swap"), args)
+    }
+
+    fn get_path_swap_out_call_data(
+        _e: &Env,
+        _path: Vec<Address>,
+        _amount_out: i128,
+        _amount_in_max: i128,
+        _to: Address,
+        _deadline: u64,
+    ) -> (Symbol, Vec<Val>) {
+        panic!("exact output swaps are not supported")
+    }
+
+    fn create_path_sub_auth(
+        e: &Env,
+        _amount_in: i128,
+        _path: Vec<Address>,
+        _from: Address,
+    ) -> Vec<InvokerContractAuthEntry> {
+        vec![e]
+    }
+}
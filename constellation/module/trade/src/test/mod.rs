@@ -1,4 +1,5 @@
 mod add_liquidity;
 mod clients;
+mod mock_exchange;
 mod setup;
 mod test;
@@ -2,12 +2,13 @@ extern crate std;
 
 use super::add_liquidity::add_liquidity;
 use super::clients::{
-    create_adapter, create_constellation_token, create_registry, create_soroswap_factory,
-    create_soroswap_router, create_token_contract, create_trade_module, pair_contract_wasm,
-    registry, ConstellationTokenClient, RegistryClient, SoroswapFactoryClient,
+    create_adapter, create_constellation_token, create_mock_oracle, create_registry,
+    create_soroswap_factory, create_soroswap_router, create_token_contract, create_trade_module,
+    pair_contract_wasm, registry, ConstellationTokenClient, RegistryClient, SoroswapFactoryClient,
     SoroswapRouterClient, TokenClient, TradeAdapterClient,
 };
 use crate::contract::TradeClient;
+use constellation_lib::testutils::MockOracleClient;
 use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
 pub type Tokens<'a> = (
     TokenClient<'a>,
@@ -22,6 +23,7 @@ pub struct TradeTest<'a> {
     pub admin: Address,
     pub adapter: TradeAdapterClient<'a>,
     pub registry: RegistryClient<'a>,
+    pub oracle: MockOracleClient<'a>,
     pub router: SoroswapRouterClient<'a>,
     pub factory: SoroswapFactoryClient<'a>,
     pub constellation_token: ConstellationTokenClient<'a>,
@@ -108,6 +110,14 @@ impl<'a> TradeTest<'a> {
             &amount_1,
         );
 
+        // the registry oracle values the four tokens at 1 USD
+        let oracle = create_mock_oracle(&env);
+        oracle.set_price(&tokens.0.address, &10_000_000);
+        oracle.set_price(&tokens.1.address, &10_000_000);
+        oracle.set_price(&tokens.2.address, &10_000_000);
+        oracle.set_price(&tokens.3.address, &10_000_000);
+        registry.set_oracle(&oracle.address, &3_600);
+
         env.budget().reset_unlimited();
 
         Self {
@@ -117,6 +127,7 @@ impl<'a> TradeTest<'a> {
             adapter,
             router,
             registry,
+            oracle,
             factory,
             constellation_token,
             tokens,
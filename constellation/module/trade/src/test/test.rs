@@ -1,13 +1,22 @@
+use super::clients::{create_constellation_factory, create_mock_exchange, create_mock_oracle};
+use super::mock_exchange::MockExchangeClient;
 use super::setup::TradeTest;
-use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, IntoVal, String, Vec};
+use crate::error::Error;
+use crate::storage::types::DEFAULT_MAX_SLIPPAGE_BPS;
+use crate::types::{NotionalBasis, RiskLimits, TradeOrder};
+use constellation_lib::module::DEFAULT_MAX_PRICE_DEVIATION_BPS;
+use soroban_sdk::{
+    testutils::Address as _, testutils::Ledger, vec, Address, BytesN, Env, IntoVal, InvokeError,
+    String, Vec,
+};
 extern crate std;
 
 #[test]
 fn test_trade() {
     let test = TradeTest::setup();
     test.env.mock_all_auths();
-    // units
-    let units = vec![&test.env, 1000, 1000];
+    // units - 1000 component base units per whole constellation token
+    let units = vec![&test.env, 1000 * 10i128.pow(6), 1000 * 10i128.pow(6)];
     // components
     let components: Vec<Address> = vec![
         &test.env,
@@ -53,7 +62,9 @@ fn test_trade() {
     test.constellation_token
         .set_registry(&test.registry.address);
     test.constellation_token
-        .add_module(&test.trade_module.address);
+        .add_module(&test.trade_module.address, &None);
+    test.trade_module
+        .initialize_token(&test.constellation_token.address, &DEFAULT_MAX_SLIPPAGE_BPS);
 
     assert_eq!(test.constellation_token.balance(&test.user), 10);
     let balance_before_trade_token_0 = test.tokens.0.balance(&test.constellation_token.address);
@@ -84,6 +95,7 @@ fn test_trade() {
         &test.tokens.2.address,
         &amount_in,
         &amount_out,
+        &None,
         &deadline,
         &expiration_ledger,
     );
@@ -111,20 +123,671 @@ fn test_trade() {
             .get_component(&test.tokens.1.address)
             .unwrap()
             .unit,
-        1000
+        1000 * 10i128.pow(6)
     );
     assert_eq!(
         test.constellation_token
             .get_component(&test.tokens.0.address)
             .unwrap()
             .unit,
-        500
+        500 * 10i128.pow(6)
     );
     assert_eq!(
         test.constellation_token
             .get_component(&test.tokens.2.address)
             .unwrap()
             .unit,
-        498
+        498_400_000
     );
 }
+
+/// Mints 10 constellation tokens backed by 10_000 of tokens 0 and 1, and registers the trade module
+/// with the soroswap router and `mock_exchange` as exchanges
+fn initialize_best_price_trade(test: &TradeTest, mock_exchange: &MockExchangeClient) {
+    let units = vec![&test.env, 1000 * 10i128.pow(6), 1000 * 10i128.pow(6)];
+    let components: Vec<Address> = vec![
+        &test.env,
+        test.tokens.0.address.clone(),
+        test.tokens.1.address.clone(),
+    ];
+    test.constellation_token.initialize(
+        &6u32,
+        &components,
+        &units,
+        &"c_token".into_val(&test.env),
+        &"token_symbol".into_val(&test.env),
+        &test.user,
+        &Address::generate(&test.env),
+    );
+    test.tokens.0.approve(&test.user, &test.constellation_token.address, &10_000i128, &1000u32);
+    test.tokens.1.approve(&test.user, &test.constellation_token.address, &10_000i128, &1000u32);
+    test.constellation_token.mint(&test.user, &10i128);
+
+    test.registry.add_module(&test.trade_module.address);
+    test.registry.add_adapter(
+        &test.trade_module.address,
+        &test.router.address,
+        &test.adapter.address,
+    );
+    test.registry.add_adapter(
+        &test.trade_module.address,
+        &mock_exchange.address,
+        &mock_exchange.address,
+    );
+    test.constellation_token
+        .set_registry(&test.registry.address);
+    test.constellation_token
+        .add_module(&test.trade_module.address, &None);
+    test.trade_module
+        .initialize_token(&test.constellation_token.address, &DEFAULT_MAX_SLIPPAGE_BPS);
+
+    // a shallow pool with a better price than soroswap for small amounts
+    test.tokens.0.mint(&mock_exchange.address, &2000);
+    test.tokens.2.mint(&mock_exchange.address, &4000);
+    mock_exchange.set_reserve(&test.tokens.0.address, &2000);
+    mock_exchange.set_reserve(&test.tokens.2.address, &4000);
+}
+
+#[test]
+fn quote_best_price_picks_exchange_with_best_output() {
+    let test = TradeTest::setup();
+    let mock_exchange = create_mock_exchange(&test.env);
+    initialize_best_price_trade(&test, &mock_exchange);
+    let (t0, t2) = (test.tokens.0.address.clone(), test.tokens.2.address.clone());
+
+    // the mock exchange gives more for small amounts
+    let legs = test.trade_module.quote_best_price(&t0, &t2, &100, &1);
+    assert_eq!(legs.len(), 1);
+    assert_eq!(legs.get(0).unwrap().exchange_id, mock_exchange.address);
+    assert_eq!(legs.get(0).unwrap().amount_out, 4000 * 100 / 2100);
+
+    // and soroswap for large ones
+    let legs = test.trade_module.quote_best_price(&t0, &t2, &5000, &1);
+    assert_eq!(legs.len(), 1);
+    assert_eq!(legs.get(0).unwrap().exchange_id, test.router.address);
+
+    let result = test.trade_module.try_quote_best_price(&t0, &t2, &5000, &0);
+    assert_eq!(result, Err(Ok(Error::InvalidSplit)));
+    let result = test
+        .trade_module
+        .try_quote_best_price(&test.tokens.1.address, &test.tokens.3.address, &5000, &1);
+    assert_eq!(result, Err(Ok(Error::NoQuote)));
+}
+
+#[test]
+fn trade_best_price_splits_order_across_exchanges() {
+    let test = TradeTest::setup();
+    let mock_exchange = create_mock_exchange(&test.env);
+    initialize_best_price_trade(&test, &mock_exchange);
+    let (t0, t2) = (test.tokens.0.address.clone(), test.tokens.2.address.clone());
+    let ct = &test.constellation_token;
+    let deadline: u64 = test.env.ledger().timestamp() + 1000;
+
+    let amount_in = 5000i128;
+    let quoted_legs = test.trade_module.quote_best_price(&t0, &t2, &amount_in, &4);
+    let single_leg = test.trade_module.quote_best_price(&t0, &t2, &amount_in, &1);
+
+    let result = test.trade_module.try_trade_best_price(
+        &ct.address,
+        &t0,
+        &t2,
+        &amount_in,
+        &10_000,
+        &4,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(result, Err(Ok(Error::InsufficientOutputAmount)));
+
+    let legs = test.trade_module.trade_best_price(
+        &ct.address,
+        &t0,
+        &t2,
+        &amount_in,
+        &single_leg.get(0).unwrap().amount_out,
+        &4,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(legs, quoted_legs);
+    assert_eq!(legs.len(), 2);
+    let soroswap_leg = legs.get(0).unwrap();
+    let mock_leg = legs.get(1).unwrap();
+    assert_eq!(soroswap_leg.exchange_id, test.router.address);
+    assert_eq!(mock_leg.exchange_id, mock_exchange.address);
+    assert_eq!(soroswap_leg.amount_in + mock_leg.amount_in, amount_in);
+    assert_eq!(mock_leg.amount_in, 1250);
+    assert_eq!(mock_leg.amount_out, 4000 * 1250 / 3250);
+
+    let amount_out = soroswap_leg.amount_out + mock_leg.amount_out;
+    assert!(amount_out > single_leg.get(0).unwrap().amount_out);
+    assert_eq!(test.tokens.2.balance(&ct.address), amount_out);
+    assert_eq!(test.tokens.0.balance(&ct.address), 10_000 - amount_in);
+    assert_eq!(
+        ct.get_component(&t2).unwrap().unit,
+        amount_out * 10i128.pow(6) / 10
+    );
+    assert_eq!(ct.get_component(&t0).unwrap().unit, 500 * 10i128.pow(6));
+}
+
+#[test]
+fn trade_fails_with_slippage_exceeds_limit() {
+    let test = TradeTest::setup();
+    let mock_exchange = create_mock_exchange(&test.env);
+    initialize_best_price_trade(&test, &mock_exchange);
+    let (t0, t2) = (test.tokens.0.address.clone(), test.tokens.2.address.clone());
+    let ct = &test.constellation_token;
+    let deadline: u64 = test.env.ledger().timestamp() + 1000;
+    let amount_in = 5000i128;
+
+    assert_eq!(test.trade_module.get_max_slippage(&ct.address), 100);
+    let (expected_amount_out, amount_out_min) = test.trade_module.quote_trade(
+        &ct.address,
+        &test.router.address,
+        &t0,
+        &t2,
+        &amount_in,
+        &None,
+    );
+    assert_eq!(expected_amount_out, 4984);
+    assert_eq!(amount_out_min, 4935);
+
+    // there is no pair for token 1 and token 3
+    let result = test.trade_module.try_quote_trade(
+        &ct.address,
+        &test.router.address,
+        &test.tokens.1.address,
+        &test.tokens.3.address,
+        &amount_in,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(Error::PairNotFound)));
+
+    let result = test.trade_module.try_trade(
+        &ct.address,
+        &test.router.address,
+        &t0,
+        &t2,
+        &amount_in,
+        &(amount_out_min - 1),
+        &None,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(result, Err(Ok(Error::SlippageExceedsLimit)));
+
+    let result = test.trade_module.try_set_max_slippage(&ct.address, &10_001);
+    assert_eq!(result, Err(Ok(Error::InvalidSlippage)));
+    test.trade_module.set_max_slippage(&ct.address, &500);
+    test.trade_module.trade(
+        &ct.address,
+        &test.router.address,
+        &t0,
+        &t2,
+        &amount_in,
+        &(amount_out_min - 1),
+        &None,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(test.tokens.2.balance(&ct.address), expected_amount_out);
+}
+
+#[test]
+fn trade_exact_out_buys_exact_amount() {
+    let test = TradeTest::setup();
+    let mock_exchange = create_mock_exchange(&test.env);
+    initialize_best_price_trade(&test, &mock_exchange);
+    let (t0, t2) = (test.tokens.0.address.clone(), test.tokens.2.address.clone());
+    let ct = &test.constellation_token;
+    let deadline: u64 = test.env.ledger().timestamp() + 1000;
+    let amount_out = 3000i128;
+
+    let (expected_amount_in, amount_in_max) = test.trade_module.quote_trade_exact_out(
+        &ct.address,
+        &test.router.address,
+        &t0,
+        &t2,
+        &amount_out,
+        &None,
+    );
+    assert_eq!(amount_in_max, expected_amount_in * 10_100 / 10_000);
+
+    let result = test.trade_module.try_trade_exact_out(
+        &ct.address,
+        &test.router.address,
+        &t0,
+        &t2,
+        &amount_out,
+        &(expected_amount_in - 1),
+        &None,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(result, Err(Ok(Error::ExcessiveInputAmount)));
+    let result = test.trade_module.try_trade_exact_out(
+        &ct.address,
+        &test.router.address,
+        &t0,
+        &t2,
+        &amount_out,
+        &(amount_in_max + 1),
+        &None,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(result, Err(Ok(Error::SlippageExceedsLimit)));
+
+    let amount_in = test.trade_module.trade_exact_out(
+        &ct.address,
+        &test.router.address,
+        &t0,
+        &t2,
+        &amount_out,
+        &amount_in_max,
+        &None,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(amount_in, expected_amount_in);
+    assert_eq!(test.tokens.2.balance(&ct.address), amount_out);
+    assert_eq!(test.tokens.0.balance(&ct.address), 10_000 - amount_in);
+    assert_eq!(ct.get_component(&t2).unwrap().unit, amount_out * 10i128.pow(6) / 10);
+    assert_eq!(
+        ct.get_component(&t0).unwrap().unit,
+        (10_000 - amount_in) * 10i128.pow(6) / 10
+    );
+}
+
+#[test]
+fn trade_through_multi_hop_path_reconciles_only_endpoints() {
+    let test = TradeTest::setup();
+    let mock_exchange = create_mock_exchange(&test.env);
+    initialize_best_price_trade(&test, &mock_exchange);
+    let (t0, t1, t2, t3) = (
+        test.tokens.0.address.clone(),
+        test.tokens.1.address.clone(),
+        test.tokens.2.address.clone(),
+        test.tokens.3.address.clone(),
+    );
+    let ct = &test.constellation_token;
+    let deadline: u64 = test.env.ledger().timestamp() + 1000;
+    let amount_in = 1000i128;
+
+    // there is no pair for token 1 and token 3, route through tokens 0 and 2
+    let path = vec![&test.env, t1.clone(), t0.clone(), t2.clone(), t3.clone()];
+    let amounts_out = test.router.router_get_amounts_out(&amount_in, &path);
+    let expected_amount_out = amounts_out.last().unwrap();
+    let (quoted_amount_out, amount_out_min) = test.trade_module.quote_trade(
+        &ct.address,
+        &test.router.address,
+        &t1,
+        &t3,
+        &amount_in,
+        &Some(path.clone()),
+    );
+    assert_eq!(quoted_amount_out, expected_amount_out);
+
+    let invalid_path = vec![&test.env, t1.clone(), t0.clone(), t2.clone()];
+    let result = test.trade_module.try_trade(
+        &ct.address,
+        &test.router.address,
+        &t1,
+        &t3,
+        &amount_in,
+        &amount_out_min,
+        &Some(invalid_path),
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidPath)));
+
+    test.trade_module.trade(
+        &ct.address,
+        &test.router.address,
+        &t1,
+        &t3,
+        &amount_in,
+        &amount_out_min,
+        &Some(path),
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(test.tokens.1.balance(&ct.address), 10_000 - amount_in);
+    assert_eq!(test.tokens.3.balance(&ct.address), expected_amount_out);
+    assert_eq!(test.tokens.0.balance(&ct.address), 10_000);
+    assert_eq!(test.tokens.2.balance(&ct.address), 0);
+
+    let c = ct.get_components();
+    assert_eq!(c.len(), 3);
+    assert!(ct.get_component(&t2).is_none());
+    assert_eq!(ct.get_component(&t0).unwrap().unit, 1000 * 10i128.pow(6));
+    assert_eq!(ct.get_component(&t1).unwrap().unit, 900 * 10i128.pow(6));
+    assert_eq!(
+        ct.get_component(&t3).unwrap().unit,
+        expected_amount_out * 10i128.pow(6) / 10
+    );
+}
+
+#[test]
+fn batch_trade_updates_units_once_and_reverts_atomically() {
+    let test = TradeTest::setup();
+    let mock_exchange = create_mock_exchange(&test.env);
+    initialize_best_price_trade(&test, &mock_exchange);
+    let (t0, t1, t2, t3) = (
+        test.tokens.0.address.clone(),
+        test.tokens.1.address.clone(),
+        test.tokens.2.address.clone(),
+        test.tokens.3.address.clone(),
+    );
+    let ct = &test.constellation_token;
+    let deadline: u64 = test.env.ledger().timestamp() + 1000;
+
+    let path = vec![&test.env, t1.clone(), t0.clone(), t3.clone()];
+    let amount_out_2 = test
+        .router
+        .router_get_amounts_out(&1000, &vec![&test.env, t0.clone(), t2.clone()])
+        .last()
+        .unwrap();
+    let amount_out_3 = test
+        .router
+        .router_get_amounts_out(&2000, &path)
+        .last()
+        .unwrap();
+    let order_2 = TradeOrder {
+        exchange_id: test.router.address.clone(),
+        token_in_id: t0.clone(),
+        token_out_id: t2.clone(),
+        amount_in: 1000,
+        amount_out: amount_out_2,
+        path: vec![&test.env],
+    };
+    let order_3 = TradeOrder {
+        exchange_id: test.router.address.clone(),
+        token_in_id: t1.clone(),
+        token_out_id: t3.clone(),
+        amount_in: 2000,
+        amount_out: amount_out_3,
+        path,
+    };
+
+    let result = test
+        .trade_module
+        .try_batch_trade(&ct.address, &vec![&test.env], &deadline, &1000u32);
+    assert_eq!(result, Err(Ok(Error::InvalidBatch)));
+
+    // the second trade has no direct pair, the first one is reverted with it
+    let direct_order_3 = TradeOrder {
+        path: vec![&test.env],
+        ..order_3.clone()
+    };
+    let result = test.trade_module.try_batch_trade(
+        &ct.address,
+        &vec![&test.env, order_2.clone(), direct_order_3],
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(result, Err(Ok(Error::PairNotFound)));
+    assert_eq!(test.tokens.0.balance(&ct.address), 10_000);
+    assert_eq!(test.tokens.2.balance(&ct.address), 0);
+    assert_eq!(ct.get_components().len(), 2);
+
+    let amounts_out = test.trade_module.batch_trade(
+        &ct.address,
+        &vec![&test.env, order_2, order_3],
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(amounts_out, vec![&test.env, amount_out_2, amount_out_3]);
+    assert_eq!(test.tokens.0.balance(&ct.address), 9000);
+    assert_eq!(test.tokens.1.balance(&ct.address), 8000);
+
+    assert_eq!(ct.get_components().len(), 4);
+    assert_eq!(ct.get_component(&t0).unwrap().unit, 900 * 10i128.pow(6));
+    assert_eq!(ct.get_component(&t1).unwrap().unit, 800 * 10i128.pow(6));
+    assert_eq!(
+        ct.get_component(&t2).unwrap().unit,
+        amount_out_2 * 10i128.pow(6) / 10
+    );
+    assert_eq!(
+        ct.get_component(&t3).unwrap().unit,
+        amount_out_3 * 10i128.pow(6) / 10
+    );
+}
+
+#[test]
+fn trade_fails_with_price_deviation_exceeds_limit() {
+    let test = TradeTest::setup();
+    let mock_exchange = create_mock_exchange(&test.env);
+    initialize_best_price_trade(&test, &mock_exchange);
+    let (t0, t2) = (test.tokens.0.address.clone(), test.tokens.2.address.clone());
+    let ct = &test.constellation_token;
+    let deadline: u64 = test.env.ledger().timestamp() + 1000;
+    let amount_in = 5000i128;
+    let (expected_amount_out, amount_out_min) = test.trade_module.quote_trade(
+        &ct.address,
+        &test.router.address,
+        &t0,
+        &t2,
+        &amount_in,
+        &None,
+    );
+    assert_eq!(
+        test.trade_module.get_max_price_deviation(&ct.address),
+        Some(DEFAULT_MAX_PRICE_DEVIATION_BPS)
+    );
+
+    // the registry caps the deviation every constellation token accepts
+    test.registry.set_max_price_deviation(&100);
+    assert_eq!(
+        test.trade_module.get_max_price_deviation(&ct.address),
+        Some(100)
+    );
+    let result = test
+        .trade_module
+        .try_set_max_price_deviation(&ct.address, &101);
+    assert_eq!(result, Err(Ok(Error::DeviationExceedsCeiling)));
+
+    let trade = || {
+        test.trade_module.try_trade(
+            &ct.address,
+            &test.router.address,
+            &t0,
+            &t2,
+            &amount_in,
+            &amount_out_min,
+            &None,
+            &deadline,
+            &1000u32,
+        )
+    };
+
+    // the oracle the manager sets on the token does not loosen the check
+    let oracle = create_mock_oracle(&test.env);
+    oracle.set_price(&t0, &10_000_000);
+    oracle.set_price(&t2, &20_000_000);
+    ct.set_oracle(&oracle.address, &300);
+
+    // 5000 of token 0 for 4984 of token 2 executes 0.32% below the registry oracle rate
+    test.trade_module.set_max_price_deviation(&ct.address, &20);
+    assert_eq!(trade(), Err(Ok(Error::PriceDeviationExceedsLimit)));
+    assert_eq!(test.tokens.0.balance(&ct.address), 10_000);
+
+    test.trade_module.set_max_price_deviation(&ct.address, &50);
+    trade().unwrap().unwrap();
+    assert_eq!(test.tokens.2.balance(&ct.address), expected_amount_out);
+}
+
+/// Trades `amount_in` of token 0 for token 2 at the lowest amount out the quote allows
+fn trade_0_for_2(test: &TradeTest, amount_in: i128) -> Result<(), Error> {
+    let ct = &test.constellation_token;
+    let (t0, t2) = (test.tokens.0.address.clone(), test.tokens.2.address.clone());
+    let (_, amount_out_min) = test.trade_module.quote_trade(
+        &ct.address,
+        &test.router.address,
+        &t0,
+        &t2,
+        &amount_in,
+        &None,
+    );
+    match test.trade_module.try_trade(
+        &ct.address,
+        &test.router.address,
+        &t0,
+        &t2,
+        &amount_in,
+        &amount_out_min,
+        &None,
+        &(test.env.ledger().timestamp() + 1000),
+        &1000u32,
+    ) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(error.unwrap()),
+    }
+}
+
+fn risk_limits(max_notional_bps: u32, basis: NotionalBasis) -> RiskLimits {
+    RiskLimits {
+        epoch_ledgers: 100,
+        max_notional_bps,
+        notional_basis: basis,
+        min_trade_interval: 10,
+    }
+}
+
+#[test]
+fn trade_limits_enforce_cooldown_and_notional_per_epoch() {
+    let test = TradeTest::setup();
+    let mock_exchange = create_mock_exchange(&test.env);
+    initialize_best_price_trade(&test, &mock_exchange);
+    let ct = &test.constellation_token;
+
+    let mut limits = risk_limits(3000, NotionalBasis::ComponentBalance);
+    limits.epoch_ledgers = 0;
+    let result = test.trade_module.try_set_risk_limits(&ct.address, &limits);
+    assert_eq!(result, Err(Ok(Error::InvalidRiskLimits)));
+    let limits = risk_limits(3000, NotionalBasis::ComponentBalance);
+    test.trade_module.set_risk_limits(&ct.address, &limits);
+    assert_eq!(test.trade_module.get_risk_limits(&ct.address), Some(limits));
+
+    // 30% of the 10_000 of token 0 held at the first trade of the epoch can be sold
+    trade_0_for_2(&test, 2000).unwrap();
+    assert_eq!(trade_0_for_2(&test, 1000), Err(Error::TradeCooldown));
+    test.env.ledger().with_mut(|li| li.sequence_number += 10);
+    assert_eq!(trade_0_for_2(&test, 1001), Err(Error::NotionalExceedsLimit));
+    trade_0_for_2(&test, 1000).unwrap();
+
+    // the next epoch measures the limit against the 7_000 left
+    test.env.ledger().with_mut(|li| li.sequence_number = 100);
+    assert_eq!(trade_0_for_2(&test, 2101), Err(Error::NotionalExceedsLimit));
+    trade_0_for_2(&test, 2100).unwrap();
+    assert_eq!(test.tokens.0.balance(&ct.address), 4900);
+
+    // removing the module from the token clears its limits
+    ct.remove_module(&test.trade_module.address);
+    assert_eq!(test.trade_module.get_risk_limits(&ct.address), None);
+}
+
+#[test]
+fn trade_limits_measure_notional_against_fund_value() {
+    let test = TradeTest::setup();
+    let mock_exchange = create_mock_exchange(&test.env);
+    initialize_best_price_trade(&test, &mock_exchange);
+    let ct = &test.constellation_token;
+    let mut limits = risk_limits(1000, NotionalBasis::FundValue);
+    limits.min_trade_interval = 0;
+    test.trade_module.set_risk_limits(&ct.address, &limits);
+
+    // stale prices leave the net asset value unavailable
+    test.env.ledger().with_mut(|li| li.timestamp += 3_601);
+    assert_eq!(trade_0_for_2(&test, 1000), Err(Error::NavUnavailable));
+
+    test.oracle.set_price(&test.tokens.0.address, &10_000_000);
+    test.oracle.set_price(&test.tokens.1.address, &10_000_000);
+    test.oracle.set_price(&test.tokens.2.address, &10_000_000);
+
+    // 10% of a net asset value of 20_000
+    trade_0_for_2(&test, 1500).unwrap();
+    assert_eq!(trade_0_for_2(&test, 501), Err(Error::NotionalExceedsLimit));
+    trade_0_for_2(&test, 500).unwrap();
+}
+
+/// Tries to sell 1_000 of token 0 for token 2, and checks the failed trade left the balances unchanged
+fn try_trade_0_for_2_reverts(test: &TradeTest) -> Result<(), Result<Error, InvokeError>> {
+    let ct = &test.constellation_token;
+    let (t0, t2) = (test.tokens.0.address.clone(), test.tokens.2.address.clone());
+    let (_, amount_out_min) =
+        test.trade_module
+            .quote_trade(&ct.address, &test.router.address, &t0, &t2, &1000, &None);
+    let result = test.trade_module.try_trade(
+        &ct.address,
+        &test.router.address,
+        &t0,
+        &t2,
+        &1000,
+        &amount_out_min,
+        &None,
+        &(test.env.ledger().timestamp() + 1000),
+        &1000u32,
+    );
+    assert_eq!(test.tokens.0.balance(&ct.address), 10_000);
+    assert_eq!(test.tokens.2.balance(&ct.address), 0);
+    assert!(ct.get_component(&t2).is_none());
+    result.map(|_| ())
+}
+
+/// Sets a factory allowing at most 2 components on the registry of the constellation token
+fn set_max_components(test: &TradeTest) {
+    let factory = create_constellation_factory(&test.env);
+    factory.initialize(&test.admin, &BytesN::from_array(&test.env, &[0; 32]));
+    factory.set_max_components(&2);
+    test.registry.set_factory(&factory.address);
+}
+
+#[test]
+fn trade_fails_with_component_not_allowed() {
+    let test = TradeTest::setup();
+    let mock_exchange = create_mock_exchange(&test.env);
+    initialize_best_price_trade(&test, &mock_exchange);
+    let ct = &test.constellation_token;
+
+    // the constellation token rejects the component when the trade module updates its units
+    ct.set_allowed_components(&vec![&test.env, test.tokens.3.address.clone()]);
+    // ComponentNotAllowed of the constellation token
+    assert_eq!(
+        try_trade_0_for_2_reverts(&test),
+        Err(Err(InvokeError::Contract(523)))
+    );
+}
+
+#[test]
+fn trade_fails_with_exceeds_max_components() {
+    let test = TradeTest::setup();
+    let mock_exchange = create_mock_exchange(&test.env);
+    initialize_best_price_trade(&test, &mock_exchange);
+    let ct = &test.constellation_token;
+
+    set_max_components(&test);
+    ct.set_allowed_components(&vec![&test.env, test.tokens.2.address.clone()]);
+    // ExceedsMaxComponents of the constellation token
+    assert_eq!(
+        try_trade_0_for_2_reverts(&test),
+        Err(Err(InvokeError::Contract(524)))
+    );
+}
+
+#[test]
+fn trade_replaces_component_within_max_components() {
+    let test = TradeTest::setup();
+    let mock_exchange = create_mock_exchange(&test.env);
+    initialize_best_price_trade(&test, &mock_exchange);
+    let ct = &test.constellation_token;
+
+    set_max_components(&test);
+    ct.set_allowed_components(&vec![&test.env, test.tokens.2.address.clone()]);
+    // selling the whole balance of token 0 replaces it with token 2
+    assert_eq!(trade_0_for_2(&test, 10_000), Ok(()));
+    assert_eq!(ct.get_components().len(), 2);
+    assert!(ct.get_component(&test.tokens.0.address).is_none());
+    assert!(ct.get_component(&test.tokens.2.address).is_some());
+}
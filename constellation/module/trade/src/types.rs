@@ -0,0 +1,57 @@
+use soroban_sdk::{contracttype, Address, Map, Vec};
+
+/// Maximum number of trades executed by one `batch_trade`
+pub const MAX_BATCH_TRADES: u32 = 20;
+
+/// One trade of a batch, traded with exact input like `trade`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TradeOrder {
+    /// Exchange with an adapter registered for the trade module in the registry
+    pub exchange_id: Address,
+    pub token_in_id: Address,
+    pub token_out_id: Address,
+    pub amount_in: i128,
+    /// Minimum amount of `token_out_id` received
+    pub amount_out: i128,
+    /// Path from `token_in_id` to `token_out_id`, the direct pair if empty
+    pub path: Vec<Address>,
+}
+
+/// Amount the notional traded in an epoch is measured against
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum NotionalBasis {
+    /// Oracle value sold, against the net asset value of the constellation token
+    FundValue,
+    /// Amount of each component sold, against its balance
+    ComponentBalance,
+}
+
+/// Risk controls the manager of a constellation token sets on its trades
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RiskLimits {
+    /// Length of an epoch, in ledgers
+    pub epoch_ledgers: u32,
+    /// Maximum notional sold in an epoch, in basis points of the basis at its first trade. 0 for no limit
+    pub max_notional_bps: u32,
+    pub notional_basis: NotionalBasis,
+    /// Minimum number of ledgers between two trades. 0 for no limit
+    pub min_trade_interval: u32,
+}
+
+/// Trades of a constellation token in the current epoch
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TradeActivity {
+    pub epoch: u32,
+    pub last_trade_ledger: u32,
+    /// Net asset value at the first trade of the epoch, measured with the `FundValue` basis
+    pub opening_value: i128,
+    /// Oracle value sold in the epoch, measured with the `FundValue` basis
+    pub value_sold: i128,
+    /// Balance of each component at its first sale of the epoch and the amount sold since,
+    /// measured with the `ComponentBalance` basis
+    pub sold: Map<Address, (i128, i128)>,
+}
@@ -1,7 +1,16 @@
-use super::registry::get_adapter_id;
-use crate::token::get_manager;
-use crate::{error::Error, storage::admin::read_administrator, storage::registry::read_registry};
-use soroban_sdk::{contract, contractimpl, contracttype, panic_with_error, Address, Env};
+use crate::storage::deviation::read_max_price_deviation;
+use crate::storage::slippage::read_max_slippage;
+use crate::storage::types::BPS_DENOMINATOR;
+use crate::types::RiskLimits;
+use crate::{error::Error, storage::admin::read_administrator};
+use constellation_lib::math::{mul_div, Rounding};
+use constellation_lib::module::{
+    max_price_deviation as price_deviation_ceiling, oracle_amount_out_min,
+};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, panic_with_error, token::TokenClient, vec, Address, Env,
+    Vec,
+};
 
 pub fn require_administrator(e: &Env) -> Result<(), Error> {
     match read_administrator(e) {
@@ -11,30 +20,153 @@ pub fn require_administrator(e: &Env) -> Result<(), Error> {
     Ok(())
 }
 
-pub fn require_registry(e: &Env) -> Result<Address, Error> {
-    let registry_id = match read_registry(e) {
-        Some(registry_id) => registry_id,
-        None => return Err(Error::RequiresRegistry),
-    };
-    Ok(registry_id)
+/// Returns `path`, or the direct path from `token_in_id` to `token_out_id` if not set
+/// Returns error if the path does not start with `token_in_id` and end with `token_out_id`
+pub fn require_path(
+    e: &Env,
+    token_in_id: &Address,
+    token_out_id: &Address,
+    path: Option<Vec<Address>>,
+) -> Result<Vec<Address>, Error> {
+    let path = path.unwrap_or(vec![e, token_in_id.clone(), token_out_id.clone()]);
+    if path.len() < 2
+        || path.first().as_ref() != Some(token_in_id)
+        || path.last().as_ref() != Some(token_out_id)
+    {
+        return Err(Error::InvalidPath);
+    }
+    Ok(path)
+}
+
+/// Returns the lowest amount out the constellation token accepts for a trade quoted at `expected_amount_out`
+pub fn min_amount_out(
+    e: &Env,
+    constellation_token_id: &Address,
+    expected_amount_out: i128,
+) -> Result<i128, Error> {
+    let max_slippage_bps = read_max_slippage(e, constellation_token_id);
+    mul_div(
+        expected_amount_out,
+        (BPS_DENOMINATOR - max_slippage_bps) as i128,
+        BPS_DENOMINATOR as i128,
+        Rounding::Up,
+    )
+    .ok_or(Error::ValueTooLargeOverFlow)
+}
+
+/// Returns error if `amount_out_min` allows more slippage below `expected_amount_out`
+/// than the constellation token accepts
+pub fn require_slippage(
+    e: &Env,
+    constellation_token_id: &Address,
+    expected_amount_out: i128,
+    amount_out_min: i128,
+) -> Result<(), Error> {
+    if amount_out_min < min_amount_out(e, constellation_token_id, expected_amount_out)? {
+        return Err(Error::SlippageExceedsLimit);
+    }
+    Ok(())
+}
+
+/// Returns the highest amount in the constellation token accepts for a trade quoted at `expected_amount_in`
+pub fn max_amount_in(
+    e: &Env,
+    constellation_token_id: &Address,
+    expected_amount_in: i128,
+) -> Result<i128, Error> {
+    let max_slippage_bps = read_max_slippage(e, constellation_token_id);
+    mul_div(
+        expected_amount_in,
+        (BPS_DENOMINATOR + max_slippage_bps) as i128,
+        BPS_DENOMINATOR as i128,
+        Rounding::Down,
+    )
+    .ok_or(Error::ValueTooLargeOverFlow)
+}
+
+/// Returns error if `amount_in_max` allows more slippage above `expected_amount_in`
+/// than the constellation token accepts
+pub fn require_slippage_in(
+    e: &Env,
+    constellation_token_id: &Address,
+    expected_amount_in: i128,
+    amount_in_max: i128,
+) -> Result<(), Error> {
+    if amount_in_max > max_amount_in(e, constellation_token_id, expected_amount_in)? {
+        return Err(Error::SlippageExceedsLimit);
+    }
+    Ok(())
+}
+
+/// Returns the deviation below the oracle rate the constellation token accepts on its trades,
+/// its own limit capped at the protocol ceiling of the registry, or the ceiling if it has none
+/// The ceiling defaults to `DEFAULT_MAX_PRICE_DEVIATION_BPS` when the registry does not set one
+pub fn max_price_deviation(e: &Env, registry_id: &Address, constellation_token_id: &Address) -> u32 {
+    let ceiling = price_deviation_ceiling(e, registry_id);
+    match read_max_price_deviation(e, constellation_token_id) {
+        Some(max_deviation_bps) => max_deviation_bps.min(ceiling),
+        None => ceiling,
+    }
 }
 
-pub fn require_adapter(
+/// Returns the balances of `token_in_id` and `token_out_id` held by the constellation token
+pub fn trade_balances(
+    e: &Env,
+    constellation_token_id: &Address,
+    token_in_id: &Address,
+    token_out_id: &Address,
+) -> (i128, i128) {
+    (
+        TokenClient::new(e, token_in_id).balance(constellation_token_id),
+        TokenClient::new(e, token_out_id).balance(constellation_token_id),
+    )
+}
+
+/// Returns the amounts of `token_in_id` sold and `token_out_id` bought since `balances_before`
+/// Returns error if the registry oracle values the amount bought more than the accepted deviation
+/// below the amount sold, or if a price is missing or stale
+///
+/// # Arguments
+///
+/// - `e` The runtime environment.
+/// - `registry_id` Registry of the protocol
+/// - `constellation_token_id` Constellation token trading its components
+/// - `token_in_id` Token sold
+/// - `token_out_id` Token bought
+/// - `balances_before` Balances of the traded tokens recorded by `trade_balances` before the trade
+pub fn require_oracle_rate(
     e: &Env,
     registry_id: &Address,
-    exchange_id: &Address,
-) -> Result<Address, Error> {
-    let adapter_id = match get_adapter_id(e, registry_id, exchange_id) {
-        Some(adapter_id) => adapter_id,
-        None => return Err(Error::RequiresExchangeAdapter),
-    };
-    Ok(adapter_id)
-}
-
-pub fn require_manager(e: &Env, constellation_token_id: &Address) -> Result<Address, Error> {
-    let manage_id = match get_manager(&e, constellation_token_id) {
-        Some(manage_id) => manage_id,
-        None => return Err(Error::RequiresManage),
-    };
-    Ok(manage_id)
+    constellation_token_id: &Address,
+    token_in_id: &Address,
+    token_out_id: &Address,
+    balances_before: (i128, i128),
+) -> Result<(i128, i128), Error> {
+    let (balance_in, balance_out) =
+        trade_balances(e, constellation_token_id, token_in_id, token_out_id);
+    let amount_in = balances_before.0 - balance_in;
+    let amount_out = balance_out - balances_before.1;
+
+    let amount_out_min = oracle_amount_out_min(
+        e,
+        registry_id,
+        token_in_id,
+        token_out_id,
+        amount_in,
+        max_price_deviation(e, registry_id, constellation_token_id),
+    )?;
+    if amount_out < amount_out_min {
+        return Err(Error::PriceDeviationExceedsLimit);
+    }
+    Ok((amount_in, amount_out))
+}
+
+/// Returns error if the notional limit exceeds 10_000 basis points, or is set without an epoch length
+pub fn require_risk_limits(limits: &RiskLimits) -> Result<(), Error> {
+    if limits.max_notional_bps > BPS_DENOMINATOR
+        || (limits.max_notional_bps > 0 && limits.epoch_ledgers == 0)
+    {
+        return Err(Error::InvalidRiskLimits);
+    }
+    Ok(())
 }
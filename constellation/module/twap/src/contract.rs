@@ -0,0 +1,293 @@
+use crate::error::Error;
+use crate::event;
+use crate::storage::types::{TwapChunk, TwapOrder, BPS_DENOMINATOR, PRICE_SCALE};
+use crate::validation::{require_order, require_valid_order};
+use constellation_lib::math::{mul_div, Rounding};
+use constellation_lib::module::{
+    has_registry, initialize_module, invoke, max_price_deviation, oracle_amount_out_min,
+    require_adapter, require_manager, require_pair_quote, require_registry, snapshot_balances,
+    swap, update_units, write_registry,
+};
+use constellation_lib::orders::{add_order, read_order, remove_order, remove_orders, write_order};
+use constellation_lib::traits::module::ModuleHooks;
+use soroban_sdk::token::TokenClient;
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, vec, Address, Env, IntoVal, Symbol, Val, Vec,
+};
+
+#[contract]
+pub struct Twap {}
+
+#[contractimpl]
+impl Twap {
+    pub fn initialize(e: Env, registry_id: Address) {
+        if has_registry(&e) {
+            panic_with_error!(&e, Error::AlreadyInitalized);
+        }
+
+        write_registry(&e, &registry_id);
+    }
+
+    /// Activates the module on a constellation token it was added to
+    /// Orders carry their own settings, the module keeps none for the token
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token trading its components
+    pub fn initialize_token(e: Env, constellation_token_id: Address) -> Result<(), Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        initialize_module(&e, &constellation_token_id);
+        Ok(())
+    }
+
+    /// Schedules the sale of `amount_in` of `token_in_id` held by a constellation token for `token_out_id`,
+    /// in chunks executed by keepers
+    /// Returns the id of the order
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token trading its components
+    /// - `token_in_id` Token sold
+    /// - `token_out_id` Token bought
+    /// - `amount_in` Total amount of `token_in_id` sold
+    /// - `chunk_amount_in` Amount of `token_in_id` sold by each chunk
+    /// - `interval_ledgers` Minimum number of ledgers between two chunks
+    /// - `min_price` Minimum amount of `token_out_id` per base unit of `token_in_id`, scaled by `PRICE_SCALE`
+    /// - `max_slippage_bps` Slippage allowed below the rate of the protocol oracle on each chunk,
+    ///   capped at the protocol ceiling of the registry
+    /// - `bounty_bps` Share of each chunk bought paid to the keeper, capped at `MAX_BOUNTY_BPS`
+    ///
+    /// Returns error if the order is invalid, or if the constellation token already has `MAX_OPEN_ORDERS` open orders
+    #[allow(clippy::too_many_arguments)]
+    pub fn schedule_twap(
+        e: Env,
+        constellation_token_id: Address,
+        token_in_id: Address,
+        token_out_id: Address,
+        amount_in: i128,
+        chunk_amount_in: i128,
+        interval_ledgers: u32,
+        min_price: i128,
+        max_slippage_bps: u32,
+        bounty_bps: u32,
+    ) -> Result<u32, Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        let order = TwapOrder {
+            token_in_id: token_in_id.clone(),
+            token_out_id: token_out_id.clone(),
+            amount_in_remaining: amount_in,
+            chunk_amount_in,
+            interval_ledgers,
+            next_ledger: e.ledger().sequence(),
+            min_price,
+            max_slippage_bps,
+            bounty_bps,
+        };
+        let registry_id = require_registry(&e)?;
+        require_valid_order(&order, max_price_deviation(&e, &registry_id))?;
+
+        let order_id =
+            add_order(&e, &constellation_token_id, &order).ok_or(Error::TooManyOrders)?;
+        event::schedule_twap(
+            &e,
+            constellation_token_id,
+            order_id,
+            token_in_id,
+            token_out_id,
+            amount_in,
+        );
+        Ok(order_id)
+    }
+
+    /// Cancels the remaining chunks of an order
+    pub fn cancel_twap(
+        e: Env,
+        constellation_token_id: Address,
+        order_id: u32,
+    ) -> Result<(), Error> {
+        let manager = require_manager(&e, &constellation_token_id)?;
+        manager.require_auth();
+
+        require_order(&e, &constellation_token_id, order_id)?;
+        remove_order(&e, &constellation_token_id, order_id);
+        event::cancel_twap(&e, constellation_token_id, order_id);
+        Ok(())
+    }
+
+    pub fn get_twap(e: Env, constellation_token_id: Address, order_id: u32) -> Option<TwapOrder> {
+        read_order(&e, &constellation_token_id, order_id)
+    }
+
+    /// Executes the next chunk of an order on `exchange_id` and pays the bounty to `keeper`
+    /// Can be called by anyone once `interval_ledgers` have passed since the previous chunk
+    /// Returns the executed chunk
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `keeper` Address executing the chunk and receiving the bounty
+    /// - `constellation_token_id` Constellation token trading its components
+    /// - `order_id` Order executed
+    /// - `exchange_id` Exchange to trade on, with an adapter registered for the TWAP module
+    /// - `deadline` Swap deadline
+    /// - `expiration_ledger` Expiration ledger of the exchange approval
+    ///
+    /// Returns error if the chunk is executed too early, if the protocol oracle has no fresh price for the pair,
+    /// or if the exchange quotes or the chunk receives less than the limit price or the oracle rate
+    /// minus the slippage of the order allow
+    pub fn execute_chunk(
+        e: Env,
+        keeper: Address,
+        constellation_token_id: Address,
+        order_id: u32,
+        exchange_id: Address,
+        deadline: u64,
+        expiration_ledger: u32,
+    ) -> Result<TwapChunk, Error> {
+        keeper.require_auth();
+
+        let mut order = require_order(&e, &constellation_token_id, order_id)?;
+        if e.ledger().sequence() < order.next_ledger {
+            return Err(Error::TooEarly);
+        }
+        let registry_id = require_registry(&e)?;
+        let adapter_id = require_adapter(&e, &registry_id, &exchange_id)?;
+
+        let amount_in = order.chunk_amount_in.min(order.amount_in_remaining);
+        let amount_out_min =
+            Self::chunk_amount_out_min(&e, &registry_id, &adapter_id, &order, amount_in)?;
+
+        snapshot_balances(
+            &e,
+            &constellation_token_id,
+            &vec![&e, order.token_in_id.clone(), order.token_out_id.clone()],
+        );
+
+        let token_out = TokenClient::new(&e, &order.token_out_id);
+        let balance_before = token_out.balance(&constellation_token_id);
+        swap(
+            &e,
+            &constellation_token_id,
+            &adapter_id,
+            &exchange_id,
+            &vec![&e, order.token_in_id.clone(), order.token_out_id.clone()],
+            amount_in,
+            amount_out_min,
+            deadline,
+            expiration_ledger,
+        );
+        let amount_out = token_out.balance(&constellation_token_id) - balance_before;
+        if amount_out < amount_out_min {
+            return Err(Error::InsufficientOutputAmount);
+        }
+
+        let bounty = mul_div(
+            amount_out,
+            order.bounty_bps as i128,
+            BPS_DENOMINATOR as i128,
+            Rounding::Down,
+        )
+        .ok_or(Error::ValueTooLargeOverFlow)?;
+        if bounty > 0 {
+            let args: Vec<Val> = vec![
+                &e,
+                constellation_token_id.into_val(&e),
+                keeper.into_val(&e),
+                bounty.into_val(&e),
+            ];
+            invoke(
+                &e,
+                &constellation_token_id,
+                &order.token_out_id,
+                &(Symbol::new(&e, "transfer"), args),
+                &vec![&e],
+            );
+        }
+
+        update_units(&e, &constellation_token_id);
+
+        order.amount_in_remaining -= amount_in;
+        if order.amount_in_remaining == 0 {
+            remove_order(&e, &constellation_token_id, order_id);
+        } else {
+            order.next_ledger = e.ledger().sequence() + order.interval_ledgers;
+            write_order(&e, &constellation_token_id, order_id, &order);
+        }
+
+        let chunk = TwapChunk {
+            amount_in,
+            amount_out,
+            bounty,
+        };
+        event::twap_chunk(
+            &e,
+            constellation_token_id,
+            order_id,
+            keeper,
+            exchange_id,
+            chunk.clone(),
+        );
+        Ok(chunk)
+    }
+
+    /// Returns the lowest amount out a chunk selling `amount_in` accepts, the highest of the limit price
+    /// and the rate of the protocol oracle minus the slippage of the order
+    /// The oracle is set on the registry, the rate cannot be moved within the transaction executing the chunk
+    /// Returns error if the oracle has no fresh price for the pair, or if the exchange quotes less
+    /// than the lowest amount out
+    fn chunk_amount_out_min(
+        e: &Env,
+        registry_id: &Address,
+        adapter_id: &Address,
+        order: &TwapOrder,
+        amount_in: i128,
+    ) -> Result<i128, Error> {
+        let limit_amount_out = mul_div(amount_in, order.min_price, PRICE_SCALE, Rounding::Up)
+            .ok_or(Error::ValueTooLargeOverFlow)?;
+
+        // the registry may have lowered its ceiling since the order was scheduled
+        let slippage_amount_out = oracle_amount_out_min(
+            e,
+            registry_id,
+            &order.token_in_id,
+            &order.token_out_id,
+            amount_in,
+            order
+                .max_slippage_bps
+                .min(max_price_deviation(e, registry_id)),
+        )?;
+        let amount_out_min = limit_amount_out.max(slippage_amount_out);
+
+        let expected_amount_out = require_pair_quote(
+            e,
+            adapter_id,
+            &order.token_in_id,
+            &order.token_out_id,
+            amount_in,
+        )?;
+        if expected_amount_out < amount_out_min {
+            return Err(Error::PriceBelowLimit);
+        }
+        Ok(amount_out_min)
+    }
+}
+
+#[contractimpl]
+impl ModuleHooks for Twap {
+    /// Cancels the open TWAP orders of a constellation token the module was removed from
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `constellation_token_id` Constellation token the module was removed from
+    fn on_remove(e: Env, constellation_token_id: Address) {
+        constellation_token_id.require_auth();
+        remove_orders(&e, &constellation_token_id);
+    }
+}
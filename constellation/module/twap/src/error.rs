@@ -0,0 +1,38 @@
+use constellation_lib::module::ModuleError;
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitalized = 101,
+    RequiresRegistry = 102,
+    RequiresExchangeAdapter = 103,
+    RequiresManage = 104,
+    RequiresOrder = 105,
+    InvalidOrder = 106,
+    InvalidSlippage = 107,
+    InvalidBounty = 108,
+    TooEarly = 109,
+    PairNotFound = 110,
+    PriceBelowLimit = 111,
+    InsufficientOutputAmount = 112,
+    ValueTooLargeOverFlow = 113,
+    RequiresOracle = 114,
+    StalePrice = 115,
+    TooManyOrders = 116,
+}
+
+impl From<ModuleError> for Error {
+    fn from(error: ModuleError) -> Self {
+        match error {
+            ModuleError::RequiresRegistry => Error::RequiresRegistry,
+            ModuleError::RequiresExchangeAdapter => Error::RequiresExchangeAdapter,
+            ModuleError::RequiresManage => Error::RequiresManage,
+            ModuleError::PairNotFound => Error::PairNotFound,
+            ModuleError::RequiresOracle => Error::RequiresOracle,
+            ModuleError::StalePrice => Error::StalePrice,
+            ModuleError::ValueTooLargeOverFlow => Error::ValueTooLargeOverFlow,
+        }
+    }
+}
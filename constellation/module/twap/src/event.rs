@@ -0,0 +1,72 @@
+use crate::storage::types::TwapChunk;
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleTwap {
+    constellation_token_id: Address,
+    order_id: u32,
+    token_in_id: Address,
+    token_out_id: Address,
+    amount_in: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TwapChunkExecuted {
+    constellation_token_id: Address,
+    order_id: u32,
+    keeper: Address,
+    exchange_id: Address,
+    chunk: TwapChunk,
+}
+
+pub(crate) fn schedule_twap(
+    e: &Env,
+    constellation_token_id: Address,
+    order_id: u32,
+    token_in_id: Address,
+    token_out_id: Address,
+    amount_in: i128,
+) {
+    let topics = (
+        Symbol::new(e, "schedule_twap"),
+        constellation_token_id.clone(),
+    );
+    e.events().publish(
+        topics,
+        ScheduleTwap {
+            constellation_token_id,
+            order_id,
+            token_in_id,
+            token_out_id,
+            amount_in,
+        },
+    );
+}
+
+pub(crate) fn cancel_twap(e: &Env, constellation_token_id: Address, order_id: u32) {
+    let topics = (Symbol::new(e, "cancel_twap"), constellation_token_id);
+    e.events().publish(topics, order_id);
+}
+
+pub(crate) fn twap_chunk(
+    e: &Env,
+    constellation_token_id: Address,
+    order_id: u32,
+    keeper: Address,
+    exchange_id: Address,
+    chunk: TwapChunk,
+) {
+    let topics = (Symbol::new(e, "twap_chunk"), constellation_token_id.clone());
+    e.events().publish(
+        topics,
+        TwapChunkExecuted {
+            constellation_token_id,
+            order_id,
+            keeper,
+            exchange_id,
+            chunk,
+        },
+    );
+}
@@ -0,0 +1,9 @@
+#![no_std]
+pub mod contract;
+mod error;
+mod event;
+mod storage;
+mod validation;
+
+#[cfg(test)]
+mod test;
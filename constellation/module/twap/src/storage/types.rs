@@ -0,0 +1,39 @@
+use soroban_sdk::{contracttype, Address};
+
+pub(crate) const BPS_DENOMINATOR: u32 = 10_000;
+/// Scale of the limit price of an order
+pub(crate) const PRICE_SCALE: i128 = 10_000_000;
+/// Hard cap on the keeper bounty, 1% of each chunk bought
+pub(crate) const MAX_BOUNTY_BPS: u32 = 100;
+
+/// Trade of a constellation token split into chunks executed by keepers
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TwapOrder {
+    pub token_in_id: Address,
+    pub token_out_id: Address,
+    /// Amount of `token_in_id` left to sell
+    pub amount_in_remaining: i128,
+    /// Amount of `token_in_id` sold by each chunk
+    pub chunk_amount_in: i128,
+    /// Minimum number of ledgers between two chunks
+    pub interval_ledgers: u32,
+    /// First ledger the next chunk can be executed at
+    pub next_ledger: u32,
+    /// Minimum amount of `token_out_id` per base unit of `token_in_id`, scaled by `PRICE_SCALE`
+    pub min_price: i128,
+    /// Slippage allowed below the rate of the protocol oracle on each chunk, in basis points
+    pub max_slippage_bps: u32,
+    /// Share of each chunk bought paid to the keeper, in basis points
+    pub bounty_bps: u32,
+}
+
+/// Amounts of one executed chunk
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TwapChunk {
+    pub amount_in: i128,
+    pub amount_out: i128,
+    /// Amount of `token_out_id` paid to the keeper out of `amount_out`
+    pub bounty: i128,
+}
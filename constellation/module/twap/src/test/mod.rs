@@ -0,0 +1,4 @@
+mod add_liquidity;
+mod clients;
+mod setup;
+mod test;
@@ -0,0 +1,104 @@
+use super::add_liquidity::add_liquidity;
+use super::clients::{
+    create_adapter, create_constellation_token, create_mock_oracle, create_registry,
+    create_soroswap_factory, create_soroswap_router, create_token_contract, create_twap_module,
+    pair_contract_wasm, ConstellationTokenClient, SoroswapRouterClient, TokenClient,
+};
+use crate::contract::TwapClient;
+use constellation_lib::testutils::MockOracleClient;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, IntoVal};
+
+/// Constellation tokens minted, backed by 10_000 base units of each component
+pub const SUPPLY: i128 = 10;
+pub const UNIT: i128 = 1_000 * 1_000_000;
+/// Oracle price of one whole token, 1 USD
+pub const PRICE: i128 = 10_000_000;
+
+pub struct TwapTest<'a> {
+    pub env: Env,
+    pub keeper: Address,
+    pub router: SoroswapRouterClient<'a>,
+    pub oracle: MockOracleClient<'a>,
+    pub constellation_token: ConstellationTokenClient<'a>,
+    pub twap_module: TwapClient<'a>,
+    pub tokens: (TokenClient<'a>, TokenClient<'a>),
+}
+
+impl<'a> TwapTest<'a> {
+    /// Constellation token backed by 10_000 base units of tokens 0 and 1,
+    /// with a soroswap pool between the two tokens and both tokens priced at 1 USD by the protocol oracle
+    pub fn setup() -> TwapTest<'a> {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.budget().reset_unlimited();
+        let user = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let manager = Address::generate(&env);
+        let keeper = Address::generate(&env);
+        let adapter = create_adapter(&env);
+        let router = create_soroswap_router(&env);
+        let factory = create_soroswap_factory(&env);
+        let constellation_token = create_constellation_token(&env);
+        let registry = create_registry(&env);
+        let twap_module = create_twap_module(&env);
+        let oracle = create_mock_oracle(&env);
+
+        adapter.initialize(&router.address, &factory.address);
+        router.initialize(&factory.address);
+        registry.initialize(&admin);
+        factory.initialize(&admin, &pair_contract_wasm(&env));
+        twap_module.initialize(&registry.address);
+        registry.set_oracle(&oracle.address, &3_600);
+
+        let tokens = (
+            create_token_contract(&env, &admin),
+            create_token_contract(&env, &admin),
+        );
+        let liquidity = 4_000_000_000i128;
+        for token in [&tokens.0, &tokens.1] {
+            token.mint(&user, &(liquidity + 10_000));
+            oracle.set_price(&token.address, &PRICE);
+        }
+        add_liquidity(
+            &env,
+            &router,
+            &user,
+            &tokens.0.address,
+            &tokens.1.address,
+            &liquidity,
+            &liquidity,
+        );
+
+        constellation_token.initialize(
+            &6u32,
+            &vec![&env, tokens.0.address.clone(), tokens.1.address.clone()],
+            &vec![&env, UNIT, UNIT],
+            &"c_token".into_val(&env),
+            &"token_symbol".into_val(&env),
+            &user,
+            &manager,
+        );
+        for token in [&tokens.0, &tokens.1] {
+            token.approve(&user, &constellation_token.address, &10_000, &1000u32);
+        }
+        constellation_token.mint(&user, &SUPPLY);
+
+        registry.add_module(&twap_module.address);
+        registry.add_adapter(&twap_module.address, &router.address, &adapter.address);
+        constellation_token.set_registry(&registry.address);
+        constellation_token.add_module(&twap_module.address, &None);
+        twap_module.initialize_token(&constellation_token.address);
+
+        env.budget().reset_unlimited();
+
+        Self {
+            env,
+            keeper,
+            router,
+            oracle,
+            constellation_token,
+            twap_module,
+            tokens,
+        }
+    }
+}
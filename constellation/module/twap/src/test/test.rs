@@ -0,0 +1,183 @@
+use super::setup::{TwapTest, PRICE, UNIT};
+use crate::error::Error;
+use constellation_lib::module::DEFAULT_MAX_PRICE_DEVIATION_BPS;
+use soroban_sdk::testutils::Ledger;
+
+/// Limit price of 0.9 token 1 per token 0
+const MIN_PRICE: i128 = 9_000_000;
+
+#[test]
+fn schedule_twap_fails_with_invalid_order() {
+    let test = TwapTest::setup();
+    let (t0, t1) = (&test.tokens.0.address, &test.tokens.1.address);
+    let ct = &test.constellation_token.address;
+
+    let result = test
+        .twap_module
+        .try_schedule_twap(ct, t0, t1, &1_000, &2_000, &10, &MIN_PRICE, &100, &50);
+    assert_eq!(result, Err(Ok(Error::InvalidOrder)));
+    let result = test
+        .twap_module
+        .try_schedule_twap(ct, t0, t1, &3_000, &1_000, &10, &MIN_PRICE, &100, &101);
+    assert_eq!(result, Err(Ok(Error::InvalidBounty)));
+
+    // the registry sets no ceiling, the slippage is capped at the default one
+    let result = test.twap_module.try_schedule_twap(
+        ct,
+        t0,
+        t1,
+        &3_000,
+        &1_000,
+        &10,
+        &MIN_PRICE,
+        &(DEFAULT_MAX_PRICE_DEVIATION_BPS + 1),
+        &50,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidSlippage)));
+}
+
+#[test]
+fn execute_chunk_trades_at_interval_and_pays_keeper() {
+    let test = TwapTest::setup();
+    let (t0, t1) = (&test.tokens.0.address, &test.tokens.1.address);
+    let ct = &test.constellation_token;
+    let router = &test.router.address;
+    let keeper = &test.keeper;
+    let deadline = test.env.ledger().timestamp() + 1000;
+
+    let order_id = test.twap_module.schedule_twap(
+        &ct.address,
+        t0,
+        t1,
+        &3_000,
+        &1_000,
+        &10,
+        &MIN_PRICE,
+        &100,
+        &50,
+    );
+
+    let mut bought = 0;
+    let mut bounties = 0;
+    for i in 0..3 {
+        if i > 0 {
+            let result = test.twap_module.try_execute_chunk(
+                keeper,
+                &ct.address,
+                &order_id,
+                router,
+                &deadline,
+                &1000u32,
+            );
+            assert_eq!(result, Err(Ok(Error::TooEarly)));
+            test.env.ledger().with_mut(|li| li.sequence_number += 10);
+        }
+        let chunk = test.twap_module.execute_chunk(
+            keeper,
+            &ct.address,
+            &order_id,
+            router,
+            &deadline,
+            &1000u32,
+        );
+        assert_eq!(chunk.amount_in, 1_000);
+        assert!(chunk.amount_out * 10_000_000 >= 1_000 * MIN_PRICE);
+        assert_eq!(chunk.bounty, chunk.amount_out * 50 / 10_000);
+        bought += chunk.amount_out;
+        bounties += chunk.bounty;
+    }
+
+    assert!(test.twap_module.get_twap(&ct.address, &order_id).is_none());
+    assert_eq!(test.tokens.1.balance(keeper), bounties);
+    assert_eq!(test.tokens.0.balance(&ct.address), 7_000);
+    assert_eq!(
+        test.tokens.1.balance(&ct.address),
+        10_000 + bought - bounties
+    );
+    assert_eq!(ct.get_component(t0).unwrap().unit, UNIT * 7 / 10);
+    assert_eq!(
+        ct.get_component(t1).unwrap().unit,
+        (10_000 + bought - bounties) * 10i128.pow(6) / 10
+    );
+}
+
+#[test]
+fn execute_chunk_fails_with_price_below_limit() {
+    let test = TwapTest::setup();
+    let (t0, t1) = (&test.tokens.0.address, &test.tokens.1.address);
+    let ct = &test.constellation_token.address;
+    let deadline = test.env.ledger().timestamp() + 1000;
+
+    // the pool trades at par, below a limit of 1.1
+    let order_id =
+        test.twap_module
+            .schedule_twap(ct, t0, t1, &3_000, &1_000, &10, &11_000_000, &100, &0);
+    let result = test.twap_module.try_execute_chunk(
+        &test.keeper,
+        ct,
+        &order_id,
+        &test.router.address,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(result, Err(Ok(Error::PriceBelowLimit)));
+
+    test.twap_module.cancel_twap(ct, &order_id);
+    let result = test.twap_module.try_execute_chunk(
+        &test.keeper,
+        ct,
+        &order_id,
+        &test.router.address,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(result, Err(Ok(Error::RequiresOrder)));
+}
+
+#[test]
+fn execute_chunk_fails_with_price_below_oracle_rate() {
+    let test = TwapTest::setup();
+    let (t0, t1) = (&test.tokens.0.address, &test.tokens.1.address);
+    let ct = &test.constellation_token.address;
+    let deadline = test.env.ledger().timestamp() + 10_000;
+
+    let order_id = test
+        .twap_module
+        .schedule_twap(ct, t0, t1, &3_000, &1_000, &10, &MIN_PRICE, &100, &0);
+
+    // the pool trades at par, more than 1% below the oracle rate of 1.1
+    test.oracle.set_price(t0, &(PRICE * 11 / 10));
+    let result = test.twap_module.try_execute_chunk(
+        &test.keeper,
+        ct,
+        &order_id,
+        &test.router.address,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(result, Err(Ok(Error::PriceBelowLimit)));
+
+    test.oracle.set_price(t0, &PRICE);
+    test.env.ledger().with_mut(|li| li.timestamp += 3_601);
+    let result = test.twap_module.try_execute_chunk(
+        &test.keeper,
+        ct,
+        &order_id,
+        &test.router.address,
+        &deadline,
+        &1000u32,
+    );
+    assert_eq!(result, Err(Ok(Error::StalePrice)));
+
+    test.oracle.set_price(t0, &PRICE);
+    test.oracle.set_price(t1, &PRICE);
+    let chunk = test.twap_module.execute_chunk(
+        &test.keeper,
+        ct,
+        &order_id,
+        &test.router.address,
+        &deadline,
+        &1000u32,
+    );
+    assert!(chunk.amount_out >= 990);
+}
@@ -0,0 +1,36 @@
+use crate::error::Error;
+use crate::storage::types::{TwapOrder, MAX_BOUNTY_BPS};
+use constellation_lib::orders::read_order;
+use soroban_sdk::{Address, Env};
+
+pub fn require_order(
+    e: &Env,
+    constellation_token_id: &Address,
+    order_id: u32,
+) -> Result<TwapOrder, Error> {
+    let order = match read_order(e, constellation_token_id, order_id) {
+        Some(order) => order,
+        None => return Err(Error::RequiresOrder),
+    };
+    Ok(order)
+}
+
+/// Returns error if the amounts, price or bounds of `order` are out of range,
+/// or if its slippage exceeds `ceiling_bps`
+pub fn require_valid_order(order: &TwapOrder, ceiling_bps: u32) -> Result<(), Error> {
+    if order.token_in_id == order.token_out_id
+        || order.amount_in_remaining <= 0
+        || order.chunk_amount_in <= 0
+        || order.chunk_amount_in > order.amount_in_remaining
+        || order.min_price <= 0
+    {
+        return Err(Error::InvalidOrder);
+    }
+    if order.max_slippage_bps > ceiling_bps {
+        return Err(Error::InvalidSlippage);
+    }
+    if order.bounty_bps > MAX_BOUNTY_BPS {
+        return Err(Error::InvalidBounty);
+    }
+    Ok(())
+}
@@ -1,9 +1,19 @@
-use crate::storage::adapter::{read_adapter, remove_adapter as _remove_adapter, write_adapter};
+use crate::storage::adapter::{
+    read_adapter, read_exchanges, remove_adapter as _remove_adapter, write_adapter,
+};
 use crate::storage::admin::{has_administrator, write_administrator};
 use crate::storage::module::{read_module, remove_module as _remove_module, write_module};
-use soroban_sdk::{contract, contractimpl, contracttype, panic_with_error, Address, Env};
+use crate::storage::oracle::{read_oracle, write_oracle};
+use crate::storage::protocol::{
+    read_factory, read_max_price_deviation, read_protocol_fee, write_factory,
+    write_max_price_deviation, write_protocol_fee,
+};
+use crate::storage::types::{ProtocolFee, BPS_DENOMINATOR};
+use constellation_lib::types::oracle::OracleConfig;
+use soroban_sdk::{contract, contractimpl, contracttype, panic_with_error, Address, Env, Vec};
 
 use crate::error::Error;
+use crate::event;
 use crate::validation::require_administrator;
 #[contract]
 pub struct Registry {}
@@ -52,4 +62,98 @@ impl Registry {
     pub fn get_adapter_id(e: Env, module_id: Address, target_id: Address) -> Option<Address> {
         read_adapter(&e, module_id, target_id)
     }
+
+    /// Returns the exchanges with an adapter registered for `module_id`, in the order they were added
+    pub fn get_exchanges(e: Env, module_id: Address) -> Vec<Address> {
+        read_exchanges(&e, &module_id)
+    }
+
+    /// Sets the protocol treasury and the share of mint and redeem fees it receives
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `treasury` Address receiving the protocol share of fees
+    /// - `share_bps` Share of each fee sent to the treasury, in basis points
+    pub fn set_protocol_fee(e: Env, treasury: Address, share_bps: u32) -> Result<(), Error> {
+        require_administrator(&e)?;
+        if share_bps > BPS_DENOMINATOR {
+            return Err(Error::InvalidFeeShare);
+        }
+        write_protocol_fee(
+            &e,
+            &ProtocolFee {
+                treasury,
+                share_bps,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get_protocol_fee(e: Env) -> Option<ProtocolFee> {
+        read_protocol_fee(&e)
+    }
+
+    /// Sets the price feed constellation tokens without their own oracle are valued with
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `oracle_id` SEP-40 price feed
+    /// - `max_price_age` Maximum age of a price, in seconds
+    pub fn set_oracle(e: Env, oracle_id: Address, max_price_age: u64) -> Result<(), Error> {
+        require_administrator(&e)?;
+        if max_price_age == 0 {
+            return Err(Error::InvalidPriceAge);
+        }
+        write_oracle(
+            &e,
+            &OracleConfig {
+                oracle_id: oracle_id.clone(),
+                max_price_age,
+            },
+        );
+        event::set_oracle(&e, oracle_id, max_price_age);
+        Ok(())
+    }
+
+    pub fn get_oracle(e: Env) -> Option<OracleConfig> {
+        read_oracle(&e)
+    }
+
+    /// Sets the protocol-wide ceiling on the deviation of trade rates from oracle prices
+    /// Modules cap the deviation each constellation token accepts at this ceiling
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `max_deviation_bps` Maximum deviation below the oracle rate, in basis points
+    pub fn set_max_price_deviation(e: Env, max_deviation_bps: u32) -> Result<(), Error> {
+        require_administrator(&e)?;
+        if max_deviation_bps > BPS_DENOMINATOR {
+            return Err(Error::InvalidDeviation);
+        }
+        write_max_price_deviation(&e, max_deviation_bps);
+        Ok(())
+    }
+
+    pub fn get_max_price_deviation(e: Env) -> Option<u32> {
+        read_max_price_deviation(&e)
+    }
+
+    /// Sets the factory deploying constellation tokens, whose limits modules enforce
+    ///
+    /// # Arguments
+    ///
+    /// - `e` The runtime environment.
+    /// - `factory_id` Constellation token factory
+    pub fn set_factory(e: Env, factory_id: Address) -> Result<(), Error> {
+        require_administrator(&e)?;
+        write_factory(&e, &factory_id);
+        Ok(())
+    }
+
+    pub fn get_factory(e: Env) -> Option<Address> {
+        read_factory(&e)
+    }
 }
@@ -8,4 +8,7 @@ pub enum Error {
     UnregisteredModule = 102,
     RequiresAdmin = 103,
     UnregisteredAdapter = 104,
+    InvalidFeeShare = 105,
+    InvalidPriceAge = 106,
+    InvalidDeviation = 107,
 }
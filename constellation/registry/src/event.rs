@@ -0,0 +1,19 @@
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetOracle {
+    oracle_id: Address,
+    max_price_age: u64,
+}
+
+pub(crate) fn set_oracle(e: &Env, oracle_id: Address, max_price_age: u64) {
+    let topics = (Symbol::new(e, "set_oracle"), e.current_contract_address());
+    e.events().publish(
+        topics,
+        SetOracle {
+            oracle_id,
+            max_price_age,
+        },
+    );
+}
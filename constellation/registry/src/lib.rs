@@ -1,5 +1,6 @@
 #![no_std]
 mod contract;
 mod error;
+mod event;
 pub mod storage;
 mod validation;
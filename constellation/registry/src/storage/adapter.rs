@@ -1,7 +1,7 @@
 use super::keys::DataKey;
 use super::module::read_or_panic_unregistered_module;
 use crate::error::Error;
-use soroban_sdk::{panic_with_error, Address, Env};
+use soroban_sdk::{panic_with_error, vec, Address, Env, Vec};
 pub fn read_adapter(e: &Env, module_id: Address, target_id: Address) -> Option<Address> {
     let key = DataKey::Adapter(module_id, target_id.clone());
     e.storage().instance().get(&key)
@@ -9,13 +9,36 @@ pub fn read_adapter(e: &Env, module_id: Address, target_id: Address) -> Option<A
 
 pub fn write_adapter(e: &Env, module_id: Address, target_id: Address, adapter_id: Address) {
     read_or_panic_unregistered_module(e, &module_id);
-    let key = DataKey::Adapter(module_id, target_id.clone());
+    let key = DataKey::Adapter(module_id.clone(), target_id.clone());
     e.storage().instance().set(&key, &adapter_id);
+
+    let mut exchanges = read_exchanges(e, &module_id);
+    if !exchanges.contains(&target_id) {
+        exchanges.push_back(target_id);
+        write_exchanges(e, &module_id, &exchanges);
+    }
 }
 
 pub fn remove_adapter(e: &Env, module_id: Address, target_id: Address) {
-    let key: DataKey = DataKey::Adapter(module_id, target_id);
+    let key: DataKey = DataKey::Adapter(module_id.clone(), target_id.clone());
     e.storage().instance().remove(&key);
+
+    let mut exchanges = read_exchanges(e, &module_id);
+    if let Some(index) = exchanges.first_index_of(&target_id) {
+        exchanges.remove(index);
+        write_exchanges(e, &module_id, &exchanges);
+    }
+}
+
+/// Returns the exchanges with an adapter registered for `module_id`
+pub fn read_exchanges(e: &Env, module_id: &Address) -> Vec<Address> {
+    let key = DataKey::Exchanges(module_id.clone());
+    e.storage().instance().get(&key).unwrap_or(vec![e])
+}
+
+fn write_exchanges(e: &Env, module_id: &Address, exchanges: &Vec<Address>) {
+    let key = DataKey::Exchanges(module_id.clone());
+    e.storage().instance().set(&key, exchanges);
 }
 
 pub fn has_adapter(e: &Env, module_id: Address, target_id: &Address) -> bool {
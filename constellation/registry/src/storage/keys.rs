@@ -6,4 +6,9 @@ pub enum DataKey {
     Admin,
     Adapter(Address /*module_id*/, Address /* adapter_id*/),
     Module(Address),
+    Exchanges(Address /*module_id*/),
+    ProtocolFee,
+    Oracle,
+    MaxPriceDeviation,
+    Factory,
 }
@@ -2,3 +2,6 @@ pub mod adapter;
 pub mod admin;
 pub mod keys;
 pub mod module;
+pub mod oracle;
+pub mod protocol;
+pub mod types;
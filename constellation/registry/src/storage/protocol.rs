@@ -0,0 +1,34 @@
+use soroban_sdk::{Address, Env};
+
+use super::keys::DataKey;
+use super::types::ProtocolFee;
+
+pub fn read_protocol_fee(e: &Env) -> Option<ProtocolFee> {
+    let key = DataKey::ProtocolFee;
+    e.storage().instance().get(&key)
+}
+
+pub fn write_protocol_fee(e: &Env, protocol_fee: &ProtocolFee) {
+    let key = DataKey::ProtocolFee;
+    e.storage().instance().set(&key, protocol_fee);
+}
+
+pub fn read_max_price_deviation(e: &Env) -> Option<u32> {
+    let key = DataKey::MaxPriceDeviation;
+    e.storage().instance().get(&key)
+}
+
+pub fn write_max_price_deviation(e: &Env, max_deviation_bps: u32) {
+    let key = DataKey::MaxPriceDeviation;
+    e.storage().instance().set(&key, &max_deviation_bps);
+}
+
+pub fn read_factory(e: &Env) -> Option<Address> {
+    let key = DataKey::Factory;
+    e.storage().instance().get(&key)
+}
+
+pub fn write_factory(e: &Env, factory_id: &Address) {
+    let key = DataKey::Factory;
+    e.storage().instance().set(&key, factory_id);
+}
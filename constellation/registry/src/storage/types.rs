@@ -0,0 +1,12 @@
+use soroban_sdk::{contracttype, Address};
+
+pub(crate) const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Protocol treasury and its share of the fees charged on constellation tokens
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ProtocolFee {
+    pub treasury: Address,
+    /// Share of each fee sent to the treasury, in basis points
+    pub share_bps: u32,
+}
@@ -1,18 +1,20 @@
 use crate::error::{self, Error};
 use crate::event;
 use crate::factory;
+use crate::exchange;
 use crate::helper::{
-     get_required_amount_token_in as _get_required_amount_token_in,
-    refund_unspent, swap_exact_tokens_for_tokens, swap_tokens_for_exact_tokens,
+    check_routes, component_quantity, get_best_routes, get_mint_amount_out,
+    get_required_amount_token_in as _get_required_amount_token_in, leg_route, refund_unspent,
+    split_route_out, swap_route, swap_tokens_for_exact_tokens, take_fee, DEFAULT_DECIMALS,
 };
-use crate::require::require_exchange_router;
-use crate::soroswap_router;
+use crate::require::require_manager;
 use crate::storage::{
-    has_factory, read_exchange_router, read_factory, write_exchange_router,
-    write_factory,
+    has_factory, read_exchange_router, read_factory, read_fees, write_exchange_router,
+    write_factory, write_fees, write_registry, TokenFees, MAX_FEE_BPS,
 };
-use crate::token as ctoken;
-use crate::token::constellation_token::Component;
+use crate::token::{self as ctoken, Component};
+use crate::types::SwapRoute;
+use constellation_lib::math::Rounding;
 use constellation_lib::traits::adapter::dex;
 use soroban_sdk::auth::SubContractInvocation;
 use soroban_sdk::{
@@ -31,16 +33,20 @@ impl Router {
     /// # Arguments
     /// - `e` - The runtime environment.
     /// - `factory` - Factory contract address
+    /// - `soroswap_router` - Exchange router used for swaps
+    /// - `registry` - Registry holding the protocol fee configuration
     pub fn initialize(
         e: Env,
         factory: Address,
         soroswap_router: Address,
+        registry: Address,
     ) -> Result<(), Error> {
         if has_factory(&e) {
             return Err(Error::AlreadyInitalized);
         }
         write_factory(&e, &factory);
         write_exchange_router(&e, &soroswap_router);
+        write_registry(&e, &registry);
         event::initialize(&e, factory);
         Ok(())
     }
@@ -58,6 +64,8 @@ impl Router {
     /// the amount of constellation token to mint - see the lock function called in the mint function of the constellatio token
     ///
     /// Caller must also approve constellation token to spend each of the component tokens of the constellation token
+    ///
+    /// The issue fee of the constellation token is taken from the minted amount
     pub fn mint(
         e: Env,
         to: Address,
@@ -70,7 +78,13 @@ impl Router {
             return Err(Error::ZeroOrNegativeAmount);
         }
 
-        ctoken::mint(&e, &to, amount, &constellation_token_address);
+        ctoken::mint(
+            &e,
+            &e.current_contract_address(),
+            amount,
+            &constellation_token_address,
+        );
+        Self::issue(&e, &to, amount, &constellation_token_address)?;
         Ok(())
     }
 
@@ -84,12 +98,17 @@ impl Router {
     /// - `token_in` - Address of input token
     /// - `to` - Address to receive constellation token
     /// - `constellation_token_id` Constellation token address
+    /// - `routes` Optional swap route from `token_in` to each component, in the order of the components.
+    /// Direct swaps on the default exchange are used if not set
     /// - `deadline` swap deadline
     ///
     /// Caller must possess balances of component tokens of the specified constellation token
     /// equal to or greater than the unit amount of the component token (of the constellation token) multiplied by
     /// the amount of constellation token to mint - see the lock function called in the mint function of the constellatio token
     ///
+    /// The issue fee of the constellation token is taken from `mint_amount`
+    /// If `token_in` is a component, its quantity is taken from `amount_in` without a swap
+    #[allow(clippy::too_many_arguments)]
     pub fn mint_exact_tokens(
         e: Env,
         mint_amount: i128,
@@ -97,11 +116,11 @@ impl Router {
         token_in: Address,
         to: Address,
         constellation_token_id: Address,
+        routes: Option<Vec<SwapRoute>>,
         deadline: u64,
     ) -> Result<i128, Error> {
         to.require_auth();
 
-        let router_id = require_exchange_router(&e);
         // transfers token in to the router
         token::Client::new(&e, &token_in).transfer_from(
             &e.current_contract_address(),
@@ -111,82 +130,241 @@ impl Router {
         );
 
         let components = ctoken::get_components(&e, &constellation_token_id);
+        let decimals = ctoken::decimals(&e, &constellation_token_id);
 
-        let (total_token_in_amount, token_amounts_in) =
-            _get_required_amount_token_in(&e, &token_in, mint_amount, &components)?;
+        let (total_token_in_amount, token_amounts_in) = _get_required_amount_token_in(
+            &e,
+            &token_in,
+            mint_amount,
+            &components,
+            decimals,
+            &routes,
+        )?;
 
         if total_token_in_amount > amount_in {
             return Err(Error::InsufficientInputAmount);
         }
-       
-        token::Client::new(&e, &token_in).approve(&e.current_contract_address(),&router_id, &amount_in,  &(e.ledger().sequence() + 1000u32));
-        
-        // swaps token_in for the component tokens
-        let mut total_spent = swap_tokens_for_exact_tokens(
+
+        let (_, refund) = Self::swap_and_mint(
             &e,
-            &mint_amount,
+            &to,
             &token_in,
-            &e.current_contract_address(),
-            &router_id,
+            amount_in,
+            mint_amount,
             &token_amounts_in,
             &components,
+            decimals,
+            &routes,
             &constellation_token_id,
             deadline,
         )?;
-        
-        // mints the constellation token
-        ctoken::mint(&e, &to, mint_amount, &constellation_token_id);
 
-        let refund = amount_in - total_spent;
+        event::mint_exact_constellation(&e, to, mint_amount, refund);
+        Ok(refund)
+    }
+
+    /// Spends `amount_in` of `token_in` to mint the largest amount of constellation tokens
+    /// its component quotes allow, and refunds the unspent input
+    /// Returns the amount of constellation tokens received
+    ///
+    /// # Arguments
+    /// - `e` - The runtime environment.
+    /// - `to` - Address spending `token_in` and receiving the constellation tokens
+    /// - `token_in` - Address of input token
+    /// - `amount_in` - Amount of input token to spend
+    /// - `min_mint_amount` - Minimum amount of constellation tokens to receive, after the issue fee
+    /// - `constellation_token_id` - Constellation token address
+    /// - `routes` - Optional swap route from `token_in` to each component, in the order of the components.
+    /// Direct swaps on the default exchange are used if not set
+    /// - `deadline` - swap deadline
+    ///
+    /// Returns error if less than `min_mint_amount` constellation tokens are received
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_with_exact_input(
+        e: Env,
+        to: Address,
+        token_in: Address,
+        amount_in: i128,
+        min_mint_amount: i128,
+        constellation_token_id: Address,
+        routes: Option<Vec<SwapRoute>>,
+        deadline: u64,
+    ) -> Result<i128, Error> {
+        to.require_auth();
+
+        if amount_in <= 0 {
+            return Err(Error::ZeroOrNegativeAmount);
+        }
+
+        let components = ctoken::get_components(&e, &constellation_token_id);
+        let decimals = ctoken::decimals(&e, &constellation_token_id);
+
+        let (mint_amount, _, token_amounts_in) =
+            get_mint_amount_out(&e, &token_in, amount_in, &components, decimals, &routes)?;
+        if mint_amount <= 0 {
+            return Err(Error::InsufficientInputAmount);
+        }
 
-        refund_unspent(&e, refund, &token_in, &to, deadline);
+        // transfers token in to the router
+        token::Client::new(&e, &token_in).transfer_from(
+            &e.current_contract_address(),
+            &to,
+            &e.current_contract_address(),
+            &amount_in,
+        );
+
+        let (amount_issued, refund) = Self::swap_and_mint(
+            &e,
+            &to,
+            &token_in,
+            amount_in,
+            mint_amount,
+            &token_amounts_in,
+            &components,
+            decimals,
+            &routes,
+            &constellation_token_id,
+            deadline,
+        )?;
+
+        if amount_issued < min_mint_amount {
+            return Err(Error::InsufficientOutputAmount);
+        }
 
         event::mint_exact_constellation(&e, to, mint_amount, refund);
-        Ok(refund)
+        Ok(amount_issued)
     }
 
+    /// Redeems constellation tokens and swaps the released components into `redeem_token`
+    /// The redeem fee of the constellation token is taken before redeeming
+    ///
+    /// # Arguments
+    /// - `e` - The runtime environment.
+    /// - `to` - Holder of the constellation tokens, receives the `redeem_token`
+    /// - `amount` - Amount of constellation tokens to redeem
+    /// - `constellation_token` - Constellation token address
+    /// - `redeem_token` - Token received for the components
+    /// - `min_amount_out` - Minimum total amount of `redeem_token` to receive
+    /// - `component_min_amounts` - Optional minimum amount of `redeem_token` received for each component,
+    /// in the order of the constellation token components
+    /// - `routes` - Optional swap route from each component to `redeem_token`, in the order of the components.
+    /// Direct swaps on the default exchange are used if not set
+    /// - `deadline` - swap deadline
+    ///
+    /// Returns the amount of `redeem_token` received
+    /// Returns error if a minimum is not met
+    /// If `redeem_token` is a component, its quantity is transferred without a swap
+    ///
+    /// Caller must also approve router contract token to spend the constellation token
+    #[allow(clippy::too_many_arguments)]
     pub fn redeem_into(
         e: Env,
         to: Address,
         amount: i128,
         constellation_token: Address,
         redeem_token: Address,
+        min_amount_out: i128,
+        component_min_amounts: Option<Vec<i128>>,
+        routes: Option<Vec<SwapRoute>>,
         deadline: u64,
-    ) -> Result<(), Error> {
+    ) -> Result<i128, Error> {
         to.require_auth();
 
         if amount <= 0 {
             return Err(Error::ZeroOrNegativeAmount);
         }
 
-        let router_id = &require_exchange_router(&e);
-
+        ctoken::collect(&e, &to, amount, &constellation_token);
+        let redeem_fee_bps = read_fees(&e, &constellation_token).redeem_fee_bps;
+        let amount_redeemed = take_fee(
+            &e,
+            &constellation_token,
+            amount,
+            redeem_fee_bps,
+            Symbol::new(&e, "redeem_into"),
+        )?;
         ctoken::redeem(
             &e,
-            &to,
             &e.current_contract_address(),
-            amount,
+            amount_redeemed,
             &constellation_token,
         );
 
         let components = ctoken::get_components(&e, &constellation_token);
- 
-        for c in components.iter() {
-            let amount_in = c.unit *  amount;
-            soroswap_router::swap_exact_tokens_for_tokens(
-                &e,
-                &router_id,
-                amount_in,
-                0,
-                &c.address,
-                &redeem_token,
-                &to,
-                deadline,
-            );
+        let decimals = ctoken::decimals(&e, &constellation_token);
+
+        if let Some(min_amounts) = &component_min_amounts {
+            if min_amounts.len() != components.len() {
+                return Err(Error::ComponentsLengthMismatch);
+            }
         }
+        check_routes(&routes, components.len())?;
 
-        event::redeem_into(&e, to, redeem_token, constellation_token, amount);
-        Ok(())
+        let mut amount_out = 0;
+        for (i, c) in components.iter().enumerate() {
+            let component_min_amount = match &component_min_amounts {
+                Some(min_amounts) => min_amounts.get(i as u32).unwrap_or(0),
+                None => 0,
+            };
+            // rounded down, as the constellation token releases when redeeming
+            let amount_in =
+                component_quantity(amount_redeemed, c.unit, decimals, Rounding::Down)?;
+            if amount_in == 0 {
+                if component_min_amount > 0 {
+                    return Err(Error::InsufficientOutputAmount);
+                }
+                continue;
+            }
+
+            // the redeem token is passed through without a swap
+            if c.address == redeem_token {
+                if amount_in < component_min_amount {
+                    return Err(Error::InsufficientOutputAmount);
+                }
+                token::Client::new(&e, &c.address).transfer(
+                    &e.current_contract_address(),
+                    &to,
+                    &amount_in,
+                );
+                amount_out += amount_in;
+                continue;
+            }
+
+            let route = swap_route(&e, &routes, i as u32, &c.address, &redeem_token)?;
+            if route.parts == 1 {
+                amount_out += exchange::swap_exact_in(
+                    &e,
+                    &route,
+                    amount_in,
+                    component_min_amount,
+                    &to,
+                    deadline,
+                );
+                continue;
+            }
+            let mut component_amount_out = 0;
+            for leg in split_route_out(&e, &route, amount_in)?.iter() {
+                component_amount_out += exchange::swap_exact_in(
+                    &e,
+                    &leg_route(&route, &leg),
+                    leg.amount_in,
+                    0,
+                    &to,
+                    deadline,
+                );
+            }
+            if component_amount_out < component_min_amount {
+                return Err(Error::InsufficientOutputAmount);
+            }
+            amount_out += component_amount_out;
+        }
+
+        if amount_out < min_amount_out {
+            return Err(Error::InsufficientOutputAmount);
+        }
+
+        event::redeem_into(&e, to, redeem_token, constellation_token, amount, amount_out);
+        Ok(amount_out)
     }
 
     /// Burns constellation token amount and releases component tokens to the specified `from` address
@@ -199,6 +377,7 @@ impl Router {
     /// - `amount` - Amount to mint
     ///
     /// Caller must also approve router contract token to spend the constellation token
+    /// The redeem fee of the constellation token is taken before redeeming
     pub fn burn(
         e: Env,
         from: Address,
@@ -211,10 +390,59 @@ impl Router {
             return Err(Error::ZeroOrNegativeAmount);
         }
 
-        ctoken::redeem(&e, &from, &from, amount, &constellation_token_address);
+        ctoken::collect(&e, &from, amount, &constellation_token_address);
+        let redeem_fee_bps = read_fees(&e, &constellation_token_address).redeem_fee_bps;
+        let amount_redeemed = take_fee(
+            &e,
+            &constellation_token_address,
+            amount,
+            redeem_fee_bps,
+            Symbol::new(&e, "burn"),
+        )?;
+        ctoken::redeem(&e, &from, amount_redeemed, &constellation_token_address);
+        Ok(())
+    }
+
+    /// Sets the issue and redeem fees of a constellation token
+    /// Returns error if a fee exceeds `MAX_FEE_BPS`
+    ///
+    /// # Arguments
+    /// - `e` - The runtime environment.
+    /// - `constellation_token_id` - Constellation token address
+    /// - `issue_fee_bps` - Fee taken on mint, in basis points
+    /// - `redeem_fee_bps` - Fee taken on redeem, in basis points
+    ///
+    /// Requires the authorization of the constellation token manager
+    pub fn set_fees(
+        e: Env,
+        constellation_token_id: Address,
+        issue_fee_bps: u32,
+        redeem_fee_bps: u32,
+    ) -> Result<(), Error> {
+        let manager = require_manager(&e, &constellation_token_id);
+        manager.require_auth();
+
+        if issue_fee_bps > MAX_FEE_BPS || redeem_fee_bps > MAX_FEE_BPS {
+            return Err(Error::FeeExceedsCap);
+        }
+
+        write_fees(
+            &e,
+            &constellation_token_id,
+            &TokenFees {
+                issue_fee_bps,
+                redeem_fee_bps,
+            },
+        );
+        event::set_fees(&e, constellation_token_id, issue_fee_bps, redeem_fee_bps);
         Ok(())
     }
 
+    /// Returns the issue and redeem fees of a constellation token
+    pub fn get_fees(e: Env, constellation_token_id: Address) -> TokenFees {
+        read_fees(&e, &constellation_token_id)
+    }
+
     /// creates new constellation token by calling factory
     /// Returns contellation token address. Returns error if number of components exceeds max if set
     ///
@@ -262,13 +490,160 @@ impl Router {
         read_factory(&e)
     }
 
+    /// Returns the total amount of `token_in` required to mint `mint_amount` constellation tokens
+    /// and the amount required for each component
+    ///
+    /// # Arguments
+    /// - `e` - The runtime environment.
+    /// - `token_in` - Address of input token
+    /// - `mint_amount` - Amount of constellation tokens to mint, with `DEFAULT_DECIMALS` decimals
+    /// - `components` - Components of the constellation token
+    /// - `routes` - Optional swap route from `token_in` to each component, direct swaps on the default exchange if not set
     pub fn get_required_amount_token_in(
         e: Env,
         token_in: Address,
         mint_amount: i128,
         components: Vec<Component>,
+        routes: Option<Vec<SwapRoute>>,
     ) -> Result<(i128, Vec<i128>), Error> {
-        _get_required_amount_token_in(&e, &token_in, mint_amount, &components)
+        _get_required_amount_token_in(
+            &e,
+            &token_in,
+            mint_amount,
+            &components,
+            DEFAULT_DECIMALS,
+            &routes,
+        )
+    }
+
+    /// Returns the largest amount of constellation tokens mintable with `amount_in` of `token_in`,
+    /// before the issue fee, and the amount of `token_in` it spends
+    ///
+    /// # Arguments
+    /// - `e` - The runtime environment.
+    /// - `token_in` - Address of input token
+    /// - `amount_in` - Amount of input token to spend
+    /// - `constellation_token_id` - Constellation token address
+    /// - `routes` - Optional swap route from `token_in` to each component, direct swaps on the default exchange if not set
+    pub fn get_mint_amount_out(
+        e: Env,
+        token_in: Address,
+        amount_in: i128,
+        constellation_token_id: Address,
+        routes: Option<Vec<SwapRoute>>,
+    ) -> Result<(i128, i128), Error> {
+        let components = ctoken::get_components(&e, &constellation_token_id);
+        let decimals = ctoken::decimals(&e, &constellation_token_id);
+        let (mint_amount, total_token_in_amount, _) =
+            get_mint_amount_out(&e, &token_in, amount_in, &components, decimals, &routes)?;
+        Ok((mint_amount, total_token_in_amount))
+    }
+
+    /// Returns the route from `token_in` to each component on the registered exchange
+    /// requiring the least `token_in` to mint `mint_amount` constellation tokens
+    ///
+    /// # Arguments
+    /// - `e` - The runtime environment.
+    /// - `token_in` - Address of input token
+    /// - `mint_amount` - Amount of constellation tokens to mint
+    /// - `constellation_token_id` - Constellation token address
+    ///
+    /// Returns error if no exchange quotes a component
+    pub fn get_best_mint_routes(
+        e: Env,
+        token_in: Address,
+        mint_amount: i128,
+        constellation_token_id: Address,
+    ) -> Result<Vec<SwapRoute>, Error> {
+        let components = ctoken::get_components(&e, &constellation_token_id);
+        let decimals = ctoken::decimals(&e, &constellation_token_id);
+        get_best_routes(&e, &token_in, mint_amount, &components, decimals, true)
+    }
+
+    /// Returns the route from each component to `redeem_token` on the registered exchange
+    /// giving the most `redeem_token` for redeeming `amount` constellation tokens
+    ///
+    /// # Arguments
+    /// - `e` - The runtime environment.
+    /// - `amount` - Amount of constellation tokens to redeem, after the redeem fee
+    /// - `constellation_token_id` - Constellation token address
+    /// - `redeem_token` - Token received for the components
+    ///
+    /// Returns error if no exchange quotes a component
+    pub fn get_best_redeem_routes(
+        e: Env,
+        amount: i128,
+        constellation_token_id: Address,
+        redeem_token: Address,
+    ) -> Result<Vec<SwapRoute>, Error> {
+        let components = ctoken::get_components(&e, &constellation_token_id);
+        let decimals = ctoken::decimals(&e, &constellation_token_id);
+        get_best_routes(&e, &redeem_token, amount, &components, decimals, false)
+    }
+
+    /// Swaps the `token_in` held by the router for the components of `mint_amount` constellation tokens,
+    /// mints them to `to` and refunds the unspent input
+    /// Returns the amount of constellation tokens issued after the fee and the refund
+    #[allow(clippy::too_many_arguments)]
+    fn swap_and_mint(
+        e: &Env,
+        to: &Address,
+        token_in: &Address,
+        amount_in: i128,
+        mint_amount: i128,
+        token_amounts_in: &Vec<i128>,
+        components: &Vec<Component>,
+        decimals: u32,
+        routes: &Option<Vec<SwapRoute>>,
+        constellation_token_id: &Address,
+        deadline: u64,
+    ) -> Result<(i128, i128), Error> {
+        // swaps token_in for the component tokens
+        let total_spent = swap_tokens_for_exact_tokens(
+            e,
+            &mint_amount,
+            token_in,
+            &e.current_contract_address(),
+            token_amounts_in,
+            components,
+            decimals,
+            routes,
+            constellation_token_id,
+            deadline,
+        )?;
+
+        // mints the constellation token
+        ctoken::mint(
+            e,
+            &e.current_contract_address(),
+            mint_amount,
+            constellation_token_id,
+        );
+        let amount_issued = Self::issue(e, to, mint_amount, constellation_token_id)?;
+
+        let refund = amount_in - total_spent;
+        refund_unspent(e, refund, token_in, to, deadline);
+        Ok((amount_issued, refund))
+    }
+
+    /// Takes the issue fee from constellation tokens minted to the router
+    /// and transfers the rest to `to`
+    fn issue(
+        e: &Env,
+        to: &Address,
+        amount: i128,
+        constellation_token_id: &Address,
+    ) -> Result<i128, Error> {
+        let issue_fee_bps = read_fees(e, constellation_token_id).issue_fee_bps;
+        let amount_issued = take_fee(
+            e,
+            constellation_token_id,
+            amount,
+            issue_fee_bps,
+            Symbol::new(e, "mint"),
+        )?;
+        ctoken::transfer(e, to, amount_issued, constellation_token_id);
+        Ok(amount_issued)
     }
 
     pub fn invoke(
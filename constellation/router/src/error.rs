@@ -19,4 +19,14 @@ pub enum Error {
     RequiresXlmID = 507,
     AmountsInError = 508,
     SwapError = 509,
+    ValueTooLargeOverFlow = 510,
+    FeeExceedsCap = 511,
+    RequiresRegistry = 512,
+    RequiresManager = 513,
+    InsufficientOutputAmount = 514,
+    ComponentsLengthMismatch = 515,
+    InvalidPath = 516,
+    RequiresExchangeAdapter = 517,
+    NoQuote = 518,
+    InvalidSplit = 519,
 }
@@ -21,6 +21,34 @@ pub struct RedeemInto {
     redeem_token: Address,
     constellation_token: Address,
     amount: i128,
+    amount_out: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetFees {
+    constellation_token: Address,
+    issue_fee_bps: u32,
+    redeem_fee_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeTaken {
+    constellation_token: Address,
+    action: Symbol,
+    manager_amount: i128,
+    protocol_amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Swap {
+    exchange_id: Address,
+    token_in: Address,
+    token_out: Address,
+    amount_in: i128,
+    amount_out: i128,
 }
 
 /// Emits initialize contract even
@@ -44,6 +72,7 @@ pub(crate) fn redeem_into(
     redeem_token: Address,
     constellation_token: Address,
     amount: i128,
+    amount_out: i128,
 ) {
     let topics = (Symbol::new(e, "redeem_into"), e.current_contract_address());
     e.events().publish(
@@ -53,6 +82,65 @@ pub(crate) fn redeem_into(
             redeem_token,
             constellation_token,
             amount,
+            amount_out,
+        },
+    );
+}
+
+pub(crate) fn set_fees(
+    e: &Env,
+    constellation_token: Address,
+    issue_fee_bps: u32,
+    redeem_fee_bps: u32,
+) {
+    let topics = (Symbol::new(e, "set_fees"), e.current_contract_address());
+    e.events().publish(
+        topics,
+        SetFees {
+            constellation_token,
+            issue_fee_bps,
+            redeem_fee_bps,
+        },
+    );
+}
+
+pub(crate) fn fee_taken(
+    e: &Env,
+    constellation_token: Address,
+    action: Symbol,
+    manager_amount: i128,
+    protocol_amount: i128,
+) {
+    let topics = (Symbol::new(e, "fee_taken"), e.current_contract_address());
+    e.events().publish(
+        topics,
+        FeeTaken {
+            constellation_token,
+            action,
+            manager_amount,
+            protocol_amount,
+        },
+    );
+}
+
+/// Emits the exchange and amounts of a swap made for a mint or a redeem
+pub(crate) fn swap(
+    e: &Env,
+    exchange_id: Address,
+    token_in: Address,
+    token_out: Address,
+    amount_in: i128,
+    amount_out: i128,
+) {
+    let topics = (Symbol::new(e, "swap"), e.current_contract_address());
+    e.events().publish(
+        topics,
+        Swap {
+            exchange_id,
+            token_in,
+            token_out,
+            amount_in,
+            amount_out,
         },
     );
 }
@@ -0,0 +1,109 @@
+use crate::event;
+use crate::registry::get_exchanges;
+use crate::require::{require_adapter, require_registry};
+use crate::types::SwapRoute;
+use constellation_lib::traits::adapter::dex;
+use constellation_lib::types::exchange::Venue;
+use soroban_sdk::{token, vec, Address, Env, Val, Vec};
+
+pub(crate) fn get_amounts_in(
+    e: &Env,
+    exchange_id: &Address,
+    amount_out: i128,
+    path: &Vec<Address>,
+) -> Vec<i128> {
+    let adapter = dex::Client::new(e, &require_adapter(e, exchange_id));
+    adapter.get_path_amounts_in(&amount_out, path)
+}
+
+/// Swaps exactly `amount_in` of the first token of the route held by the router,
+/// for at least `amount_out_min` of its last token sent to `to`
+/// Returns the amount received by `to`
+pub(crate) fn swap_exact_in(
+    e: &Env,
+    route: &SwapRoute,
+    amount_in: i128,
+    amount_out_min: i128,
+    to: &Address,
+    deadline: u64,
+) -> i128 {
+    let adapter = dex::Client::new(e, &require_adapter(e, &route.exchange_id));
+    let token_out = token::Client::new(e, &route.path.get_unchecked(route.path.len() - 1));
+    let balance_before = token_out.balance(to);
+
+    let (function, args) =
+        adapter.get_path_swap_call_data(&route.path, &amount_in, &amount_out_min, to, &deadline);
+    execute(e, &adapter, route, amount_in, function, args);
+
+    let amount_out = token_out.balance(to) - balance_before;
+    emit_swap(e, route, amount_in, amount_out);
+    amount_out
+}
+
+/// Swaps at most `amount_in_max` of the first token of the route held by the router,
+/// for exactly `amount_out` of its last token sent to the router
+/// Returns the amount spent
+pub(crate) fn swap_exact_out(
+    e: &Env,
+    route: &SwapRoute,
+    amount_out: i128,
+    amount_in_max: i128,
+    deadline: u64,
+) -> i128 {
+    let adapter = dex::Client::new(e, &require_adapter(e, &route.exchange_id));
+    let token_in = token::Client::new(e, &route.path.get_unchecked(0));
+    let balance_before = token_in.balance(&e.current_contract_address());
+
+    let (function, args) = adapter.get_path_swap_out_call_data(
+        &route.path,
+        &amount_out,
+        &amount_in_max,
+        &e.current_contract_address(),
+        &deadline,
+    );
+    execute(e, &adapter, route, amount_in_max, function, args);
+
+    let amount_in = balance_before - token_in.balance(&e.current_contract_address());
+    emit_swap(e, route, amount_in, amount_out);
+    amount_in
+}
+
+/// Returns the exchanges with an adapter registered for the router, with their adapters
+pub(crate) fn get_venues(e: &Env) -> Vec<Venue> {
+    let registry_id = require_registry(e);
+    let mut venues: Vec<Venue> = vec![e];
+    for exchange_id in get_exchanges(e, &registry_id).iter() {
+        let adapter_id = require_adapter(e, &exchange_id);
+        venues.push_back(Venue {
+            exchange_id,
+            adapter_id,
+        });
+    }
+    venues
+}
+
+fn emit_swap(e: &Env, route: &SwapRoute, amount_in: i128, amount_out: i128) {
+    event::swap(
+        e,
+        route.exchange_id.clone(),
+        route.path.get_unchecked(0),
+        route.path.get_unchecked(route.path.len() - 1),
+        amount_in,
+        amount_out,
+    );
+}
+
+fn execute(
+    e: &Env,
+    adapter: &dex::Client,
+    route: &SwapRoute,
+    amount_in: i128,
+    function: soroban_sdk::Symbol,
+    args: Vec<Val>,
+) {
+    // the exchange moves the input token under the router's authorization, no allowance is left
+    let auth_entries =
+        adapter.create_path_sub_auth(&amount_in, &route.path, &e.current_contract_address());
+    e.authorize_as_current_contract(auth_entries);
+    e.invoke_contract::<Val>(&route.exchange_id, &function, args);
+}
@@ -1,145 +1,348 @@
 use crate::error::Error;
-use crate::require::require_exchange_router;
-use crate::soroswap_router;
-use crate::soroswap_router::{router_get_amounts_in, router_get_amounts_out, router_pair_for};
-use crate::token::Component;
-use crate::{auth::*, token::invoke};
-use soroban_sdk::xdr;
-use soroban_sdk::{token, vec, Address, Env, Val, Vec};
+use crate::event;
+use crate::exchange;
+use crate::registry::{get_protocol_fee, ProtocolFee};
+use crate::require::{require_exchange_router, require_manager, require_registry};
+use crate::storage::BPS_DENOMINATOR;
+use crate::token::{self as ctoken, Component};
+use crate::types::SwapRoute;
+use constellation_lib::aggregator::{
+    best_quote_in, best_quote_out, split_quote_in, split_quote_out, MAX_SPLIT_PARTS,
+};
+use constellation_lib::math::{self, Rounding};
+use constellation_lib::types::exchange::SwapLeg;
+use soroban_sdk::{token, vec, Address, Env, Symbol, Vec};
 
-extern crate std;
+/// Decimals of the constellation token amount quoted from a list of components
+pub const DEFAULT_DECIMALS: u32 = 7;
 
+/// Returns the quantity of a component backing `amount` constellation tokens
+/// Returns error if the quantity overflows
+pub fn component_quantity(
+    amount: i128,
+    unit: i128,
+    decimals: u32,
+    rounding: Rounding,
+) -> Result<i128, Error> {
+    match math::component_quantity(amount, unit, decimals, rounding) {
+        Some(quantity) => Ok(quantity),
+        None => Err(Error::ValueTooLargeOverFlow),
+    }
+}
+
+/// Takes `fee_bps` of `amount` constellation tokens held by the router and splits it
+/// between the token manager and the protocol treasury configured in the registry
+/// Returns the amount left after the fee
+///
+/// # Arguments
+/// - `e` - The runtime environment.
+/// - `constellation_token_id` - Constellation token the fee is taken in
+/// - `amount` - Amount of constellation tokens held by the router
+/// - `fee_bps` - Fee in basis points
+/// - `action` - Operation charged, emitted with the fee event
+pub fn take_fee(
+    e: &Env,
+    constellation_token_id: &Address,
+    amount: i128,
+    fee_bps: u32,
+    action: Symbol,
+) -> Result<i128, Error> {
+    let fee = math::mul_div(amount, fee_bps as i128, BPS_DENOMINATOR, Rounding::Down)
+        .ok_or(Error::ValueTooLargeOverFlow)?;
+    if fee == 0 {
+        return Ok(amount);
+    }
+
+    let protocol_amount = match get_protocol_fee(e, &require_registry(e)) {
+        Some(ProtocolFee {
+            treasury,
+            share_bps,
+        }) => {
+            let protocol_amount =
+                math::mul_div(fee, share_bps as i128, BPS_DENOMINATOR, Rounding::Down)
+                    .ok_or(Error::ValueTooLargeOverFlow)?;
+            if protocol_amount > 0 {
+                ctoken::transfer(e, &treasury, protocol_amount, constellation_token_id);
+            }
+            protocol_amount
+        }
+        None => 0,
+    };
+    let manager_amount = fee - protocol_amount;
+    if manager_amount > 0 {
+        let manager = require_manager(e, constellation_token_id);
+        ctoken::transfer(e, &manager, manager_amount, constellation_token_id);
+    }
+
+    event::fee_taken(
+        e,
+        constellation_token_id.clone(),
+        action,
+        manager_amount,
+        protocol_amount,
+    );
+    Ok(amount - fee)
+}
+
+/// Returns error unless `routes` holds one route per component
+pub fn check_routes(routes: &Option<Vec<SwapRoute>>, components_len: u32) -> Result<(), Error> {
+    match routes {
+        Some(routes) if routes.len() != components_len => Err(Error::ComponentsLengthMismatch),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the swap route at `index` of `routes`,
+/// or a direct swap from `token_in` to `token_out` on the default exchange
+/// Returns error if the path does not start with `token_in` and end with `token_out`
+pub fn swap_route(
+    e: &Env,
+    routes: &Option<Vec<SwapRoute>>,
+    index: u32,
+    token_in: &Address,
+    token_out: &Address,
+) -> Result<SwapRoute, Error> {
+    let route = match routes {
+        Some(routes) => routes.get(index).ok_or(Error::ComponentsLengthMismatch)?,
+        None => {
+            return Ok(SwapRoute {
+                exchange_id: require_exchange_router(e),
+                path: vec![e, token_in.clone(), token_out.clone()],
+                parts: 1,
+            })
+        }
+    };
+    if route.path.len() < 2
+        || route.path.first().as_ref() != Some(token_in)
+        || route.path.last().as_ref() != Some(token_out)
+    {
+        return Err(Error::InvalidPath);
+    }
+    if route.parts == 0 || route.parts > MAX_SPLIT_PARTS {
+        return Err(Error::InvalidSplit);
+    }
+    Ok(route)
+}
+
+/// Returns the legs buying `amount_out` of the last token of `route`, one leg on the exchange of the route,
+/// or the route split into its parts across the registered exchanges
+pub fn quote_route_in(e: &Env, route: &SwapRoute, amount_out: i128) -> Result<Vec<SwapLeg>, Error> {
+    if route.parts == 1 {
+        let amounts_in = exchange::get_amounts_in(e, &route.exchange_id, amount_out, &route.path);
+        return match amounts_in.first() {
+            Some(amount_in) => Ok(vec![
+                e,
+                SwapLeg {
+                    exchange_id: route.exchange_id.clone(),
+                    amount_in,
+                    amount_out,
+                },
+            ]),
+            None => Err(Error::AmountsInError),
+        };
+    }
+    let venues = exchange::get_venues(e);
+    let legs = split_quote_in(e, &venues, amount_out, &route.path, route.parts);
+    if legs.is_empty() {
+        return Err(Error::NoQuote);
+    }
+    Ok(legs)
+}
+
+/// Returns the legs selling `amount_in` of the first token of `route`,
+/// split into the parts of the route across the registered exchanges
+pub fn split_route_out(e: &Env, route: &SwapRoute, amount_in: i128) -> Result<Vec<SwapLeg>, Error> {
+    let venues = exchange::get_venues(e);
+    let legs = split_quote_out(e, &venues, amount_in, &route.path, route.parts);
+    if legs.is_empty() {
+        return Err(Error::NoQuote);
+    }
+    Ok(legs)
+}
+
+/// Returns the route of `leg`, swapping on its exchange only
+pub fn leg_route(route: &SwapRoute, leg: &SwapLeg) -> SwapRoute {
+    SwapRoute {
+        exchange_id: leg.exchange_id.clone(),
+        path: route.path.clone(),
+        parts: 1,
+    }
+}
+
+/// Returns the total amount of `token_in_id` required to mint `amount_constellation` constellation tokens
+/// and the amount required for each component
+///
+/// # Arguments
+/// - `e` - The runtime environment.
+/// - `token_in_id` - Address of input token
+/// - `amount_constellation` - Amount of constellation tokens to mint
+/// - `components` - Components of the constellation token
+/// - `decimals` - Decimals of the constellation token
+/// - `routes` - Optional swap route from `token_in_id` to each component, direct swaps on the default exchange if not set
+///
+/// A component equal to `token_in_id` requires its own quantity, without a swap
 pub fn get_required_amount_token_in(
     e: &Env,
     token_in_id: &Address,
     amount_constellation: i128,
     components: &Vec<Component>,
+    decimals: u32,
+    routes: &Option<Vec<SwapRoute>>,
 ) -> Result<(i128, Vec<i128>), Error> {
-    let router_id = require_exchange_router(&e);
+    check_routes(routes, components.len())?;
     let mut total_token_in_amount = 0;
     let mut token_amounts_in: Vec<i128> = vec![e]; // amounts of each component token required
 
-    for Component { unit, address } in components.iter() {
-        let component_in_amount = amount_constellation * unit;
-        let path = vec![
-            e,
-            token_in_id.clone(),
-            address.clone(), /* component token address */
-        ];
-
-        let amounts_in = router_get_amounts_in(e, component_in_amount, &router_id, &path);
+    for (i, Component { unit, address }) in components.iter().enumerate() {
+        // rounded up, as the constellation token locks when minting
+        let component_in_amount =
+            component_quantity(amount_constellation, unit, decimals, Rounding::Up)?;
+        // the input token is used as is for its own component
+        if address == *token_in_id {
+            total_token_in_amount += component_in_amount;
+            token_amounts_in.push_back(component_in_amount);
+            continue;
+        }
+        let route = swap_route(e, routes, i as u32, token_in_id, &address)?;
 
-        match amounts_in.get(0) {
-            Some(amount) => {
-                total_token_in_amount += amount;
-                token_amounts_in.push_back(amount);
-            }
-            None => {
-                return Err(Error::AmountsInError);
-            }
+        let mut amount = 0;
+        for leg in quote_route_in(e, &route, component_in_amount)?.iter() {
+            amount += leg.amount_in;
         }
+        total_token_in_amount += amount;
+        token_amounts_in.push_back(amount);
     }
 
     Ok((total_token_in_amount, token_amounts_in))
 }
 
-pub fn swap_exact_tokens_for_tokens(
+/// Returns the largest amount of constellation tokens whose components cost at most `amount_in` of `token_in_id`,
+/// with the total amount of `token_in_id` it requires and the amount required for each component
+///
+/// Estimates the amount from the price of one whole constellation token, then binary searches between
+/// the largest affordable and the smallest unaffordable amount until they are adjacent.
+/// Swap costs grow faster than the amount bought, so the estimate from the price of one token is
+/// usually unaffordable, otherwise the upper bound is doubled until it is
+///
+/// # Arguments
+/// - `e` - The runtime environment.
+/// - `token_in_id` - Address of input token
+/// - `amount_in` - Amount of input token to spend
+/// - `components` - Components of the constellation token
+/// - `decimals` - Decimals of the constellation token
+/// - `routes` - Optional swap route from `token_in_id` to each component, direct swaps on the default exchange if not set
+pub fn get_mint_amount_out(
     e: &Env,
-    router_id: &Address,
-    token_out: &Address,
+    token_in_id: &Address,
+    amount_in: i128,
     components: &Vec<Component>,
-    to: &Address,
-    deadline: u64,
-) {
-    for c in components.iter() {
-        let token_client = token::Client::new(&e, &c.address);
-        let amount_in = token_client.balance(&e.current_contract_address());
-        token_client.approve(&e.current_contract_address(), router_id, &amount_in,  
-        
-        &(e.ledger().sequence() + 1000u32));
-        let pair = router_pair_for(e, &router_id, &c.address.clone(), &token_out.clone());
-
-        let results = router_get_amounts_out(
-            e,
-            amount_in,
-            router_id,
-            &vec![e, c.address.clone(), token_out.clone()],
-        );
+    decimals: u32,
+    routes: &Option<Vec<SwapRoute>>,
+) -> Result<(i128, i128, Vec<i128>), Error> {
+    let scale = math::unit_scale(decimals).ok_or(Error::ValueTooLargeOverFlow)?;
+    let (cost, _) =
+        get_required_amount_token_in(e, token_in_id, scale, components, decimals, routes)?;
+    if cost <= 0 {
+        return Err(Error::AmountsInError);
+    }
+    let estimate = math::mul_div(scale, amount_in, cost, Rounding::Down)
+        .ok_or(Error::ValueTooLargeOverFlow)?;
 
-        let amount_out = results.get(1).unwrap();
+    let mut best: (i128, i128, Vec<i128>) = (0, 0, vec![e]);
+    let mut upper = estimate
+        .checked_add(1)
+        .ok_or(Error::ValueTooLargeOverFlow)?;
+    loop {
+        let (cost, amounts_in) =
+            get_required_amount_token_in(e, token_in_id, upper, components, decimals, routes)?;
+        if cost > amount_in {
+            break;
+        }
+        best = (upper, cost, amounts_in);
+        upper = upper.checked_mul(2).ok_or(Error::ValueTooLargeOverFlow)?;
+    }
 
-        let (function, args) = get_swap_exact_tokens_for_tokens_call_data(
+    while upper - best.0 > 1 {
+        let mint_amount = best.0 + (upper - best.0) / 2;
+        let (cost, amounts_in) = get_required_amount_token_in(
             e,
-            c.address.clone(),
-            token_out.clone(),
-            amount_in,
-            amount_out,
-            to.clone(),
-            deadline,
-        );
-
-        let auth_entries = create_sub_auth(
-            e,
-            amount_in,
-            c.address.clone(),
-            token_out.clone(),
-            to.clone(),
-            pair.clone(),
-        );
-
-        e.authorize_as_current_contract(auth_entries);
-        e.invoke_contract(router_id, &function, args)
+            token_in_id,
+            mint_amount,
+            components,
+            decimals,
+            routes,
+        )?;
+        if cost <= amount_in {
+            best = (mint_amount, cost, amounts_in);
+        } else {
+            upper = mint_amount;
+        }
     }
+    Ok(best)
 }
 
+/// Swaps `token_in` held by the router for the component quantities of `mint_amount` constellation tokens,
+/// and approves the constellation token to lock them
+/// Returns the amount of `token_in` spent
+#[allow(clippy::too_many_arguments)]
 pub fn swap_tokens_for_exact_tokens(
     e: &Env,
     mint_amount: &i128,
     token_in: &Address,
     to: &Address,
-    router_id: &Address, // soroswap router
     token_amounts_in: &Vec<i128>,
     components: &Vec<Component>,
+    decimals: u32,
+    routes: &Option<Vec<SwapRoute>>,
     constellation_token_id: &Address,
     deadline: u64,
 ) -> Result<i128, Error> {
+    check_routes(routes, components.len())?;
     let mut total_spent = 0;
     for (i, c) in components.iter().enumerate() {
-        let pair = router_pair_for(e, &router_id, &token_in.clone(), &c.address.clone());
-        let token_client = token::Client::new(&e, &c.address);
+        let token_client = token::Client::new(e, &c.address);
 
-        let amount_out = c.unit * mint_amount;
+        let amount_out = component_quantity(*mint_amount, c.unit, decimals, Rounding::Up)?;
+        // the input token is locked as is for its own component
+        if c.address == *token_in {
+            token_client.approve(
+                to,
+                constellation_token_id,
+                &amount_out,
+                &(e.ledger().sequence() + 1000u32),
+            );
+            total_spent += amount_out;
+            continue;
+        }
+        let route = swap_route(e, routes, i as u32, token_in, &c.address)?;
         match token_amounts_in.get(i as u32) {
             Some(amount_in) => {
-                let (function, args) = get_swap_tokens_for_exact_tokens_call_data(
-                    e,
-                    token_in.clone(),
-                    c.address.clone(),
-                    amount_in,
-                    amount_out,
-                    to.clone(),
-                    deadline,
-                );
-                let auth_entries = create_sub_auth(
-                    e,
-                    amount_in,
-                    token_in.clone(),
-                    c.address.clone(),
-                    to.clone(),
-                    pair.clone(),
-                );
-
-                e.authorize_as_current_contract(auth_entries);
-
-                let result: Vec<i128> = e.invoke_contract::<Vec<i128>>(router_id, &function, args);
-
-                let amount_in_spent: i128 = match result.get(0) {
-                    Some(value) => value,
-                    None => return Err(Error::SwapError),
+                let amount_in_spent = if route.parts == 1 {
+                    exchange::swap_exact_out(e, &route, amount_out, amount_in, deadline)
+                } else {
+                    let mut amount_in_spent = 0;
+                    for leg in quote_route_in(e, &route, amount_out)?.iter() {
+                        amount_in_spent += exchange::swap_exact_out(
+                            e,
+                            &leg_route(&route, &leg),
+                            leg.amount_out,
+                            leg.amount_in,
+                            deadline,
+                        );
+                    }
+                    if amount_in_spent > amount_in {
+                        return Err(Error::InsufficientInputAmount);
+                    }
+                    amount_in_spent
                 };
 
                 // approve the constellation token to transfer the routers token
-                token_client.approve(&to, &constellation_token_id, &amount_out,  &(e.ledger().sequence() + 1000u32));
+                token_client.approve(
+                    to,
+                    constellation_token_id,
+                    &amount_out,
+                    &(e.ledger().sequence() + 1000u32),
+                );
 
                 total_spent += amount_in_spent;
             }
@@ -151,6 +354,57 @@ pub fn swap_tokens_for_exact_tokens(
     Ok(total_spent)
 }
 
+/// Returns for each component the registered exchange with the best direct swap
+/// between `token` and the component quantity of `amount` constellation tokens
+///
+/// # Arguments
+/// - `e` - The runtime environment.
+/// - `token` - Token swapped into the components when minting, or out of them when redeeming
+/// - `amount` - Amount of constellation tokens minted or redeemed
+/// - `components` - Components of the constellation token
+/// - `decimals` - Decimals of the constellation token
+/// - `mint` - Whether the routes buy the components, quoted by the least `token` spent,
+/// or sell them, quoted by the most `token` received
+///
+/// Components equal to `token` are not swapped, their route is a direct path on the default exchange
+/// Returns error if no exchange quotes a component
+pub fn get_best_routes(
+    e: &Env,
+    token: &Address,
+    amount: i128,
+    components: &Vec<Component>,
+    decimals: u32,
+    mint: bool,
+) -> Result<Vec<SwapRoute>, Error> {
+    let venues = exchange::get_venues(e);
+    let mut routes: Vec<SwapRoute> = vec![e];
+    for c in components.iter() {
+        if c.address == *token {
+            routes.push_back(swap_route(e, &None, 0, token, &c.address)?);
+            continue;
+        }
+        let leg = if mint {
+            let path = vec![e, token.clone(), c.address.clone()];
+            let amount_out = component_quantity(amount, c.unit, decimals, Rounding::Up)?;
+            best_quote_in(e, &venues, amount_out, &path).map(|leg| (leg, path))
+        } else {
+            let path = vec![e, c.address.clone(), token.clone()];
+            let amount_in = component_quantity(amount, c.unit, decimals, Rounding::Down)?;
+            best_quote_out(e, &venues, amount_in, &path).map(|leg| (leg, path))
+        };
+        let (exchange_id, path) = match leg {
+            Some((leg, path)) => (leg.exchange_id, path),
+            None => return Err(Error::NoQuote),
+        };
+        routes.push_back(SwapRoute {
+            exchange_id,
+            path,
+            parts: 1,
+        });
+    }
+    Ok(routes)
+}
+
 pub fn refund_unspent(e: &Env, refund: i128, token_in: &Address, to: &Address, deadline: u64) {
     token::Client::new(&e, &token_in).transfer(&e.current_contract_address(), &to, &refund);
 }
@@ -1,13 +1,14 @@
 #![no_std]
-mod auth;
 pub mod contract;
 mod error;
 mod event;
+mod exchange;
 mod factory;
 mod helper;
+mod registry;
 mod require;
-mod soroswap_router;
 mod storage;
 #[cfg(test)]
 mod test;
 mod token;
+pub mod types;
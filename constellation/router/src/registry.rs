@@ -0,0 +1,25 @@
+use soroban_sdk::{Address, Env, Vec};
+
+pub(crate) mod registry {
+    soroban_sdk::contractimport!(
+        file = "../../target/wasm32-unknown-unknown/release/constellation_registry.wasm"
+    );
+}
+pub use registry::ProtocolFee;
+
+pub(crate) fn get_protocol_fee(e: &Env, registry_id: &Address) -> Option<ProtocolFee> {
+    let client = registry::Client::new(e, registry_id);
+    client.get_protocol_fee()
+}
+
+/// Returns the adapter registered for the router to swap on `exchange_id`
+pub(crate) fn get_adapter_id(e: &Env, registry_id: &Address, exchange_id: &Address) -> Option<Address> {
+    let client = registry::Client::new(e, registry_id);
+    client.get_adapter_id(&e.current_contract_address(), exchange_id)
+}
+
+/// Returns the exchanges with an adapter registered for the router
+pub(crate) fn get_exchanges(e: &Env, registry_id: &Address) -> Vec<Address> {
+    let client = registry::Client::new(e, registry_id);
+    client.get_exchanges(&e.current_contract_address())
+}
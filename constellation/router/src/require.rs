@@ -2,7 +2,9 @@ use soroban_sdk::{panic_with_error, Address, Env};
 
 use crate::{
     error::Error,
-    storage::{read_exchange_router, read_xlm},
+    registry,
+    storage::{read_exchange_router, read_registry, read_xlm},
+    token,
 };
 
 pub fn require_exchange_router(e: &Env) -> Address {
@@ -18,3 +20,24 @@ pub fn require_xlm(e: &Env) -> Address {
         None => panic_with_error!(&e, Error::RequiresXlmID),
     }
 }
+
+pub fn require_registry(e: &Env) -> Address {
+    match read_registry(e) {
+        Some(registry_id) => registry_id,
+        None => panic_with_error!(&e, Error::RequiresRegistry),
+    }
+}
+
+pub fn require_manager(e: &Env, constellation_token_id: &Address) -> Address {
+    match token::get_manager(e, constellation_token_id) {
+        Some(manager) => manager,
+        None => panic_with_error!(&e, Error::RequiresManager),
+    }
+}
+
+pub fn require_adapter(e: &Env, exchange_id: &Address) -> Address {
+    match registry::get_adapter_id(e, &require_registry(e), exchange_id) {
+        Some(adapter_id) => adapter_id,
+        None => panic_with_error!(&e, Error::RequiresExchangeAdapter),
+    }
+}
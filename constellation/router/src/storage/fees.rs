@@ -0,0 +1,36 @@
+use super::{PERSISTENT_LEDGER_LIFE, PERSISTENT_LEDGER_TTL_THRESHOLD};
+use soroban_sdk::{contracttype, Address, Env};
+
+use super::DataKey;
+
+/// Issue and redeem fees of a constellation token, in basis points
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct TokenFees {
+    pub issue_fee_bps: u32,
+    pub redeem_fee_bps: u32,
+}
+
+pub(crate) fn write_fees(e: &Env, constellation_token: &Address, fees: &TokenFees) {
+    let key = DataKey::Fees(constellation_token.clone());
+    e.storage().persistent().set(&key, fees);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LEDGER_TTL_THRESHOLD, PERSISTENT_LEDGER_LIFE);
+}
+
+/// Returns zero fees for tokens without fee settings
+pub(crate) fn read_fees(e: &Env, constellation_token: &Address) -> TokenFees {
+    let key = DataKey::Fees(constellation_token.clone());
+    match e.storage().persistent().get::<_, TokenFees>(&key) {
+        Some(fees) => {
+            e.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LEDGER_TTL_THRESHOLD,
+                PERSISTENT_LEDGER_LIFE,
+            );
+            fees
+        }
+        None => TokenFees::default(),
+    }
+}
@@ -1,11 +1,15 @@
-use soroban_sdk::contracttype;
+use soroban_sdk::{contracttype, Address};
 
 pub(crate) mod exchange_router;
 pub(crate) mod factory;
+pub(crate) mod fees;
+pub(crate) mod registry;
 pub(crate) mod xlm;
 
 pub(crate) use exchange_router::{read_exchange_router, write_exchange_router};
 pub(crate) use factory::{has_factory, read_factory, write_factory};
+pub(crate) use fees::{read_fees, write_fees, TokenFees};
+pub(crate) use registry::{read_registry, write_registry};
 pub(crate) use xlm::{read_xlm, write_xlm};
 
 pub(crate) const DAY_IN_LEDGERS: u32 = 17280;
@@ -15,10 +19,16 @@ pub(crate) const INSTANCE_LEDGER_TTL_THRESHOLD: u32 = INSTANCE_LEDGER_LIFE - DAY
 pub(crate) const PERSISTENT_LEDGER_LIFE: u32 = 90 * DAY_IN_LEDGERS; // ~90 days.
 pub(crate) const PERSISTENT_LEDGER_TTL_THRESHOLD: u32 = PERSISTENT_LEDGER_LIFE - DAY_IN_LEDGERS;
 
+pub(crate) const BPS_DENOMINATOR: i128 = 10_000;
+/// Hard cap on issue and redeem fees, 5%
+pub(crate) const MAX_FEE_BPS: u32 = 500;
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
     Factory,
     ExchangeRouter,
     XLM,
+    Registry,
+    Fees(Address),
 }
@@ -72,10 +72,12 @@ impl<'a> TradeTest<'a> {
         adapter.initialize(&s_router.address, &s_factory.address);
 
         registry.initialize(&admin);
+        registry.add_module(&router.address);
+        registry.add_adapter(&router.address, &s_router.address, &adapter.address);
 
         factory.initialize(&admin, &constellation_token_bytes);
 
-        router.initialize(&factory.address, &s_router.address);
+        router.initialize(&factory.address, &s_router.address, &registry.address);
 
         let amount_0: i128 = 10_000_000_000_0000000;
         let amount_1: i128 = 10_000_000_000_0000000;
@@ -135,4 +137,26 @@ impl<'a> TradeTest<'a> {
             deadline: 10000000u64,
         }
     }
+
+    /// Deploys a second exchange with the same pools for `token_in` and each token of `tokens_out`,
+    /// and registers its adapter for the router
+    /// Returns the address of the exchange
+    pub fn add_exchange(&self, token_in: &Address, tokens_out: Vec<Address>) -> Address {
+        let s_factory = create_soroswap_factory(&self.env);
+        let s_router = create_soroswap_router(&self.env);
+        let adapter = create_adapter(&self.env);
+        s_factory.initialize(&self.admin, &pair_contract_wasm(&self.env));
+        s_router.initialize(&s_factory.address);
+        adapter.initialize(&s_router.address, &s_factory.address);
+        self.registry
+            .add_adapter(&self.router.address, &s_router.address, &adapter.address);
+
+        let amount: i128 = 100_000_000_000_000_000;
+        for token_out in tokens_out.iter() {
+            add_liquidity(
+                &self.env, &s_router, &self.user, token_in, &token_out, &amount, &amount,
+            );
+        }
+        s_router.address
+    }
 }
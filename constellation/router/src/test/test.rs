@@ -15,13 +15,25 @@ use super::setup::TradeTest;
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
-    vec, Address, BytesN, Env, InvokeError, String, Val, Vec,
+    vec, Address, BytesN, Env, InvokeError, Map, String, Symbol, Val, Vec,
 };
 use soroban_sdk::{xdr, IntoVal};
 extern crate std;
-use crate::auth::*;
 use crate::helper::*;
 use crate::token::Component;
+use crate::types::SwapRoute;
+
+/// Returns the components of `ct` as the router's component type
+fn router_components(e: &Env, ct: &ConstellationTokenClient) -> Vec<Component> {
+    let mut components = vec![e];
+    for c in ct.get_components().iter() {
+        components.push_back(Component {
+            address: c.address.clone(),
+            unit: c.unit,
+        })
+    }
+    components
+}
 
 pub(crate) fn initialize_token<'a>(
     e: &Env,
@@ -173,6 +185,7 @@ fn create_token_succeeds() {
     router.initialize(
         &factory.address,
         &soroswap_router.address,
+        &Address::generate(&e),
     );
 
     let result = router.create_token(
@@ -249,6 +262,7 @@ fn test_mint() {
         &test.tokens.0.address,
         &test.user,
         &test.constellation_token.address,
+        &None,
         &deadline,
     );
 
@@ -265,8 +279,8 @@ fn test_redeem_to() {
     let redeem_address = Address::generate(&test.env);
     // test.env.mock_all_auths_allowing_non_root_auth();
 
-    // units
-    let units = vec![&test.env, 1, 1];
+    // units - one whole component per whole constellation token
+    let units = vec![&test.env, 10i128.pow(7), 10i128.pow(7)];
     // components
     let components: Vec<Address> = vec![
         &test.env,
@@ -315,18 +329,13 @@ fn test_redeem_to() {
     );
 
     let mint_amount = 1 * 10i128.pow(7);
-    //
-    let mut comp = vec![&test.env];
-    for c in test.constellation_token.get_components().iter() {
-        comp.push_back(Component {
-            address: c.address.clone(),
-            unit: c.unit.clone(),
-        })
-    }
 
-    let (amount_in, _) =
-        test.router
-            .get_required_amount_token_in(&test.tokens.0.address, &mint_amount, &comp);
+    let (amount_in, _) = test.router.get_required_amount_token_in(
+        &test.tokens.0.address,
+        &mint_amount,
+        &router_components(&test.env, &test.constellation_token),
+        &None,
+    );
 
     // let amount_in = 1000 * 10i128.pow(7)
     let path = &vec![
@@ -353,18 +362,581 @@ fn test_redeem_to() {
         &test.tokens.0.address,
         &test.user,
         &test.constellation_token.address,
+        &None,
         &deadline,
     );
     test.constellation_token
         .approve(&test.user, &test.router.address, &mint_amount, &200);
 
     let initial_balance = test.tokens.0.balance(&test.user);
-    test.router.redeem_into(
+
+    let result = test.router.try_redeem_into(
         &test.user,
         &mint_amount,
         &test.constellation_token.address,
         &test.tokens.0.address,
+        &(2 * mint_amount),
+        &None,
+        &None,
+        &test.deadline,
+    );
+    assert_eq!(result, Err(Ok(Error::InsufficientOutputAmount)));
+
+    let result = test.router.try_redeem_into(
+        &test.user,
+        &mint_amount,
+        &test.constellation_token.address,
+        &test.tokens.0.address,
+        &0,
+        &Some(vec![&test.env, 0, mint_amount]),
+        &None,
+        &test.deadline,
+    );
+    // the exchange rejects a swap below the minimum amount of its component
+    assert!(result.is_err());
+
+    let result = test.router.try_redeem_into(
+        &test.user,
+        &mint_amount,
+        &test.constellation_token.address,
+        &test.tokens.0.address,
+        &0,
+        &Some(vec![&test.env, 0]),
+        &None,
+        &test.deadline,
+    );
+    assert_eq!(result, Err(Ok(Error::ComponentsLengthMismatch)));
+
+    let amount_out = test.router.redeem_into(
+        &test.user,
+        &mint_amount,
+        &test.constellation_token.address,
+        &test.tokens.0.address,
+        &(mint_amount * 19 / 10),
+        &Some(vec![&test.env, mint_amount * 9 / 10, mint_amount * 9 / 10]),
+        &None,
         &test.deadline,
     );
     let final_balance = test.tokens.0.balance(&test.user);
+    assert_eq!(final_balance - initial_balance, amount_out);
+}
+
+#[test]
+fn set_fees_fails_with_fee_exceeds_cap() {
+    let test = TradeTest::setup();
+    let (ct, _, _) = initialize_token(&test.env, test.constellation_token);
+
+    let result = test.router.try_set_fees(&ct.address, &501u32, &0u32);
+    assert_eq!(result, Err(Ok(Error::FeeExceedsCap)));
+}
+
+#[test]
+fn mint_and_burn_take_fees_split_with_treasury() {
+    let test = TradeTest::setup();
+    let treasury = Address::generate(&test.env);
+    let manager = Address::generate(&test.env);
+
+    // units - one whole component per whole constellation token
+    let units = vec![&test.env, 10i128.pow(7), 10i128.pow(7)];
+    let components: Vec<Address> = vec![
+        &test.env,
+        test.tokens.1.address.clone(),
+        test.tokens.2.address.clone(),
+    ];
+    test.constellation_token.initialize(
+        &7u32,
+        &components,
+        &units,
+        &"c_token".into_val(&test.env),
+        &"token_symbol".into_val(&test.env),
+        &test.router.address,
+        &manager,
+    );
+    let ct = &test.constellation_token;
+
+    // 20% of each fee goes to the treasury, 1% issue and redeem fees
+    test.registry.set_protocol_fee(&treasury, &2_000u32);
+    test.router.set_fees(&ct.address, &100u32, &100u32);
+    assert_eq!(test.router.get_fees(&ct.address).issue_fee_bps, 100);
+
+    let approve_amount = 10_000_000 * 10i128.pow(7);
+    test.tokens
+        .0
+        .approve(&test.user, &test.router.address, &approve_amount, &1000u32);
+
+    let mint_amount = 10i128.pow(7);
+    let (amount_in, _) = test.router.get_required_amount_token_in(
+        &test.tokens.0.address,
+        &mint_amount,
+        &router_components(&test.env, ct),
+        &None,
+    );
+    test.router.mint_exact_tokens(
+        &mint_amount,
+        &amount_in,
+        &test.tokens.0.address,
+        &test.user,
+        &ct.address,
+        &None,
+        &test.deadline,
+    );
+
+    assert_eq!(ct.balance(&test.user), 9_900_000);
+    assert_eq!(ct.balance(&manager), 80_000);
+    assert_eq!(ct.balance(&treasury), 20_000);
+    assert_eq!(ct.balance(&test.router.address), 0);
+
+    let component_balance = test.tokens.1.balance(&test.user);
+    ct.approve(&test.user, &test.router.address, &9_900_000, &200);
+    test.router.burn(&test.user, &ct.address, &9_900_000);
+
+    assert_eq!(ct.balance(&test.user), 0);
+    assert_eq!(ct.balance(&manager), 80_000 + 79_200);
+    assert_eq!(ct.balance(&treasury), 20_000 + 19_800);
+    assert_eq!(
+        test.tokens.1.balance(&test.user),
+        component_balance + 9_801_000
+    );
+}
+
+#[test]
+fn mint_and_redeem_into_with_multi_hop_routes() {
+    let test = TradeTest::setup();
+    let (t0, t1, t2, t3) = (
+        test.tokens.0.address.clone(),
+        test.tokens.1.address.clone(),
+        test.tokens.2.address.clone(),
+        test.tokens.3.address.clone(),
+    );
+
+    // there is no pair for token 2 and token 1, it is swapped through token 0
+    let units = vec![&test.env, 10i128.pow(7), 10i128.pow(7)];
+    test.constellation_token.initialize(
+        &7u32,
+        &vec![&test.env, t1.clone(), t3.clone()],
+        &units,
+        &"c_token".into_val(&test.env),
+        &"token_symbol".into_val(&test.env),
+        &test.router.address,
+        &Address::generate(&test.env),
+    );
+    let ct = &test.constellation_token;
+    let approve_amount = 10_000_000 * 10i128.pow(7);
+    test.tokens
+        .2
+        .approve(&test.user, &test.router.address, &approve_amount, &1000u32);
+
+    let mint_amount = 10i128.pow(7);
+    let route = |path: Vec<Address>| SwapRoute {
+        exchange_id: test.s_router.address.clone(),
+        path,
+        parts: 1,
+    };
+    let mint_routes = Some(vec![
+        &test.env,
+        route(vec![&test.env, t2.clone(), t0.clone(), t1.clone()]),
+        route(vec![&test.env, t2.clone(), t3.clone()]),
+    ]);
+
+    let invalid_routes = Some(vec![
+        &test.env,
+        route(vec![&test.env, t2.clone(), t0.clone()]),
+        route(vec![&test.env, t2.clone(), t3.clone()]),
+    ]);
+    let result = test.router.try_get_required_amount_token_in(
+        &t2,
+        &mint_amount,
+        &router_components(&test.env, ct),
+        &invalid_routes,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidPath)));
+
+    let (amount_in, _) = test.router.get_required_amount_token_in(
+        &t2,
+        &mint_amount,
+        &router_components(&test.env, ct),
+        &mint_routes,
+    );
+    test.router.mint_exact_tokens(
+        &mint_amount,
+        &amount_in,
+        &t2,
+        &test.user,
+        &ct.address,
+        &mint_routes,
+        &test.deadline,
+    );
+    assert_eq!(ct.balance(&test.user), mint_amount);
+
+    let redeem_routes = Some(vec![
+        &test.env,
+        route(vec![&test.env, t1.clone(), t0.clone(), t2.clone()]),
+        route(vec![&test.env, t3.clone(), t2.clone()]),
+    ]);
+    ct.approve(&test.user, &test.router.address, &mint_amount, &200);
+    let initial_balance = test.tokens.2.balance(&test.user);
+    let amount_out = test.router.redeem_into(
+        &test.user,
+        &mint_amount,
+        &ct.address,
+        &t2,
+        &0,
+        &None,
+        &redeem_routes,
+        &test.deadline,
+    );
+    assert!(amount_out > 0);
+    assert_eq!(test.tokens.2.balance(&test.user), initial_balance + amount_out);
+    assert_eq!(ct.balance(&test.user), 0);
+}
+
+#[test]
+fn mint_and_redeem_into_pass_through_input_component() {
+    let test = TradeTest::setup();
+    let (t0, t1) = (
+        test.tokens.0.address.clone(),
+        test.tokens.1.address.clone(),
+    );
+
+    // token 0 is both the input token and a component
+    let units = vec![&test.env, 10i128.pow(7), 10i128.pow(7)];
+    test.constellation_token.initialize(
+        &7u32,
+        &vec![&test.env, t0.clone(), t1.clone()],
+        &units,
+        &"c_token".into_val(&test.env),
+        &"token_symbol".into_val(&test.env),
+        &test.router.address,
+        &Address::generate(&test.env),
+    );
+    let ct = &test.constellation_token;
+    let approve_amount = 10_000_000 * 10i128.pow(7);
+    test.tokens
+        .0
+        .approve(&test.user, &test.router.address, &approve_amount, &1000u32);
+
+    let mint_amount = 10i128.pow(7);
+    let (amount_in, amounts_in) = test.router.get_required_amount_token_in(
+        &t0,
+        &mint_amount,
+        &router_components(&test.env, ct),
+        &None,
+    );
+    // the component of token 0 requires its own quantity, without a swap
+    let t0_index = ct
+        .get_components()
+        .iter()
+        .position(|c| c.address == t0)
+        .unwrap() as u32;
+    assert_eq!(amounts_in.get(t0_index).unwrap(), mint_amount);
+
+    let initial_balance = test.tokens.0.balance(&test.user);
+    let refund = test.router.mint_exact_tokens(
+        &mint_amount,
+        &amount_in,
+        &t0,
+        &test.user,
+        &ct.address,
+        &None,
+        &test.deadline,
+    );
+    assert_eq!(ct.balance(&test.user), mint_amount);
+    assert_eq!(
+        test.tokens.0.balance(&test.user),
+        initial_balance - amount_in + refund
+    );
+    assert_eq!(test.tokens.0.balance(&ct.address), mint_amount);
+
+    ct.approve(&test.user, &test.router.address, &mint_amount, &200);
+    let initial_balance = test.tokens.0.balance(&test.user);
+    let amount_out = test.router.redeem_into(
+        &test.user,
+        &mint_amount,
+        &ct.address,
+        &t0,
+        &0,
+        &Some(if t0_index == 0 {
+            vec![&test.env, mint_amount, 0]
+        } else {
+            vec![&test.env, 0, mint_amount]
+        }),
+        &None,
+        &test.deadline,
+    );
+    assert!(amount_out > mint_amount);
+    assert_eq!(test.tokens.0.balance(&test.user), initial_balance + amount_out);
+}
+
+#[test]
+fn mint_with_exact_input_spends_at_most_amount_in() {
+    let test = TradeTest::setup();
+    let (t0, t1, t2) = (
+        test.tokens.0.address.clone(),
+        test.tokens.1.address.clone(),
+        test.tokens.2.address.clone(),
+    );
+
+    let units = vec![&test.env, 10i128.pow(7), 10i128.pow(7)];
+    test.constellation_token.initialize(
+        &7u32,
+        &vec![&test.env, t1.clone(), t2.clone()],
+        &units,
+        &"c_token".into_val(&test.env),
+        &"token_symbol".into_val(&test.env),
+        &test.router.address,
+        &Address::generate(&test.env),
+    );
+    let ct = &test.constellation_token;
+    let approve_amount = 10_000_000 * 10i128.pow(7);
+    test.tokens
+        .0
+        .approve(&test.user, &test.router.address, &approve_amount, &1000u32);
+
+    // about two and a half constellation tokens
+    let amount_in = 5 * 10i128.pow(7);
+    let (mint_amount, cost) =
+        test.router
+            .get_mint_amount_out(&t0, &amount_in, &ct.address, &None);
+    assert!(mint_amount > 0);
+    assert!(cost <= amount_in);
+    // one more base unit is no longer affordable
+    let (larger_cost, _) = test.router.get_required_amount_token_in(
+        &t0,
+        &(mint_amount + 1),
+        &router_components(&test.env, ct),
+        &None,
+    );
+    assert!(larger_cost > amount_in);
+
+    let result = test.router.try_mint_with_exact_input(
+        &test.user,
+        &t0,
+        &amount_in,
+        &(mint_amount + 1),
+        &ct.address,
+        &None,
+        &test.deadline,
+    );
+    assert_eq!(result, Err(Ok(Error::InsufficientOutputAmount)));
+
+    let initial_balance = test.tokens.0.balance(&test.user);
+    let minted = test.router.mint_with_exact_input(
+        &test.user,
+        &t0,
+        &amount_in,
+        &mint_amount,
+        &ct.address,
+        &None,
+        &test.deadline,
+    );
+    assert_eq!(minted, mint_amount);
+    assert_eq!(ct.balance(&test.user), mint_amount);
+    assert_eq!(test.tokens.0.balance(&test.user), initial_balance - cost);
+}
+
+#[test]
+fn mint_and_redeem_into_with_best_routes() {
+    let test = TradeTest::setup();
+    let (t0, t1, t2, t3) = (
+        test.tokens.0.address.clone(),
+        test.tokens.1.address.clone(),
+        test.tokens.2.address.clone(),
+        test.tokens.3.address.clone(),
+    );
+
+    let units = vec![&test.env, 10i128.pow(7), 10i128.pow(7)];
+    test.constellation_token.initialize(
+        &7u32,
+        &vec![&test.env, t2.clone(), t3.clone()],
+        &units,
+        &"c_token".into_val(&test.env),
+        &"token_symbol".into_val(&test.env),
+        &test.router.address,
+        &Address::generate(&test.env),
+    );
+    let ct = &test.constellation_token;
+    let approve_amount = 10_000_000 * 10i128.pow(7);
+    test.tokens
+        .0
+        .approve(&test.user, &test.router.address, &approve_amount, &1000u32);
+
+    // there is no pair for token 1 and token 3
+    let mint_amount = 10i128.pow(7);
+    let result = test
+        .router
+        .try_get_best_mint_routes(&t1, &mint_amount, &ct.address);
+    assert_eq!(result, Err(Ok(Error::NoQuote)));
+
+    let mint_routes = test
+        .router
+        .get_best_mint_routes(&t0, &mint_amount, &ct.address);
+    for (route, c) in mint_routes.iter().zip(ct.get_components().iter()) {
+        assert_eq!(route.exchange_id, test.s_router.address);
+        assert_eq!(route.path, vec![&test.env, t0.clone(), c.address]);
+    }
+
+    let (amount_in, _) = test.router.get_required_amount_token_in(
+        &t0,
+        &mint_amount,
+        &router_components(&test.env, ct),
+        &Some(mint_routes.clone()),
+    );
+    test.router.mint_exact_tokens(
+        &mint_amount,
+        &amount_in,
+        &t0,
+        &test.user,
+        &ct.address,
+        &Some(mint_routes),
+        &test.deadline,
+    );
+    assert_eq!(ct.balance(&test.user), mint_amount);
+
+    let redeem_routes = test
+        .router
+        .get_best_redeem_routes(&mint_amount, &ct.address, &t0);
+    ct.approve(&test.user, &test.router.address, &mint_amount, &200);
+    test.router.redeem_into(
+        &test.user,
+        &mint_amount,
+        &ct.address,
+        &t0,
+        &0,
+        &None,
+        &Some(redeem_routes),
+        &test.deadline,
+    );
+
+    // each swap of the mint and the redeem reports the exchange it was made on
+    use soroban_sdk::testutils::Events as _;
+    let swap_events = test
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract_id, topics, _)| {
+            *contract_id == test.router.address
+                && topics.get(0).map(|topic| topic.shallow_eq(&symbol_short!("swap").to_val()))
+                    == Some(true)
+        })
+        .count();
+    assert_eq!(swap_events, 4);
+}
+
+#[test]
+fn mint_and_redeem_into_split_across_exchanges() {
+    let test = TradeTest::setup();
+    let (t0, t2, t3) = (
+        test.tokens.0.address.clone(),
+        test.tokens.2.address.clone(),
+        test.tokens.3.address.clone(),
+    );
+    let exchange = test.add_exchange(&t0, vec![&test.env, t2.clone(), t3.clone()]);
+
+    let units = vec![&test.env, 10i128.pow(7), 10i128.pow(7)];
+    test.constellation_token.initialize(
+        &7u32,
+        &vec![&test.env, t2.clone(), t3.clone()],
+        &units,
+        &"c_token".into_val(&test.env),
+        &"token_symbol".into_val(&test.env),
+        &test.router.address,
+        &Address::generate(&test.env),
+    );
+    let ct = &test.constellation_token;
+    let approve_amount = 10_000_000_000 * 10i128.pow(7);
+    test.tokens
+        .0
+        .approve(&test.user, &test.router.address, &approve_amount, &1000u32);
+
+    // a mint large enough to move the price of a single pool
+    let mint_amount = 100_000_000 * 10i128.pow(7);
+    let routes = |parts: u32, to_components: bool| {
+        let mut routes = vec![&test.env];
+        for c in [&t2, &t3] {
+            let path = if to_components {
+                vec![&test.env, t0.clone(), c.clone()]
+            } else {
+                vec![&test.env, c.clone(), t0.clone()]
+            };
+            routes.push_back(SwapRoute {
+                exchange_id: test.s_router.address.clone(),
+                path,
+                parts,
+            });
+        }
+        Some(routes)
+    };
+
+    let result = test.router.try_get_required_amount_token_in(
+        &t0,
+        &mint_amount,
+        &router_components(&test.env, ct),
+        &routes(0, true),
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidSplit)));
+
+    let (single_amount_in, _) = test.router.get_required_amount_token_in(
+        &t0,
+        &mint_amount,
+        &router_components(&test.env, ct),
+        &routes(1, true),
+    );
+    let (amount_in, _) = test.router.get_required_amount_token_in(
+        &t0,
+        &mint_amount,
+        &router_components(&test.env, ct),
+        &routes(4, true),
+    );
+    assert!(amount_in < single_amount_in);
+
+    test.router.mint_exact_tokens(
+        &mint_amount,
+        &amount_in,
+        &t0,
+        &test.user,
+        &ct.address,
+        &routes(4, true),
+        &test.deadline,
+    );
+    assert_eq!(ct.balance(&test.user), mint_amount);
+
+    ct.approve(&test.user, &test.router.address, &mint_amount, &200);
+    let amount_out = test.router.redeem_into(
+        &test.user,
+        &mint_amount,
+        &ct.address,
+        &t0,
+        &0,
+        &None,
+        &routes(4, false),
+        &test.deadline,
+    );
+    assert!(amount_out > 0);
+
+    // each component is swapped on both exchanges when minting and redeeming
+    use soroban_sdk::testutils::Events as _;
+    let mut swaps_on_exchange = 0;
+    let mut swaps = 0;
+    for (contract_id, topics, data) in test.env.events().all().iter() {
+        if contract_id != test.router.address
+            || topics
+                .get(0)
+                .map(|topic| topic.shallow_eq(&symbol_short!("swap").to_val()))
+                != Some(true)
+        {
+            continue;
+        }
+        swaps += 1;
+        let swap: Map<Symbol, Val> = data.into_val(&test.env);
+        let exchange_id: Address = swap
+            .get(Symbol::new(&test.env, "exchange_id"))
+            .unwrap()
+            .into_val(&test.env);
+        if exchange_id == exchange {
+            swaps_on_exchange += 1;
+        }
+    }
+    assert_eq!(swaps, 8);
+    assert_eq!(swaps_on_exchange, 4);
 }
@@ -14,23 +14,50 @@ pub(crate) fn mint(e: &Env, to: &Address, amount: i128, constellation_token_addr
     ctoken.mint(to, &amount);
 }
 
-pub(crate) fn redeem(
+/// Moves `amount` constellation tokens from `from` to the router, `from` must approve the router
+pub(crate) fn collect(
     e: &Env,
     from: &Address,
-    to: &Address,
     amount: i128,
     constellation_token_address: &Address,
 ) {
-    let ctoken = constellation_token::Client::new(&e, constellation_token_address);
-    ctoken.burn_from(&e.current_contract_address(), from, &amount);
+    let ctoken = constellation_token::Client::new(e, constellation_token_address);
+    ctoken.transfer_from(
+        &e.current_contract_address(),
+        from,
+        &e.current_contract_address(),
+        &amount,
+    );
+}
+
+/// Burns `amount` constellation tokens held by the router and releases the components to `to`
+pub(crate) fn redeem(e: &Env, to: &Address, amount: i128, constellation_token_address: &Address) {
+    let ctoken = constellation_token::Client::new(e, constellation_token_address);
+    ctoken.burn(&e.current_contract_address(), &amount);
     ctoken.redeem(to, &amount);
 }
 
+/// Transfers constellation tokens held by the router
+pub(crate) fn transfer(e: &Env, to: &Address, amount: i128, constellation_token_address: &Address) {
+    let ctoken = constellation_token::Client::new(e, constellation_token_address);
+    ctoken.transfer(&e.current_contract_address(), to, &amount);
+}
+
+pub(crate) fn get_manager(e: &Env, constellation_token_address: &Address) -> Option<Address> {
+    let ctoken = constellation_token::Client::new(e, constellation_token_address);
+    ctoken.get_manager()
+}
+
 pub(crate) fn get_components(e: &Env, constellation_token_address: &Address) -> Vec<Component> {
     let ctoken = constellation_token::Client::new(&e, constellation_token_address);
     ctoken.get_components()
 }
 
+pub(crate) fn decimals(e: &Env, constellation_token_address: &Address) -> u32 {
+    let ctoken = constellation_token::Client::new(e, constellation_token_address);
+    ctoken.decimals()
+}
+
 pub(crate) fn invoke(
     e: &Env,
     constellation_token_id: &Address,
@@ -0,0 +1,14 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+/// Exchange and token path used to swap into or out of one component
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SwapRoute {
+    /// Exchange with an adapter registered for the router in the registry
+    pub exchange_id: Address,
+    /// Tokens swapped through, from the input token to the output token
+    pub path: Vec<Address>,
+    /// Number of parts the swap is split into across the registered exchanges quoting `path`,
+    /// 1 swaps on `exchange_id` only
+    pub parts: u32,
+}
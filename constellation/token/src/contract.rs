@@ -1,36 +1,50 @@
 use super::event;
 use super::helpers::{
-    calculate_airdropped_amount, calculate_position, decrease_supply, increase_supply, lock, redeem,
+    calculate_airdropped_amount, calculate_position, component_balances, component_values,
+    component_weights, decrease_supply, dilute_units, increase_supply, lock, net_asset_value,
+    reconcile_snapshot, redeem, spend_cap_balances,
 };
 use crate::admin::read_administrator;
 use crate::admin::{has_administrator, write_administrator};
 use crate::allowance::*;
 use crate::balance::*;
 use crate::component::{
-    read_component, read_components_list, remove_component, write_component, write_components,
+    read_allowed_components, read_component, read_components_list, remove_component,
+    write_allowed_components, write_component, write_components,
 };
 use crate::error::Error;
 use crate::error::{check_nonnegative_amount, check_zero_or_negative_amount};
 use crate::helpers::update_position;
 use crate::manager::{read_manager, write_manager};
 use crate::metadata::*;
-use crate::module::{is_registered, read_module, remove_module, write_module};
+use crate::module::{
+    is_registered, read_last_fee_mint, read_module, read_module_scope, read_module_spending,
+    read_modules, remove_last_fee_mint, remove_module, remove_module_spending, write_last_fee_mint,
+    write_module, write_module_scope, write_module_spending,
+};
 use crate::require::{
-    assert_registered_module, assert_token_registered_module, require_administrator,
-    require_manager, require_registry,
+    assert_registered_module, require_administrator, require_backed_components, require_manager,
+    require_max_components, require_module, require_oracle, require_registry,
+    require_scoped_approve, require_scoped_auth, require_scoped_call, require_spend_caps,
 };
 use crate::storage::keys::{AllowanceDataKey, DataKey};
+use crate::storage::oracle::write_oracle;
 use crate::storage::registry::write_registry;
+use crate::storage::snapshot::{read_snapshot, remove_snapshot, write_snapshot};
 use crate::storage::total_supply::read_total_supply;
 use crate::storage::types::{
-    AllowanceValue, Component, INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD,
+    AllowanceValue, BalanceSnapshot, Component, ModuleScope, ModuleState, INSTANCE_BUMP_AMOUNT,
+    INSTANCE_LIFETIME_THRESHOLD, MAX_MINT_FEE_BPS,
 };
 use crate::traits::{ConstellationTokenInterface, Module};
+use constellation_lib::math::{accrued_fee, unit_scale};
+use constellation_lib::traits::module as module_hooks;
+use constellation_lib::types::oracle::OracleConfig;
 use soroban_sdk::auth::InvokerContractAuthEntry;
 use soroban_sdk::token::TokenClient;
 use soroban_sdk::{
     contract, contractimpl, contracttype, log, panic_with_error, symbol_short, token,
-    token::Interface, Address, Env, IntoVal, String, Symbol, Val, Vec,
+    token::Interface, Address, Env, IntoVal, Map, String, Symbol, Val, Vec,
 };
 
 use soroban_token_sdk::{metadata::TokenMetadata, TokenUtils};
@@ -82,7 +96,7 @@ impl ConstellationTokenInterface for ConstellationToken {
     /// - `e` Runtime environment.
     /// - `decimal` Token decimal
     /// - `components` Component tokens of this token
-    /// - `units` Amounts of each componet token required to mint constellation token
+    /// - `units` Amounts of each componet token backing one whole constellation token (10^decimal base units)
     /// - `name` Name of token
     /// - `symbol` Symbol of token
     /// - `admin` Token administrator
@@ -101,7 +115,7 @@ impl ConstellationTokenInterface for ConstellationToken {
             panic_with_error!(&e, Error::AlreadyInitalized);
         }
 
-        if decimal > u8::MAX.into() {
+        if decimal > u8::MAX.into() || unit_scale(decimal).is_none() {
             panic_with_error!(&e, Error::ValueTooLargeOverFlow);
         }
 
@@ -129,6 +143,7 @@ impl ConstellationTokenInterface for ConstellationToken {
     /// - `amount` Amount of constellation tokens to mint
     ///
     /// `to` Address should have balance of component tokens equal to or greater than amount * unit (component unit)
+    /// divided by 10^decimal, rounded up
     fn mint(e: Env, to: Address, amount: i128) -> Result<(), Error> {
         check_zero_or_negative_amount(&e, amount);
         let admin = require_administrator(&e)?;
@@ -167,6 +182,33 @@ impl ConstellationTokenInterface for ConstellationToken {
         Ok(())
     }
 
+    /// Burns constellation tokens held by `from` and releases the underlying
+    /// component tokens to `to`
+    /// Does not require the administrator, so holders can always exit the fund
+    ///
+    /// # Arguments
+    /// - `e` Runtime environment
+    /// - `from` Holder of the constellation tokens to redeem
+    /// - `to` Address receiving the component tokens
+    /// - `amount` Amount of constellation tokens to redeem
+    ///
+    /// `to` receives amount * unit / 10^decimal of each component token, rounded down
+    fn redeem_in_kind(e: Env, from: Address, to: Address, amount: i128) -> Result<(), Error> {
+        from.require_auth();
+        check_zero_or_negative_amount(&e, amount);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        spend_balance(&e, from.clone(), amount);
+        redeem(&e, &to, amount);
+        decrease_supply(&e, amount);
+
+        TokenUtils::new(&e).events().burn(from.clone(), amount);
+        event::redeem_in_kind(&e, from, to, amount);
+        Ok(())
+    }
+
     fn set_manager(e: Env, new_manager: Address) -> Result<(), Error> {
         let manager = require_manager(&e)?;
         manager.require_auth();
@@ -193,6 +235,50 @@ impl ConstellationTokenInterface for ConstellationToken {
         Ok(())
     }
 
+    /// Sets the price feed this token is valued with, overriding the protocol oracle of the registry
+    ///
+    /// # Arguments
+    /// - `e` Runtime environment
+    /// - `oracle_id` SEP-40 price feed quoting every component
+    /// - `max_price_age` Maximum age of a price, in seconds
+    fn set_oracle(e: Env, oracle_id: Address, max_price_age: u64) -> Result<(), Error> {
+        let manager = require_manager(&e)?;
+        manager.require_auth();
+        if max_price_age == 0 {
+            return Err(Error::InvalidPriceAge);
+        }
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        write_oracle(
+            &e,
+            &OracleConfig {
+                oracle_id: oracle_id.clone(),
+                max_price_age,
+            },
+        );
+        event::set_oracle(&e, oracle_id, max_price_age);
+        Ok(())
+    }
+
+    /// Restricts the tokens modules may add as new components of this token
+    ///
+    /// # Arguments
+    /// - `e` Runtime environment
+    /// - `components` Tokens `update_units` may add as components, any token if empty
+    fn set_allowed_components(e: Env, components: Vec<Address>) -> Result<(), Error> {
+        let manager = require_manager(&e)?;
+        manager.require_auth();
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        write_allowed_components(&e, &components);
+        event::set_allowed_components(&e, components);
+        Ok(())
+    }
+
     //////////////////////////////////////////////////////////////////
     ///////// Read Only functions ////////////////////////////////////
     //////////////////////////////////////////////////////////////////
@@ -208,6 +294,37 @@ impl ConstellationTokenInterface for ConstellationToken {
     fn get_manager(e: Env) -> Option<Address> {
         read_manager(&e)
     }
+
+    fn total_supply(e: Env) -> i128 {
+        read_total_supply(&e)
+    }
+
+    /// Returns the oracle set on this token, or the protocol oracle of the registry
+    fn get_oracle(e: Env) -> Option<OracleConfig> {
+        require_oracle(&e).ok()
+    }
+
+    /// Returns the tokens modules may add as new components, any token if empty
+    fn get_allowed_components(e: Env) -> Vec<Address> {
+        read_allowed_components(&e)
+    }
+
+    /// Returns the net asset value of the total supply, in the base asset of the oracle
+    /// Returns error if no oracle is set or a component price is missing or stale
+    fn get_nav(e: Env) -> Result<i128, Error> {
+        net_asset_value(&component_values(&e)?)
+    }
+
+    /// Returns the value of each component backing the total supply, in the base asset of the oracle
+    fn get_component_values(e: Env) -> Result<Map<Address, i128>, Error> {
+        component_values(&e)
+    }
+
+    /// Returns the share of the net asset value held in each component, in basis points
+    /// Returns error if the total supply is zero
+    fn get_weights(e: Env) -> Result<Map<Address, u32>, Error> {
+        component_weights(&e, &component_values(&e)?)
+    }
 }
 
 #[contractimpl]
@@ -316,28 +433,193 @@ impl token::Interface for ConstellationToken {
 
 #[contractimpl]
 impl Module for ConstellationToken {
-    fn update_units(
-        e: Env,
-        token_in: (Address, i128),
-        token_out: (Address, i128),
-    ) -> Result<(), Error> {
-        let token_in_unit = update_position(&e, token_in);
-        let token_out_unit = update_position(&e, token_out);
-        // TODO: EMIT EVENT
+    /// Records the balances of `tokens` held by the constellation token
+    /// Must be called by the module before it moves the tokens, `invoke` then updates their units
+    /// after each call of the module in the same ledger
+    ///
+    /// # Arguments
+    /// - `e` Runtime environment
+    /// - `module_id` Module starting the operation
+    /// - `tokens` Components (and future components) the operation trades
+    fn snapshot_balances(e: Env, module_id: Address, tokens: Vec<Address>) -> Result<(), Error> {
+        module_id.require_auth();
+        require_module(&e, &module_id)?;
+
+        let mut balances = Map::new(&e);
+        for token_id in tokens.iter() {
+            let balance = TokenClient::new(&e, &token_id).balance(&e.current_contract_address());
+            balances.set(token_id, balance);
+        }
+        write_snapshot(
+            &e,
+            &module_id,
+            &BalanceSnapshot {
+                ledger: e.ledger().sequence(),
+                balances,
+            },
+        );
+        Ok(())
+    }
+
+    /// Updates the units of the tokens in the balance snapshot of `module_id`
+    /// from the difference between the snapshot and the current balances
+    /// Returns error if the module did not snapshot balances in the current ledger,
+    /// or if it added a component the manager does not allow or more than the factory allows
+    ///
+    /// # Arguments
+    /// - `e` Runtime environment
+    /// - `module_id` Module ending the operation
+    fn update_units(e: Env, module_id: Address) -> Result<(), Error> {
+        module_id.require_auth();
+        require_module(&e, &module_id)?;
+
+        let snapshot = match read_snapshot(&e, &module_id) {
+            Some(snapshot) => snapshot,
+            None => return Err(Error::RequiresBalanceSnapshot),
+        };
+        if snapshot.ledger != e.ledger().sequence() {
+            return Err(Error::StaleBalanceSnapshot);
+        }
+        remove_snapshot(&e, &module_id);
+
+        let components_before = read_components_list(&e).len();
+        let mut units = Map::new(&e);
+        for (component_id, previous_balance) in snapshot.balances.iter() {
+            let balance =
+                TokenClient::new(&e, &component_id).balance(&e.current_contract_address());
+            // invoke reconciles the snapshot, recomputing an unchanged balance would only round its unit down
+            let unit = if balance == previous_balance {
+                read_component(&e, component_id.clone()).map_or(0, |component| component.unit)
+            } else {
+                update_position(&e, (component_id.clone(), previous_balance))?
+            };
+            units.set(component_id, unit);
+        }
+        require_max_components(&e, components_before)?;
+        event::update_units(&e, module_id, units);
+        Ok(())
+    }
+
+    /// Mints `amount` constellation tokens to `to` as a fee without locking components
+    /// Every unit is diluted by supply / (supply + amount) so the held components back the new supply
+    /// A module may not mint more than `MAX_MINT_FEE_BPS` per year of the supply since its last mint,
+    /// nor mint at all if its scope does not allow `mint_fee`
+    ///
+    /// # Arguments
+    /// - `e` Runtime environment
+    /// - `module_id` Fee module minting the tokens
+    /// - `to` Fee recipient
+    /// - `amount` Amount of constellation tokens to mint
+    fn mint_fee(e: Env, module_id: Address, to: Address, amount: i128) -> Result<(), Error> {
+        module_id.require_auth();
+        require_module(&e, &module_id)?;
+        if let Some(scope) = read_module_scope(&e, &module_id) {
+            if !scope.mint_fee {
+                return Err(Error::FunctionNotInScope);
+            }
+        }
+        check_zero_or_negative_amount(&e, amount);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let supply = read_total_supply(&e);
+        if supply <= 0 {
+            return Err(Error::ZeroSupply);
+        }
+        let now = e.ledger().timestamp();
+        let elapsed = now.saturating_sub(read_last_fee_mint(&e, &module_id).unwrap_or(now));
+        let cap =
+            accrued_fee(supply, MAX_MINT_FEE_BPS, elapsed).ok_or(Error::ValueTooLargeOverFlow)?;
+        if amount > cap {
+            return Err(Error::MintFeeExceedsCap);
+        }
+        write_last_fee_mint(&e, &module_id, now);
+
+        dilute_units(&e, supply, amount);
+        receive_balance(&e, to.clone(), amount);
+        increase_supply(&e, amount);
+
+        TokenUtils::new(&e)
+            .events()
+            .mint(module_id.clone(), to.clone(), amount);
+        event::mint_fee(&e, module_id, to, amount);
         Ok(())
     }
-    fn add_module(e: Env, module_id: Address) -> Result<(), Error> {
+    /// Adds a module of the registry to this token, pending until the module initializes itself
+    /// for the token with `initialize_module`
+    /// Returns error if the module is already added, it must be removed to change its scope
+    ///
+    /// # Arguments
+    /// - `e` Runtime environment
+    /// - `module_id` Module registered in the registry
+    /// - `scope` Targets, functions and spend caps the module is limited to in `invoke`,
+    ///   and whether it may call `mint_fee`. The module may invoke any call if not set
+    fn add_module(e: Env, module_id: Address, scope: Option<ModuleScope>) -> Result<(), Error> {
         let manager = require_manager(&e)?;
         manager.require_auth();
         let registry = require_registry(&e)?;
-        assert_registered_module(&e, &module_id, &registry);
-        write_module(&e, &module_id);
+        assert_registered_module(&e, &module_id, &registry)?;
+        if is_registered(&e, &module_id) {
+            return Err(Error::ModuleAlreadyAdded);
+        }
+        if let Some(scope) = &scope {
+            if scope
+                .spend_caps
+                .values()
+                .iter()
+                .any(|spend_cap| spend_cap < 0)
+            {
+                return Err(Error::NegativeAmount);
+            }
+        }
+        write_module(&e, &module_id, ModuleState::Pending);
+        write_module_scope(&e, &module_id, &scope);
+        event::add_module(&e, module_id);
+        Ok(())
+    }
+
+    /// Activates a pending module, called back by the module once it stored its settings for this token
+    ///
+    /// # Arguments
+    /// - `e` Runtime environment
+    /// - `module_id` Module added by the manager
+    fn initialize_module(e: Env, module_id: Address) -> Result<(), Error> {
+        module_id.require_auth();
+        let registry = require_registry(&e)?;
+        assert_registered_module(&e, &module_id, &registry)?;
+        match read_module(&e, &module_id) {
+            Some(ModuleState::Pending) => {}
+            Some(ModuleState::Active) => return Err(Error::ModuleNotPending),
+            None => return Err(Error::RequiresTokenRegisteredModule),
+        }
+        write_module(&e, &module_id, ModuleState::Active);
+        write_last_fee_mint(&e, &module_id, e.ledger().timestamp());
+        event::initialize_module(&e, module_id);
         Ok(())
     }
+
+    /// Removes a module from this token, then notifies it through `on_remove` so it can
+    /// clear its state for the token
+    /// The removal does not depend on the module, failures of the notification are ignored
+    ///
+    /// # Arguments
+    /// - `e` Runtime environment
+    /// - `module_id` Module to remove
     fn remove_module(e: Env, module_id: Address) -> Result<(), Error> {
         let manager = require_manager(&e)?;
         manager.require_auth();
+        if !is_registered(&e, &module_id) {
+            return Ok(());
+        }
         remove_module(&e, &module_id);
+        write_module_scope(&e, &module_id, &None);
+        remove_last_fee_mint(&e, &module_id);
+        remove_module_spending(&e, &module_id);
+        event::remove_module(&e, module_id.clone());
+
+        let _ =
+            module_hooks::Client::new(&e, &module_id).try_on_remove(&e.current_contract_address());
         Ok(())
     }
 
@@ -345,6 +627,32 @@ impl Module for ConstellationToken {
         is_registered(&e, &module_id)
     }
 
+    fn get_module_scope(e: Env, module_id: Address) -> Option<ModuleScope> {
+        read_module_scope(&e, &module_id)
+    }
+
+    /// Returns the modules added to this token with their state
+    fn get_modules(e: Env) -> Map<Address, ModuleState> {
+        let mut modules = Map::new(&e);
+        for module_id in read_modules(&e).iter() {
+            if let Some(state) = read_module(&e, &module_id) {
+                modules.set(module_id, state);
+            }
+        }
+        modules
+    }
+
+    /// Calls `target_id` as the constellation token on behalf of `module_id`
+    /// The units of the tokens in the balance snapshot of the module are updated after the call
+    /// Returns error if the call is outside the scope of the module, spends more of a capped token
+    /// than is left of its cap in the spend window, or leaves a component not backing the total supply
+    ///
+    /// # Arguments
+    /// - `e` Runtime environment
+    /// - `module_id` Module invoking the call
+    /// - `target_id` Contract to call
+    /// - `call_data` Function and arguments of the call
+    /// - `auth_entries` Calls authorized for the constellation token beneath the call
     fn invoke(
         e: Env,
         module_id: Address,
@@ -352,15 +660,38 @@ impl Module for ConstellationToken {
         call_data: (Symbol, Vec<Val>),
         auth_entries: Vec<InvokerContractAuthEntry>,
     ) -> Result<(), Error> {
-         module_id.require_auth();
-
-        let registry = require_registry(&e)?;
-        assert_registered_module(&e, &module_id, &registry);
-        assert_token_registered_module(&e, &module_id);
+        module_id.require_auth();
+        require_module(&e, &module_id)?;
 
         let (function, args) = call_data;
+        let scope = read_module_scope(&e, &module_id);
+        let mut spent = read_module_spending(&e, &module_id);
+        let balances_before = match &scope {
+            Some(scope) => {
+                require_scoped_call(scope, &target_id, &function)?;
+                require_scoped_approve(&e, scope, &spent, &target_id, &function, &args)?;
+                require_scoped_auth(&e, scope, &spent, &auth_entries)?;
+                spend_cap_balances(&e, scope)
+            }
+            None => Map::new(&e),
+        };
+        let component_balances_before = component_balances(&e);
+        let snapshot = read_snapshot(&e, &module_id)
+            .filter(|snapshot| snapshot.ledger == e.ledger().sequence());
+
         e.authorize_as_current_contract(auth_entries);
         e.invoke_contract::<Val>(&target_id, &function, args);
+
+        if let Some(scope) = &scope {
+            require_spend_caps(&e, scope, &balances_before, &mut spent)?;
+            write_module_spending(&e, &module_id, &spent);
+        }
+        // the units of the snapshot tokens follow their balances as soon as the call returns,
+        // whether or not the module calls `update_units`
+        if let Some(snapshot) = &snapshot {
+            reconcile_snapshot(&e, &module_id, snapshot)?;
+        }
+        require_backed_components(&e, &component_balances_before)?;
         Ok(())
     }
 }
@@ -32,6 +32,22 @@ pub enum Error {
     RequiresManage = 506,
     RequiresTokenRegisteredModule = 507,
     ModuleNotInRegistery = 508,
+    RequiresBalanceSnapshot = 509,
+    StaleBalanceSnapshot = 510,
+    ZeroSupply = 511,
+    RequiresOracle = 512,
+    StalePrice = 513,
+    InvalidPriceAge = 514,
+    TargetNotInScope = 515,
+    FunctionNotInScope = 516,
+    SpendCapExceeded = 517,
+    ComponentNotBacked = 518,
+    ModulePending = 519,
+    ModuleNotPending = 520,
+    MintFeeExceedsCap = 521,
+    ModuleAlreadyAdded = 522,
+    ComponentNotAllowed = 523,
+    ExceedsMaxComponents = 524,
 }
 
 pub fn check_zero_or_negative_amount(e: &Env, amount: i128) {
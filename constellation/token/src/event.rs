@@ -1,7 +1,9 @@
-use soroban_sdk::{contracttype, symbol_short, Address, Env, IntoVal, String, Symbol, Vec};
+use soroban_sdk::{
+    contracttype, symbol_short, Address, Env, IntoVal, Map, String, Symbol, Vec,
+};
 use soroban_token_sdk::{metadata::TokenMetadata, TokenUtils};
 
-use crate::storage::types::Component;
+use crate::storage::types::{Component, ModuleState};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -11,6 +13,14 @@ pub struct Redeem {
     amount: i128,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RedeemInKind {
+    from: Address,
+    to: Address,
+    amount: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Initialize {
@@ -25,12 +35,47 @@ pub struct SetManager {
     new_manager: Address,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpdateUnits {
+    module_id: Address,
+    units: Map<Address, i128>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintFee {
+    module_id: Address,
+    to: Address,
+    amount: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SetRegistry {
     registry: Address,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetModuleState {
+    module_id: Address,
+    state: ModuleState,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemoveModule {
+    module_id: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetOracle {
+    oracle_id: Address,
+    max_price_age: u64,
+}
+
 pub(crate) fn redeem(e: &Env, spender: Address, from: Address, amount: i128) {
     let topics = (Symbol::new(e, "redeem"),);
     e.events().publish(
@@ -43,6 +88,11 @@ pub(crate) fn redeem(e: &Env, spender: Address, from: Address, amount: i128) {
     );
 }
 
+pub(crate) fn redeem_in_kind(e: &Env, from: Address, to: Address, amount: i128) {
+    let topics = (Symbol::new(e, "redeem_in_kind"),);
+    e.events().publish(topics, RedeemInKind { from, to, amount });
+}
+
 pub(crate) fn set_manager(e: &Env, old_manager: Address, new_manager: Address) {
     let topics = (Symbol::new(e, "set_manager"),);
     e.events().publish(
@@ -63,3 +113,72 @@ pub(crate) fn initialize(e: &Env, addresses: Vec<Address>, units: Vec<i128>) {
     let topics = (Symbol::new(e, "intialize"), e.current_contract_address());
     e.events().publish(topics, Initialize { addresses, units });
 }
+
+pub(crate) fn update_units(e: &Env, module_id: Address, units: Map<Address, i128>) {
+    let topics = (Symbol::new(e, "update_units"), e.current_contract_address());
+    e.events().publish(topics, UpdateUnits { module_id, units });
+}
+
+pub(crate) fn mint_fee(e: &Env, module_id: Address, to: Address, amount: i128) {
+    let topics = (Symbol::new(e, "mint_fee"), e.current_contract_address());
+    e.events().publish(
+        topics,
+        MintFee {
+            module_id,
+            to,
+            amount,
+        },
+    );
+}
+
+pub(crate) fn set_oracle(e: &Env, oracle_id: Address, max_price_age: u64) {
+    let topics = (Symbol::new(e, "set_oracle"), e.current_contract_address());
+    e.events().publish(
+        topics,
+        SetOracle {
+            oracle_id,
+            max_price_age,
+        },
+    );
+}
+
+pub(crate) fn set_allowed_components(e: &Env, components: Vec<Address>) {
+    let topics = (
+        Symbol::new(e, "set_allowed_components"),
+        e.current_contract_address(),
+    );
+    e.events().publish(topics, components);
+}
+
+pub(crate) fn add_module(e: &Env, module_id: Address) {
+    let topics = (Symbol::new(e, "add_module"), e.current_contract_address());
+    e.events().publish(
+        topics,
+        SetModuleState {
+            module_id,
+            state: ModuleState::Pending,
+        },
+    );
+}
+
+pub(crate) fn initialize_module(e: &Env, module_id: Address) {
+    let topics = (
+        Symbol::new(e, "initialize_module"),
+        e.current_contract_address(),
+    );
+    e.events().publish(
+        topics,
+        SetModuleState {
+            module_id,
+            state: ModuleState::Active,
+        },
+    );
+}
+
+pub(crate) fn remove_module(e: &Env, module_id: Address) {
+    let topics = (
+        Symbol::new(e, "remove_module"),
+        e.current_contract_address(),
+    );
+    e.events().publish(topics, RemoveModule { module_id });
+}
@@ -1,11 +1,18 @@
+use crate::error::Error;
+use crate::require::{
+    require_allowed_component, require_max_components, require_oracle, require_price,
+};
 use crate::storage::component::{
     read_component, read_components_list, remove_component, write_component,
 };
 use crate::storage::metadata::read_decimal;
+use crate::storage::snapshot::write_snapshot;
 use crate::storage::total_supply::{read_total_supply, write_total_supply};
-use crate::storage::types::Component;
+use crate::storage::types::{BalanceSnapshot, Component, ModuleScope, BPS_DENOMINATOR};
+use constellation_lib::math::{self, Rounding};
+use constellation_lib::oracle::asset_value;
 use soroban_sdk::token::TokenClient;
-use soroban_sdk::{token, Address, Env};
+use soroban_sdk::{panic_with_error, token, Address, Env, Map};
 
 ///  Lock - Transfers each component token to constellation token
 ///
@@ -13,13 +20,15 @@ use soroban_sdk::{token, Address, Env};
 ///
 /// - `e` The runtime environment.
 /// - `from` Address of component tokens owner account
-/// - `amount` amount of constellation token mint, which is multiplied by unit to obtain component value to transfer
+/// - `amount` amount of constellation token mint, converted with each unit to the component value to transfer
+///
+/// Component quantities are rounded up so minted tokens are always fully backed
 pub fn lock(e: &Env, from: &Address, amount: i128) {
     let components = read_components_list(e);
+    let decimals = read_decimal(e);
     for c in components.iter() {
-        let quantity = amount * c.unit; // unit * amount
+        let quantity = component_quantity(e, amount, c.unit, decimals, Rounding::Up);
         let _token = token::Client::new(e, &c.address);
-        let b = _token.balance(from);
         _token.transfer_from(
             &e.current_contract_address(),
             from,
@@ -35,16 +44,35 @@ pub fn lock(e: &Env, from: &Address, amount: i128) {
 ///
 /// - `e` The runtime environment.
 /// - `to` Address to send component tokens
-/// - `amount` amount of constellation token  which is multiplied by  unit to obtain component value to transfer
+/// - `amount` amount of constellation token, converted with each unit to the component value to transfer
+///
+/// Component quantities are rounded down so redemptions never release more than the tokens are backed by
 pub fn redeem(e: &Env, to: &Address, amount: i128) {
     let components = read_components_list(e);
+    let decimals = read_decimal(e);
     for c in components.iter() {
-        let quantity = amount * c.unit; // c.unit * amount;
+        let quantity = component_quantity(e, amount, c.unit, decimals, Rounding::Down);
+        if quantity == 0 {
+            continue;
+        }
         let _token = token::Client::new(&e, &c.address);
         _token.transfer(&e.current_contract_address(), &to, &quantity);
     }
 }
 
+/// Returns the quantity of a component backing `amount` constellation tokens
+/// Panics if the quantity overflows
+pub fn component_quantity(
+    e: &Env,
+    amount: i128,
+    unit: i128,
+    decimals: u32,
+    rounding: Rounding,
+) -> i128 {
+    math::component_quantity(amount, unit, decimals, rounding)
+        .unwrap_or_else(|| panic_with_error!(e, Error::ValueTooLargeOverFlow))
+}
+
 pub fn increase_supply(e: &Env, amount: i128) {
     let mut new_total_supply = read_total_supply(&e);
     write_total_supply(&e, new_total_supply + amount);
@@ -61,46 +89,97 @@ pub fn decrease_supply(e: &Env, amount: i128) {
     write_total_supply(&e, total_supply);
 }
 
+/// Returns the component balance not accounted for by the units of the current supply
 #[inline(always)]
 pub fn calculate_airdropped_amount(
+    e: &Env,
     component_previous_balance: i128,
     component_unit: i128,
     constellation_token_supply: i128,
+    decimals: u32,
 ) -> i128 {
-    component_previous_balance - (component_unit * constellation_token_supply)
+    component_previous_balance
+        - component_quantity(
+            e,
+            constellation_token_supply,
+            component_unit,
+            decimals,
+            Rounding::Down,
+        )
 }
 
+/// Returns the unit per whole constellation token backed by the component balance
+/// Rounded down so the position never claims more than the token holds
 #[inline(always)]
 pub fn calculate_position(
+    e: &Env,
     component_total_balance: i128,
     constellation_token_supply: i128,
     airdropped_amount: i128,
+    decimals: u32,
 ) -> i128 {
-    (component_total_balance - airdropped_amount) / constellation_token_supply
+    let scale = math::unit_scale(decimals)
+        .unwrap_or_else(|| panic_with_error!(e, Error::ValueTooLargeOverFlow));
+    math::mul_div(
+        component_total_balance - airdropped_amount,
+        scale,
+        constellation_token_supply,
+        Rounding::Down,
+    )
+    .unwrap_or_else(|| panic_with_error!(e, Error::ValueTooLargeOverFlow))
 }
 
+/// Scales every component unit by `supply / (supply + minted)` so the components held
+/// back `minted` newly issued tokens as well as the current supply
+/// Units are rounded down and components whose unit drops to zero are removed
+pub fn dilute_units(e: &Env, supply: i128, minted: i128) {
+    let diluted_supply = supply
+        .checked_add(minted)
+        .unwrap_or_else(|| panic_with_error!(e, Error::ValueTooLargeOverFlow));
+    for component in read_components_list(e).iter() {
+        let unit = math::mul_div(component.unit, supply, diluted_supply, Rounding::Down)
+            .unwrap_or_else(|| panic_with_error!(e, Error::ValueTooLargeOverFlow));
+        if unit <= 0 {
+            remove_component(e, component.address);
+        } else {
+            write_component(
+                e,
+                component.address.clone(),
+                Component { unit, ..component },
+            );
+        }
+    }
+}
+
+/// Updates the unit of a component from its balance and the total supply, adding or removing it
+/// Returns error if the component is new and outside the components allowed by the manager
 pub fn update_position(
     e: &Env,
     (component_address, component_previous_balance): (Address, i128),
-) -> i128 {
+) -> Result<i128, Error> {
     let component_current_balance =
         TokenClient::new(&e, &component_address).balance(&e.current_contract_address());
     let constellation_token_supply = read_total_supply(&e);
+    let decimals = read_decimal(e);
 
     let unit = match read_component(&e, component_address.clone()) {
         Some(mut component) => {
             let airdropped_amount = calculate_airdropped_amount(
+                e,
                 component_previous_balance,
                 component.unit,
                 constellation_token_supply,
+                decimals,
             );
             let unit = calculate_position(
+                e,
                 component_current_balance,
                 constellation_token_supply,
                 airdropped_amount,
+                decimals,
             );
 
-            if unit == 0 {
+            if unit <= 0 {
                 remove_component(&e, component_address.clone());
             } else {
                 write_component(
@@ -113,17 +192,22 @@ pub fn update_position(
         }
         None => {
             let airdropped_amount = calculate_airdropped_amount(
+                e,
                 component_previous_balance,
                 0,
                 constellation_token_supply,
+                decimals,
             );
             let unit = calculate_position(
+                e,
                 component_current_balance,
                 constellation_token_supply,
                 airdropped_amount,
+                decimals,
             );
 
             if unit > 0 {
+                require_allowed_component(e, &component_address)?;
                 write_component(
                     &e,
                     component_address.clone(),
@@ -137,5 +221,100 @@ pub fn update_position(
         }
     };
 
-    unit
+    Ok(unit)
+}
+
+/// Returns the value of the balance backing the total supply of each component,
+/// priced by the oracle of the token in the base asset of its feed
+/// Returns error if no oracle is set or a component price is missing or stale
+pub fn component_values(e: &Env) -> Result<Map<Address, i128>, Error> {
+    let config = require_oracle(e)?;
+    let supply = read_total_supply(e);
+    let decimals = read_decimal(e);
+    let mut values = Map::new(e);
+    for component in read_components_list(e).iter() {
+        let price = require_price(e, &config, &component.address)?;
+        let quantity = math::component_quantity(supply, component.unit, decimals, Rounding::Down)
+            .ok_or(Error::ValueTooLargeOverFlow)?;
+        let component_decimals = TokenClient::new(e, &component.address).decimals();
+        let value =
+            asset_value(quantity, price, component_decimals).ok_or(Error::ValueTooLargeOverFlow)?;
+        values.set(component.address, value);
+    }
+    Ok(values)
+}
+
+/// Returns the net asset value of the token, the sum of its component values
+pub fn net_asset_value(values: &Map<Address, i128>) -> Result<i128, Error> {
+    let mut nav = 0i128;
+    for value in values.values().iter() {
+        nav = nav.checked_add(value).ok_or(Error::ValueTooLargeOverFlow)?;
+    }
+    Ok(nav)
+}
+
+/// Returns the share of the net asset value held in each component, in basis points
+/// Rounded down, so weights may sum to slightly less than 10_000
+pub fn component_weights(e: &Env, values: &Map<Address, i128>) -> Result<Map<Address, u32>, Error> {
+    let nav = net_asset_value(values)?;
+    if nav <= 0 {
+        return Err(Error::ZeroSupply);
+    }
+    let mut weights = Map::new(e);
+    for (component_id, value) in values.iter() {
+        let weight = math::mul_div(value, BPS_DENOMINATOR as i128, nav, Rounding::Down)
+            .ok_or(Error::ValueTooLargeOverFlow)?;
+        weights.set(component_id, weight as u32);
+    }
+    Ok(weights)
+}
+
+/// Returns the balances held by the constellation token of the tokens with a spend cap in `scope`
+pub fn spend_cap_balances(e: &Env, scope: &ModuleScope) -> Map<Address, i128> {
+    let mut balances = Map::new(e);
+    for token_id in scope.spend_caps.keys().iter() {
+        let balance = TokenClient::new(e, &token_id).balance(&e.current_contract_address());
+        balances.set(token_id, balance);
+    }
+    balances
+}
+
+/// Returns the balance of each component held by the constellation token
+pub fn component_balances(e: &Env) -> Map<Address, i128> {
+    let mut balances = Map::new(e);
+    for component in read_components_list(e).iter() {
+        let balance =
+            TokenClient::new(e, &component.address).balance(&e.current_contract_address());
+        balances.set(component.address, balance);
+    }
+    balances
+}
+
+/// Updates the units of the tokens of `snapshot` whose balance changed since it was recorded,
+/// and records their current balances in the balance snapshot of `module_id`
+/// Returns error if a new component is not allowed or exceeds the maximum number of components
+pub fn reconcile_snapshot(
+    e: &Env,
+    module_id: &Address,
+    snapshot: &BalanceSnapshot,
+) -> Result<(), Error> {
+    let components_before = read_components_list(e).len();
+    let mut balances = Map::new(e);
+    for (token_id, previous_balance) in snapshot.balances.iter() {
+        let balance = TokenClient::new(e, &token_id).balance(&e.current_contract_address());
+        if balance != previous_balance {
+            update_position(e, (token_id.clone(), previous_balance))?;
+        }
+        balances.set(token_id, balance);
+    }
+    require_max_components(e, components_before)?;
+    write_snapshot(
+        e,
+        module_id,
+        &BalanceSnapshot {
+            ledger: snapshot.ledger,
+            balances,
+        },
+    );
+    Ok(())
 }
@@ -1,3 +1,5 @@
+use constellation_lib::traits::factory;
+use constellation_lib::types::oracle::OracleConfig;
 use soroban_sdk::{Address, Env, String, Symbol, Val, Vec};
 
 pub(crate) mod registry {
@@ -10,3 +12,19 @@ pub(crate) fn is_registered_module(e: &Env, module_id: &Address, registry_id: &A
     let client = registry::Client::new(&e, &registry_id);
     client.is_registered_module(&module_id)
 }
+
+/// Returns the protocol oracle set in the registry
+pub(crate) fn registry_oracle(e: &Env, registry_id: &Address) -> Option<OracleConfig> {
+    let client = registry::Client::new(e, registry_id);
+    client.get_oracle().map(|config| OracleConfig {
+        oracle_id: config.oracle_id,
+        max_price_age: config.max_price_age,
+    })
+}
+
+/// Returns the maximum number of components set in the factory of the registry
+pub(crate) fn max_components(e: &Env, registry_id: &Address) -> Option<u32> {
+    let client = registry::Client::new(e, registry_id);
+    let factory_id = client.get_factory()?;
+    factory::Client::new(e, &factory_id).get_max_components()
+}
@@ -1,9 +1,23 @@
 // use super::registry::get_adapter_id;
 use crate::error::Error;
-use crate::registry::is_registered_module;
-use crate::storage::module::is_registered;
+use crate::helpers::component_quantity;
+use crate::registry::{is_registered_module, max_components, registry_oracle};
+use crate::storage::component::{read_allowed_components, read_components_list};
+use crate::storage::metadata::read_decimal;
+use crate::storage::module::read_module;
+use crate::storage::oracle::read_oracle;
+use crate::storage::total_supply::read_total_supply;
+use crate::storage::types::{ModuleScope, ModuleState};
 use crate::storage::{admin::read_administrator, manager::read_manager, registry::read_registry};
-use soroban_sdk::{contract, contractimpl, contracttype, panic_with_error, Address, Env};
+use constellation_lib::math::Rounding;
+use constellation_lib::oracle::config_price;
+use constellation_lib::types::oracle::OracleConfig;
+use soroban_sdk::auth::InvokerContractAuthEntry;
+use soroban_sdk::token::TokenClient;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, panic_with_error, Address, Env, Map, Symbol, TryFromVal,
+    Val, Vec,
+};
 
 pub fn require_administrator(e: &Env) -> Result<Address, Error> {
     let admin = match read_administrator(e) {
@@ -29,13 +43,6 @@ pub fn require_manager(e: &Env) -> Result<Address, Error> {
     Ok(manage_id)
 }
 
-pub fn assert_token_registered_module(e: &Env, module_id: &Address) -> Result<(), Error> {
-    if is_registered(e, &module_id) == false {
-        return Err(Error::RequiresTokenRegisteredModule);
-    }
-    Ok(())
-}
-
 pub fn assert_registered_module(
     e: &Env,
     module_id: &Address,
@@ -46,3 +53,192 @@ pub fn assert_registered_module(
     };
     Ok(())
 }
+
+/// Returns error unless `module_id` is registered in the registry and initialized on this token
+pub fn require_module(e: &Env, module_id: &Address) -> Result<(), Error> {
+    let registry_id = require_registry(e)?;
+    assert_registered_module(e, module_id, &registry_id)?;
+    match read_module(e, module_id) {
+        Some(ModuleState::Active) => Ok(()),
+        Some(ModuleState::Pending) => Err(Error::ModulePending),
+        None => Err(Error::RequiresTokenRegisteredModule),
+    }
+}
+
+/// Returns the oracle set on this token, or the protocol oracle of the registry
+pub fn require_oracle(e: &Env) -> Result<OracleConfig, Error> {
+    if let Some(config) = read_oracle(e) {
+        return Ok(config);
+    }
+    let config = match read_registry(e) {
+        Some(registry_id) => registry_oracle(e, &registry_id),
+        None => None,
+    };
+    config.ok_or(Error::RequiresOracle)
+}
+
+/// Returns the price of `token_id`, or error if the oracle does not quote it or its price is stale
+pub fn require_price(e: &Env, config: &OracleConfig, token_id: &Address) -> Result<i128, Error> {
+    config_price(e, config, token_id).ok_or(Error::StalePrice)
+}
+
+/// Returns error unless `scope` allows calling `function` on `target_id`
+pub fn require_scoped_call(
+    scope: &ModuleScope,
+    target_id: &Address,
+    function: &Symbol,
+) -> Result<(), Error> {
+    let functions = match scope.targets.get(target_id.clone()) {
+        Some(functions) => functions,
+        None => return Err(Error::TargetNotInScope),
+    };
+    if !functions.is_empty() && !functions.contains(function) {
+        return Err(Error::FunctionNotInScope);
+    }
+    Ok(())
+}
+
+/// Returns error if `function` approves a spender outside the targets of `scope`, or an amount
+/// of a capped token above what is left of its cap after `spent`
+/// Calls other than `approve` are not checked
+pub fn require_scoped_approve(
+    e: &Env,
+    scope: &ModuleScope,
+    spent: &Map<Address, i128>,
+    target_id: &Address,
+    function: &Symbol,
+    args: &Vec<Val>,
+) -> Result<(), Error> {
+    if *function != Symbol::new(e, "approve") {
+        return Ok(());
+    }
+    // approve(from, spender, amount, expiration_ledger)
+    let spender = args
+        .get(1)
+        .and_then(|spender| Address::try_from_val(e, &spender).ok())
+        .ok_or(Error::TargetNotInScope)?;
+    if !scope.targets.contains_key(spender) {
+        return Err(Error::TargetNotInScope);
+    }
+    if let Some(spend_cap) = scope.spend_caps.get(target_id.clone()) {
+        let amount = args
+            .get(2)
+            .and_then(|amount| i128::try_from_val(e, &amount).ok())
+            .ok_or(Error::SpendCapExceeded)?;
+        if amount > spend_cap - spent.get(target_id.clone()).unwrap_or(0) {
+            return Err(Error::SpendCapExceeded);
+        }
+    }
+    Ok(())
+}
+
+/// Returns error unless `scope` allows every call authorized by `auth_entries`, including nested ones
+/// Contract creation is never in scope
+pub fn require_scoped_auth(
+    e: &Env,
+    scope: &ModuleScope,
+    spent: &Map<Address, i128>,
+    auth_entries: &Vec<InvokerContractAuthEntry>,
+) -> Result<(), Error> {
+    for entry in auth_entries.iter() {
+        match entry {
+            InvokerContractAuthEntry::Contract(invocation) => {
+                let context = &invocation.context;
+                require_scoped_call(scope, &context.contract, &context.fn_name)?;
+                require_scoped_approve(
+                    e,
+                    scope,
+                    spent,
+                    &context.contract,
+                    &context.fn_name,
+                    &context.args,
+                )?;
+                require_scoped_auth(e, scope, spent, &invocation.sub_invocations)?;
+            }
+            InvokerContractAuthEntry::CreateContractHostFn(_) => {
+                return Err(Error::TargetNotInScope)
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Adds the drop of the balance of each token with a spend cap in `scope` since `balances_before`
+/// to `spent`, the amounts spent in the current spend window
+/// Returns error if a token spent more than its cap in the window
+pub fn require_spend_caps(
+    e: &Env,
+    scope: &ModuleScope,
+    balances_before: &Map<Address, i128>,
+    spent: &mut Map<Address, i128>,
+) -> Result<(), Error> {
+    for (token_id, spend_cap) in scope.spend_caps.iter() {
+        let balance_before = balances_before.get(token_id.clone()).unwrap_or(0);
+        let balance = TokenClient::new(e, &token_id).balance(&e.current_contract_address());
+        if balance >= balance_before {
+            continue;
+        }
+        let token_spent = spent
+            .get(token_id.clone())
+            .unwrap_or(0)
+            .checked_add(balance_before - balance)
+            .ok_or(Error::ValueTooLargeOverFlow)?;
+        if token_spent > spend_cap {
+            return Err(Error::SpendCapExceeded);
+        }
+        spent.set(token_id, token_spent);
+    }
+    Ok(())
+}
+
+/// Returns error if a component balance dropped since `balances_before` below the quantity
+/// backing the total supply at its unit
+///
+/// # Arguments
+/// - `e` Runtime environment
+/// - `balances_before` Component balances recorded by `component_balances` before the call
+pub fn require_backed_components(
+    e: &Env,
+    balances_before: &Map<Address, i128>,
+) -> Result<(), Error> {
+    let supply = read_total_supply(e);
+    let decimals = read_decimal(e);
+    for component in read_components_list(e).iter() {
+        let balance =
+            TokenClient::new(e, &component.address).balance(&e.current_contract_address());
+        if balance >= balances_before.get(component.address.clone()).unwrap_or(0) {
+            continue;
+        }
+        let backing = component_quantity(e, supply, component.unit, decimals, Rounding::Down);
+        if balance < backing {
+            return Err(Error::ComponentNotBacked);
+        }
+    }
+    Ok(())
+}
+
+/// Returns error if `component_id` is outside the components the manager allows modules to add
+pub fn require_allowed_component(e: &Env, component_id: &Address) -> Result<(), Error> {
+    let allowed_components = read_allowed_components(e);
+    if !allowed_components.is_empty() && !allowed_components.contains(component_id) {
+        return Err(Error::ComponentNotAllowed);
+    }
+    Ok(())
+}
+
+/// Returns error if the token holds more components than `components_before`
+/// and than the maximum set in the factory of the registry
+pub fn require_max_components(e: &Env, components_before: u32) -> Result<(), Error> {
+    let components = read_components_list(e).len();
+    if components <= components_before {
+        return Ok(());
+    }
+    let max_components = match read_registry(e) {
+        Some(registry_id) => max_components(e, &registry_id),
+        None => None,
+    };
+    match max_components {
+        Some(max_components) if components > max_components => Err(Error::ExceedsMaxComponents),
+        _ => Ok(()),
+    }
+}
@@ -73,3 +73,14 @@ fn _extend_ttl(e: &Env) {
         PERSISTENT_LEDGER_LIFE,
     );
 }
+
+/// Returns the tokens modules may add as new components, any token if empty
+pub fn read_allowed_components(e: &Env) -> Vec<Address> {
+    let key = DataKey::AllowedComponents;
+    e.storage().instance().get(&key).unwrap_or(Vec::new(e))
+}
+
+pub fn write_allowed_components(e: &Env, components: &Vec<Address>) {
+    let key = DataKey::AllowedComponents;
+    e.storage().instance().set(&key, components);
+}
@@ -20,5 +20,12 @@ pub enum DataKey {
     Balance(Address),
     Nonce(Address),
     State(Address),
+    Modules,
     Module(Address),
+    ModuleScope(Address),
+    BalanceSnapshot(Address),
+    Oracle,
+    LastFeeMint(Address),
+    AllowedComponents,
+    ModuleSpending(Address),
 }
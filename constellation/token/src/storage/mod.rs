@@ -6,6 +6,8 @@ pub mod keys;
 pub mod manager;
 pub mod metadata;
 pub mod module;
+pub mod oracle;
 pub mod registry;
+pub mod snapshot;
 pub mod total_supply;
 pub mod types;
@@ -1,20 +1,40 @@
 use super::keys::DataKey;
-use crate::error::Error;
-use soroban_sdk::{Address, Env};
+use super::types::{ModuleScope, ModuleSpending, ModuleState, SPEND_WINDOW_LEDGERS};
+use soroban_sdk::{vec, Address, Env, Map, Vec};
 
-pub fn read_module(e: &Env, id: &Address) -> Option<Address> {
+pub fn read_module(e: &Env, id: &Address) -> Option<ModuleState> {
     let key = DataKey::Module(id.clone());
     e.storage().instance().get(&key)
 }
 
-pub fn write_module(e: &Env, id: &Address) {
+pub fn write_module(e: &Env, id: &Address, state: ModuleState) {
     let key = DataKey::Module(id.clone());
-    e.storage().instance().set(&key, &id);
+    e.storage().instance().set(&key, &state);
+
+    let mut modules = read_modules(e);
+    if !modules.contains(id) {
+        modules.push_back(id.clone());
+        e.storage().instance().set(&DataKey::Modules, &modules);
+    }
 }
 
 pub fn remove_module(e: &Env, id: &Address) {
     let key: DataKey = DataKey::Module(id.clone());
     e.storage().instance().remove(&key);
+
+    let mut modules = read_modules(e);
+    if let Some(index) = modules.first_index_of(id) {
+        modules.remove(index);
+        e.storage().instance().set(&DataKey::Modules, &modules);
+    }
+}
+
+/// Returns the modules added to the token, in the order they were added
+pub fn read_modules(e: &Env) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::Modules)
+        .unwrap_or(vec![e])
 }
 
 pub fn is_registered(e: &Env, id: &Address) -> bool {
@@ -23,3 +43,56 @@ pub fn is_registered(e: &Env, id: &Address) -> bool {
         None => false,
     }
 }
+
+pub fn read_module_scope(e: &Env, id: &Address) -> Option<ModuleScope> {
+    let key = DataKey::ModuleScope(id.clone());
+    e.storage().instance().get(&key)
+}
+
+pub fn write_module_scope(e: &Env, id: &Address, scope: &Option<ModuleScope>) {
+    let key = DataKey::ModuleScope(id.clone());
+    match scope {
+        Some(scope) => e.storage().instance().set(&key, scope),
+        None => e.storage().instance().remove(&key),
+    }
+}
+
+/// Returns the timestamp a module last minted fees at, or was activated at if it never did
+pub fn read_last_fee_mint(e: &Env, id: &Address) -> Option<u64> {
+    let key = DataKey::LastFeeMint(id.clone());
+    e.storage().instance().get(&key)
+}
+
+pub fn write_last_fee_mint(e: &Env, id: &Address, timestamp: u64) {
+    let key = DataKey::LastFeeMint(id.clone());
+    e.storage().instance().set(&key, &timestamp);
+}
+
+pub fn remove_last_fee_mint(e: &Env, id: &Address) {
+    let key = DataKey::LastFeeMint(id.clone());
+    e.storage().instance().remove(&key);
+}
+
+/// Returns the amounts of the capped tokens the module spent in the current spend window
+pub fn read_module_spending(e: &Env, id: &Address) -> Map<Address, i128> {
+    let key = DataKey::ModuleSpending(id.clone());
+    let window = e.ledger().sequence() / SPEND_WINDOW_LEDGERS;
+    match e.storage().instance().get::<_, ModuleSpending>(&key) {
+        Some(spending) if spending.window == window => spending.spent,
+        _ => Map::new(e),
+    }
+}
+
+pub fn write_module_spending(e: &Env, id: &Address, spent: &Map<Address, i128>) {
+    let key = DataKey::ModuleSpending(id.clone());
+    let spending = ModuleSpending {
+        window: e.ledger().sequence() / SPEND_WINDOW_LEDGERS,
+        spent: spent.clone(),
+    };
+    e.storage().instance().set(&key, &spending);
+}
+
+pub fn remove_module_spending(e: &Env, id: &Address) {
+    let key = DataKey::ModuleSpending(id.clone());
+    e.storage().instance().remove(&key);
+}
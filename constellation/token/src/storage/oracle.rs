@@ -0,0 +1,14 @@
+use constellation_lib::types::oracle::OracleConfig;
+use soroban_sdk::Env;
+
+use super::keys::DataKey;
+
+pub fn read_oracle(e: &Env) -> Option<OracleConfig> {
+    let key = DataKey::Oracle;
+    e.storage().instance().get(&key)
+}
+
+pub fn write_oracle(e: &Env, config: &OracleConfig) {
+    let key = DataKey::Oracle;
+    e.storage().instance().set(&key, config);
+}
@@ -0,0 +1,18 @@
+use super::keys::DataKey;
+use super::types::BalanceSnapshot;
+use soroban_sdk::{Address, Env};
+
+pub fn read_snapshot(e: &Env, module_id: &Address) -> Option<BalanceSnapshot> {
+    let key = DataKey::BalanceSnapshot(module_id.clone());
+    e.storage().temporary().get(&key)
+}
+
+pub fn write_snapshot(e: &Env, module_id: &Address, snapshot: &BalanceSnapshot) {
+    let key = DataKey::BalanceSnapshot(module_id.clone());
+    e.storage().temporary().set(&key, snapshot);
+}
+
+pub fn remove_snapshot(e: &Env, module_id: &Address) {
+    let key = DataKey::BalanceSnapshot(module_id.clone());
+    e.storage().temporary().remove(&key);
+}
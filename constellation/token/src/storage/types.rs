@@ -1,9 +1,15 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, Map, Symbol, Vec};
 
 pub(crate) const DAY_IN_LEDGERS: u32 = 17280;
 pub(crate) const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
 pub(crate) const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
 
+pub(crate) const BPS_DENOMINATOR: u32 = 10_000;
+/// Hard cap on the annual rate modules may mint fees at, 10% of the supply
+pub(crate) const MAX_MINT_FEE_BPS: u32 = 1_000;
+/// Length of the window spend caps of module scopes apply to, ~1 day
+pub(crate) const SPEND_WINDOW_LEDGERS: u32 = DAY_IN_LEDGERS;
+
 pub(crate) const PERSISTENT_LEDGER_LIFE: u32 = 90 * DAY_IN_LEDGERS; // ~90 days.
 pub(crate) const PERSISTENT_LEDGER_TTL_THRESHOLD: u32 = PERSISTENT_LEDGER_LIFE - DAY_IN_LEDGERS;
 
@@ -19,3 +25,43 @@ pub struct Component {
     pub address: Address,
     pub unit: i128,
 }
+
+/// Component balances recorded by the token at the start of a module operation
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct BalanceSnapshot {
+    pub ledger: u32,
+    pub balances: Map<Address, i128>,
+}
+
+/// Calls a module may make through `invoke`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ModuleScope {
+    /// Contracts the module may call or authorize calls to, with the functions allowed on each.
+    /// Any function of the contract if its list is empty
+    pub targets: Map<Address, Vec<Symbol>>,
+    /// Maximum amount of each token the constellation token may spend in a window of
+    /// `SPEND_WINDOW_LEDGERS`, and approve at once
+    pub spend_caps: Map<Address, i128>,
+    /// Whether the module may mint fees with `mint_fee`
+    pub mint_fee: bool,
+}
+
+/// Amounts of the capped tokens a scoped module spent in its current spend window
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ModuleSpending {
+    pub window: u32,
+    pub spent: Map<Address, i128>,
+}
+
+/// Lifecycle state of a module on the constellation token
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ModuleState {
+    /// Added by the manager, waiting for the module to initialize itself for the token
+    Pending,
+    /// Initialized by the module, allowed to operate on the token
+    Active,
+}
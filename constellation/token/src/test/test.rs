@@ -3,10 +3,15 @@ extern crate std;
 use super::test_interface::initialize_token;
 use crate::contract::ConstellationTokenClient;
 use crate::error::Error;
+use crate::registry::registry;
+use crate::storage::types::{ModuleScope, ModuleState};
+use constellation_lib::math::SECONDS_PER_YEAR;
+use constellation_lib::testutils::{MockOracle, MockOracleClient};
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
 use soroban_sdk::{
-    symbol_short,
-    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
-    vec, BytesN, Env, IntoVal, Symbol, Val, Vec,
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Ledger},
+    vec, BytesN, Env, IntoVal, Map, Symbol, Val, Vec,
 };
 use soroban_sdk::{Address, String};
 
@@ -278,3 +283,739 @@ fn mint_reverts_with_zero_or_negative_amount_not_allowed() {
     let restult = ct.try_mint(&mint_to, &i128::from(0));
     assert_eq!(restult, Err(Ok(Error::ZeroOrNegativeAmount.into())));
 }
+
+#[test]
+fn redeem_in_kind_releases_components_to_holder() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let holder = Address::generate(&e);
+    let recipient = Address::generate(&e);
+
+    let (ct, admin, _, (token1, token2, token3)) =
+        initialize_token(&e, create_constellation_token(&e));
+
+    token1.mint(&admin, &1000i128);
+    token2.mint(&admin, &1000i128);
+    token3.mint(&admin, &1000i128);
+    token1.approve(&admin, &ct.address, &1000i128, &1000u32);
+    token2.approve(&admin, &ct.address, &1000i128, &1000u32);
+    token3.approve(&admin, &ct.address, &1000i128, &1000u32);
+    ct.mint(&holder, &1000);
+
+    ct.redeem_in_kind(&holder, &recipient, &400);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            holder.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    ct.address.clone(),
+                    Symbol::new(&e, "redeem_in_kind"),
+                    (&holder, &recipient, 400_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+
+    assert_eq!(ct.balance(&holder), 600);
+    assert_eq!(token1.balance(&recipient), 400);
+    assert_eq!(token2.balance(&recipient), 400);
+    assert_eq!(token3.balance(&recipient), 400);
+    assert_eq!(token1.balance(&ct.address), 600);
+}
+
+#[test]
+fn redeem_in_kind_fails_with_insufficient_balance() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let holder = Address::generate(&e);
+
+    let (ct, _, _, _) = initialize_token(&e, create_constellation_token(&e));
+
+    let result = ct.try_redeem_in_kind(&holder, &holder, &1);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+}
+
+#[test]
+fn fractional_units_round_up_on_mint_and_down_on_redeem() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let holder = Address::generate(&e);
+
+    let token1 = create_token_contract(&e, &admin);
+    let token2 = create_token_contract(&e, &admin);
+    let components = vec![&e, token1.address.clone(), token2.address.clone()];
+    // 0.5 and 2.25 component base units per whole constellation token
+    let units = vec![&e, 500_000, 2_250_000];
+
+    let ct = create_constellation_token(&e);
+    ct.initialize(
+        &6u32,
+        &components,
+        &units,
+        &"c_token".into_val(&e),
+        &"token_symbol".into_val(&e),
+        &admin,
+        &Address::generate(&e),
+    );
+
+    token1.mint(&admin, &10_000_000i128);
+    token2.mint(&admin, &10_000_000i128);
+    token1.approve(&admin, &ct.address, &10_000_000i128, &1000u32);
+    token2.approve(&admin, &ct.address, &10_000_000i128, &1000u32);
+
+    ct.mint(&holder, &3_000_001);
+    assert_eq!(token1.balance(&ct.address), 1_500_001);
+    assert_eq!(token2.balance(&ct.address), 6_750_003);
+
+    ct.redeem_in_kind(&holder, &holder, &3_000_001);
+    assert_eq!(token1.balance(&holder), 1_500_000);
+    assert_eq!(token2.balance(&holder), 6_750_002);
+    assert_eq!(token1.balance(&ct.address), 1);
+    assert_eq!(token2.balance(&ct.address), 1);
+}
+
+fn create_registry<'a>(e: &Env) -> registry::Client<'a> {
+    registry::Client::new(e, &e.register_contract_wasm(None, registry::WASM))
+}
+
+fn register_module(
+    e: &Env,
+    ct: &ConstellationTokenClient,
+    in_registry: bool,
+    on_token: bool,
+) -> Address {
+    let registry = create_registry(e);
+    registry.initialize(&Address::generate(e));
+    ct.set_registry(&registry.address);
+    let module = Address::generate(e);
+    if in_registry {
+        registry.add_module(&module);
+    }
+    if on_token {
+        ct.add_module(&module, &None);
+        ct.initialize_module(&module);
+    }
+    module
+}
+
+#[contract]
+struct MockModule;
+
+#[contractimpl]
+impl MockModule {
+    pub fn initialize_token(e: Env, constellation_token_id: Address) {
+        ConstellationTokenClient::new(&e, &constellation_token_id)
+            .initialize_module(&e.current_contract_address());
+    }
+
+    pub fn on_remove(e: Env, constellation_token_id: Address) {
+        constellation_token_id.require_auth();
+        e.storage()
+            .instance()
+            .set(&symbol_short!("removed"), &constellation_token_id);
+    }
+
+    pub fn removed_from(e: Env) -> Option<Address> {
+        e.storage().instance().get(&symbol_short!("removed"))
+    }
+}
+
+#[test]
+fn module_is_pending_until_it_initializes_and_notified_on_removal() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (ct, _, _, (token1, _, _)) = initialize_token(&e, create_constellation_token(&e));
+    let module = MockModuleClient::new(&e, &e.register_contract(None, MockModule));
+    let registry = create_registry(&e);
+    registry.initialize(&Address::generate(&e));
+    registry.add_module(&module.address);
+    ct.set_registry(&registry.address);
+
+    assert_eq!(
+        ct.try_initialize_module(&module.address),
+        Err(Ok(Error::RequiresTokenRegisteredModule))
+    );
+    ct.add_module(&module.address, &None);
+    assert_eq!(
+        ct.get_modules(),
+        Map::from_array(&e, [(module.address.clone(), ModuleState::Pending)])
+    );
+    assert_eq!(
+        ct.try_snapshot_balances(&module.address, &vec![&e, token1.address.clone()]),
+        Err(Ok(Error::ModulePending))
+    );
+
+    module.initialize_token(&ct.address);
+    assert_eq!(
+        ct.get_modules(),
+        Map::from_array(&e, [(module.address.clone(), ModuleState::Active)])
+    );
+    assert_eq!(
+        ct.try_initialize_module(&module.address),
+        Err(Ok(Error::ModuleNotPending))
+    );
+    assert_eq!(
+        ct.try_add_module(&module.address, &None),
+        Err(Ok(Error::ModuleAlreadyAdded))
+    );
+    ct.snapshot_balances(&module.address, &vec![&e, token1.address.clone()]);
+
+    ct.remove_module(&module.address);
+    assert_eq!(ct.get_modules(), Map::new(&e));
+    assert_eq!(module.removed_from(), Some(ct.address.clone()));
+    assert_eq!(
+        ct.try_update_units(&module.address),
+        Err(Ok(Error::RequiresTokenRegisteredModule))
+    );
+}
+
+#[test]
+fn update_units_fails_with_module_not_in_registry() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (ct, _, _, _) = initialize_token(&e, create_constellation_token(&e));
+    let module = register_module(&e, &ct, false, false);
+
+    let result = ct.try_update_units(&module);
+    assert_eq!(result, Err(Ok(Error::ModuleNotInRegistery)));
+}
+
+#[test]
+fn update_units_fails_with_requires_token_registered_module() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (ct, _, _, _) = initialize_token(&e, create_constellation_token(&e));
+    let module = register_module(&e, &ct, true, false);
+
+    let result = ct.try_update_units(&module);
+    assert_eq!(result, Err(Ok(Error::RequiresTokenRegisteredModule)));
+}
+
+#[test]
+fn update_units_fails_with_requires_balance_snapshot() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (ct, _, _, _) = initialize_token(&e, create_constellation_token(&e));
+    let module = register_module(&e, &ct, true, true);
+
+    let result = ct.try_update_units(&module);
+    assert_eq!(result, Err(Ok(Error::RequiresBalanceSnapshot)));
+}
+
+#[test]
+fn update_units_fails_with_stale_balance_snapshot() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (ct, _, _, (token1, _, _)) = initialize_token(&e, create_constellation_token(&e));
+    let module = register_module(&e, &ct, true, true);
+
+    ct.snapshot_balances(&module, &vec![&e, token1.address.clone()]);
+    e.ledger().with_mut(|li| li.sequence_number += 1);
+
+    let result = ct.try_update_units(&module);
+    assert_eq!(result, Err(Ok(Error::StaleBalanceSnapshot)));
+}
+
+#[test]
+fn update_units_reconciles_snapshot_balances() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let holder = Address::generate(&e);
+    let (ct, admin, _, (token1, token2, token3)) =
+        initialize_token(&e, create_constellation_token(&e));
+    let module = register_module(&e, &ct, true, true);
+
+    token1.mint(&admin, &1000i128);
+    token2.mint(&admin, &1000i128);
+    token3.mint(&admin, &1000i128);
+    token1.approve(&admin, &ct.address, &1000i128, &1000u32);
+    token2.approve(&admin, &ct.address, &1000i128, &1000u32);
+    token3.approve(&admin, &ct.address, &1000i128, &1000u32);
+    ct.mint(&holder, &1000);
+
+    ct.snapshot_balances(&module, &vec![&e, token1.address.clone()]);
+    let mut args: Vec<Val> = vec![&e];
+    args.push_back(ct.address.into_val(&e));
+    args.push_back(Address::generate(&e).into_val(&e));
+    args.push_back(250i128.into_val(&e));
+    ct.invoke(
+        &module,
+        &token1.address,
+        &(Symbol::new(&e, "transfer"), args),
+        &vec![&e],
+    );
+    ct.update_units(&module);
+
+    assert_eq!(ct.get_component(&token1.address).unwrap().unit, 750_000);
+    assert_eq!(ct.get_component(&token2.address).unwrap().unit, 1_000_000);
+}
+
+#[test]
+fn update_units_keeps_units_of_unchanged_balances() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (ct, admin, _, (token1, token2, token3)) =
+        initialize_token(&e, create_constellation_token(&e));
+    let module = register_module(&e, &ct, true, true);
+    for token in [&token1, &token2, &token3] {
+        token.mint(&admin, &1000i128);
+        token.approve(&admin, &ct.address, &1000i128, &1000u32);
+    }
+    ct.mint(&Address::generate(&e), &3);
+
+    // 2 base units backing a supply of 3 leave a unit that does not divide evenly
+    ct.snapshot_balances(&module, &vec![&e, token1.address.clone()]);
+    ct.invoke(
+        &module,
+        &token1.address,
+        &transfer_call(&e, &ct.address, 1),
+        &vec![&e],
+    );
+    ct.update_units(&module);
+    assert_eq!(ct.get_component(&token1.address).unwrap().unit, 666_666);
+
+    ct.snapshot_balances(
+        &module,
+        &vec![&e, token1.address.clone(), token2.address.clone()],
+    );
+    ct.update_units(&module);
+    assert_eq!(ct.get_component(&token1.address).unwrap().unit, 666_666);
+    assert_eq!(ct.get_component(&token2.address).unwrap().unit, 1_000_000);
+    assert_eq!(ct.get_components().len(), 3);
+}
+
+#[test]
+fn update_units_fails_with_component_not_allowed() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (ct, admin, _, (token1, token2, token3)) =
+        initialize_token(&e, create_constellation_token(&e));
+    let module = register_module(&e, &ct, true, true);
+    for token in [&token1, &token2, &token3] {
+        token.mint(&admin, &1000i128);
+        token.approve(&admin, &ct.address, &1000i128, &1000u32);
+    }
+    ct.mint(&Address::generate(&e), &1000);
+    let token4 = create_token_contract(&e, &admin);
+    let token5 = create_token_contract(&e, &admin);
+
+    ct.set_allowed_components(&vec![&e, token5.address.clone()]);
+    assert_eq!(
+        ct.get_allowed_components(),
+        vec![&e, token5.address.clone()]
+    );
+    ct.snapshot_balances(&module, &vec![&e, token4.address.clone()]);
+    token4.mint(&ct.address, &1000);
+    let result = ct.try_update_units(&module);
+    assert_eq!(result, Err(Ok(Error::ComponentNotAllowed)));
+
+    ct.set_allowed_components(&vec![&e]);
+    ct.update_units(&module);
+    assert!(ct.get_component(&token4.address).is_some());
+}
+
+fn transfer_call(e: &Env, from: &Address, amount: i128) -> (Symbol, Vec<Val>) {
+    let mut args: Vec<Val> = vec![e];
+    args.push_back(from.into_val(e));
+    args.push_back(Address::generate(e).into_val(e));
+    args.push_back(amount.into_val(e));
+    (Symbol::new(e, "transfer"), args)
+}
+
+#[test]
+fn invoke_is_limited_to_module_scope() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let holder = Address::generate(&e);
+    let (ct, admin, _, (token1, token2, token3)) =
+        initialize_token(&e, create_constellation_token(&e));
+    let module = register_module(&e, &ct, true, false);
+
+    token1.mint(&admin, &1000i128);
+    token2.mint(&admin, &1000i128);
+    token3.mint(&admin, &1000i128);
+    token1.approve(&admin, &ct.address, &1000i128, &1000u32);
+    token2.approve(&admin, &ct.address, &1000i128, &1000u32);
+    token3.approve(&admin, &ct.address, &1000i128, &1000u32);
+    ct.mint(&holder, &1000);
+
+    let scope = ModuleScope {
+        targets: Map::from_array(
+            &e,
+            [(
+                token1.address.clone(),
+                vec![&e, Symbol::new(&e, "transfer")],
+            )],
+        ),
+        spend_caps: Map::from_array(&e, [(token1.address.clone(), 300i128)]),
+        mint_fee: false,
+    };
+    ct.add_module(&module, &Some(scope.clone()));
+    ct.initialize_module(&module);
+    assert_eq!(ct.get_module_scope(&module), Some(scope));
+
+    let result = ct.try_invoke(
+        &module,
+        &token2.address,
+        &transfer_call(&e, &ct.address, 100),
+        &vec![&e],
+    );
+    assert_eq!(result, Err(Ok(Error::TargetNotInScope)));
+
+    let mut args: Vec<Val> = vec![&e];
+    args.push_back(ct.address.into_val(&e));
+    let result = ct.try_invoke(
+        &module,
+        &token1.address,
+        &(Symbol::new(&e, "balance"), args),
+        &vec![&e],
+    );
+    assert_eq!(result, Err(Ok(Error::FunctionNotInScope)));
+
+    let auth_entries = vec![
+        &e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: token2.address.clone(),
+                fn_name: Symbol::new(&e, "transfer"),
+                args: transfer_call(&e, &ct.address, 100).1,
+            },
+            sub_invocations: vec![&e],
+        }),
+    ];
+    let result = ct.try_invoke(
+        &module,
+        &token1.address,
+        &transfer_call(&e, &ct.address, 100),
+        &auth_entries,
+    );
+    assert_eq!(result, Err(Ok(Error::TargetNotInScope)));
+
+    let result = ct.try_invoke(
+        &module,
+        &token1.address,
+        &transfer_call(&e, &ct.address, 301),
+        &vec![&e],
+    );
+    assert_eq!(result, Err(Ok(Error::SpendCapExceeded)));
+    assert_eq!(token1.balance(&ct.address), 1000);
+
+    ct.snapshot_balances(&module, &vec![&e, token1.address.clone()]);
+    ct.invoke(
+        &module,
+        &token1.address,
+        &transfer_call(&e, &ct.address, 300),
+        &vec![&e],
+    );
+    ct.update_units(&module);
+    assert_eq!(token1.balance(&ct.address), 700);
+
+    ct.remove_module(&module);
+    assert_eq!(ct.get_module_scope(&module), None);
+}
+
+fn approve_call(e: &Env, from: &Address, spender: &Address, amount: i128) -> (Symbol, Vec<Val>) {
+    let mut args: Vec<Val> = vec![e];
+    args.push_back(from.into_val(e));
+    args.push_back(spender.into_val(e));
+    args.push_back(amount.into_val(e));
+    args.push_back(20_000u32.into_val(e));
+    (Symbol::new(e, "approve"), args)
+}
+
+#[test]
+fn invoke_caps_spending_and_approvals_over_spend_window() {
+    let e = Env::default();
+    e.mock_all_auths();
+    // last ledger of the first spend window
+    e.ledger().with_mut(|li| li.sequence_number = 17_279);
+    let (ct, admin, _, (token1, token2, token3)) =
+        initialize_token(&e, create_constellation_token(&e));
+    let module = register_module(&e, &ct, true, false);
+    let exchange = Address::generate(&e);
+    for token in [&token1, &token2, &token3] {
+        token.mint(&admin, &1000i128);
+        token.approve(&admin, &ct.address, &1000i128, &20_000u32);
+    }
+    ct.mint(&Address::generate(&e), &1000);
+    token1.mint(&ct.address, &1000i128);
+
+    let scope = ModuleScope {
+        targets: Map::from_array(
+            &e,
+            [
+                (token1.address.clone(), vec![&e]),
+                (exchange.clone(), vec![&e]),
+            ],
+        ),
+        spend_caps: Map::from_array(&e, [(token1.address.clone(), 300i128)]),
+        mint_fee: false,
+    };
+    ct.add_module(&module, &Some(scope));
+    ct.initialize_module(&module);
+
+    let result = ct.try_mint_fee(&module, &Address::generate(&e), &1);
+    assert_eq!(result, Err(Ok(Error::FunctionNotInScope)));
+
+    // approvals go to targets only and stay within the cap
+    let result = ct.try_invoke(
+        &module,
+        &token1.address,
+        &approve_call(&e, &ct.address, &Address::generate(&e), 100),
+        &vec![&e],
+    );
+    assert_eq!(result, Err(Ok(Error::TargetNotInScope)));
+    let result = ct.try_invoke(
+        &module,
+        &token1.address,
+        &approve_call(&e, &ct.address, &exchange, 301),
+        &vec![&e],
+    );
+    assert_eq!(result, Err(Ok(Error::SpendCapExceeded)));
+
+    // the cap applies to the spending of the window, not to each call
+    ct.invoke(
+        &module,
+        &token1.address,
+        &transfer_call(&e, &ct.address, 200),
+        &vec![&e],
+    );
+    let result = ct.try_invoke(
+        &module,
+        &token1.address,
+        &transfer_call(&e, &ct.address, 101),
+        &vec![&e],
+    );
+    assert_eq!(result, Err(Ok(Error::SpendCapExceeded)));
+    let result = ct.try_invoke(
+        &module,
+        &token1.address,
+        &approve_call(&e, &ct.address, &exchange, 101),
+        &vec![&e],
+    );
+    assert_eq!(result, Err(Ok(Error::SpendCapExceeded)));
+    ct.invoke(
+        &module,
+        &token1.address,
+        &approve_call(&e, &ct.address, &exchange, 100),
+        &vec![&e],
+    );
+    assert_eq!(token1.allowance(&ct.address, &exchange), 100);
+
+    e.ledger().with_mut(|li| li.sequence_number += 1);
+    ct.invoke(
+        &module,
+        &token1.address,
+        &transfer_call(&e, &ct.address, 300),
+        &vec![&e],
+    );
+    assert_eq!(token1.balance(&ct.address), 1500);
+}
+
+#[test]
+fn invoke_updates_snapshot_units_without_update_units() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let holder = Address::generate(&e);
+    let (ct, admin, _, (token1, token2, token3)) =
+        initialize_token(&e, create_constellation_token(&e));
+    let module = register_module(&e, &ct, true, true);
+    for token in [&token1, &token2, &token3] {
+        token.mint(&admin, &1000i128);
+        token.approve(&admin, &ct.address, &1000i128, &1000u32);
+    }
+    ct.mint(&holder, &1000);
+
+    // the module drains token 1 and never calls update_units
+    ct.snapshot_balances(&module, &vec![&e, token1.address.clone()]);
+    ct.invoke(
+        &module,
+        &token1.address,
+        &transfer_call(&e, &ct.address, 400),
+        &vec![&e],
+    );
+    assert_eq!(ct.get_component(&token1.address).unwrap().unit, 600_000);
+    ct.invoke(
+        &module,
+        &token1.address,
+        &transfer_call(&e, &ct.address, 600),
+        &vec![&e],
+    );
+    assert!(ct.get_component(&token1.address).is_none());
+
+    // the supply stays backed by the components left
+    let to = Address::generate(&e);
+    ct.redeem_in_kind(&holder, &to, &1000);
+    assert_eq!(token2.balance(&to), 1000);
+    assert_eq!(token3.balance(&to), 1000);
+}
+
+#[test]
+fn invoke_fails_with_component_not_backed() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let holder = Address::generate(&e);
+    let (ct, admin, _, (token1, token2, token3)) =
+        initialize_token(&e, create_constellation_token(&e));
+    let module = register_module(&e, &ct, true, true);
+
+    token1.mint(&admin, &1000i128);
+    token2.mint(&admin, &1000i128);
+    token3.mint(&admin, &1000i128);
+    token1.approve(&admin, &ct.address, &1000i128, &1000u32);
+    token2.approve(&admin, &ct.address, &1000i128, &1000u32);
+    token3.approve(&admin, &ct.address, &1000i128, &1000u32);
+    ct.mint(&holder, &1000);
+    token1.mint(&ct.address, &50i128);
+
+    let result = ct.try_invoke(
+        &module,
+        &token1.address,
+        &transfer_call(&e, &ct.address, 51),
+        &vec![&e],
+    );
+    assert_eq!(result, Err(Ok(Error::ComponentNotBacked)));
+
+    // balance above the backing of the supply is not needed by the units
+    ct.invoke(
+        &module,
+        &token1.address,
+        &transfer_call(&e, &ct.address, 50),
+        &vec![&e],
+    );
+    assert_eq!(token1.balance(&ct.address), 1000);
+    assert_eq!(ct.get_component(&token1.address).unwrap().unit, 1_000_000);
+}
+
+#[test]
+fn mint_fee_fails_with_mint_fee_exceeds_cap() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let holder = Address::generate(&e);
+    let recipient = Address::generate(&e);
+    let (ct, admin, _, (token1, token2, token3)) =
+        initialize_token(&e, create_constellation_token(&e));
+    let module = register_module(&e, &ct, true, true);
+
+    for token in [&token1, &token2, &token3] {
+        token.mint(&admin, &1000i128);
+        token.approve(&admin, &ct.address, &1000i128, &1000u32);
+    }
+    ct.mint(&holder, &1000);
+    e.ledger().with_mut(|li| li.timestamp += SECONDS_PER_YEAR / 2);
+
+    // at most 10% per year: 1000 * 0.05 / 0.95
+    let result = ct.try_mint_fee(&module, &recipient, &53);
+    assert_eq!(result, Err(Ok(Error::MintFeeExceedsCap)));
+    ct.mint_fee(&module, &recipient, &52);
+    assert_eq!(ct.balance(&recipient), 52);
+
+    // the time since the last mint is consumed
+    let result = ct.try_mint_fee(&module, &recipient, &1);
+    assert_eq!(result, Err(Ok(Error::MintFeeExceedsCap)));
+}
+
+fn create_mock_oracle<'a>(e: &Env) -> MockOracleClient<'a> {
+    MockOracleClient::new(e, &e.register_contract(None, MockOracle {}))
+}
+
+/// Mints 1000 base units of the constellation token, backed by 1000 of each component
+fn mint_priced_token<'a>(
+    e: &Env,
+) -> (
+    ConstellationTokenClient<'a>,
+    MockOracleClient<'a>,
+    (Address, Address, Address),
+) {
+    let (ct, admin, _, (token1, token2, token3)) =
+        initialize_token(e, create_constellation_token(e));
+    for token in [&token1, &token2, &token3] {
+        token.mint(&admin, &1000i128);
+        token.approve(&admin, &ct.address, &1000i128, &1000u32);
+    }
+    ct.mint(&Address::generate(e), &1000);
+
+    // 7 decimal prices: token2 is worth twice as much as token1 and token3
+    let oracle = create_mock_oracle(e);
+    oracle.set_price(&token1.address, &10_000_000);
+    oracle.set_price(&token2.address, &20_000_000);
+    oracle.set_price(&token3.address, &10_000_000);
+    (ct, oracle, (token1.address, token2.address, token3.address))
+}
+
+#[test]
+fn nav_and_weights_use_registry_oracle() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (ct, oracle, (t1, t2, t3)) = mint_priced_token(&e);
+    assert_eq!(ct.try_get_nav(), Err(Ok(Error::RequiresOracle)));
+
+    let registry = create_registry(&e);
+    registry.initialize(&Address::generate(&e));
+    registry.set_oracle(&oracle.address, &300);
+    ct.set_registry(&registry.address);
+
+    let values = ct.get_component_values();
+    assert_eq!(values.get(t1.clone()), Some(1000));
+    assert_eq!(values.get(t2.clone()), Some(2000));
+    assert_eq!(values.get(t3.clone()), Some(1000));
+    assert_eq!(ct.get_nav(), 4000);
+
+    let weights = ct.get_weights();
+    assert_eq!(weights.get(t1), Some(2500));
+    assert_eq!(weights.get(t2), Some(5000));
+    assert_eq!(weights.get(t3), Some(2500));
+}
+
+#[test]
+fn nav_normalizes_feed_decimals() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (ct, _, (t1, t2, t3)) = mint_priced_token(&e);
+
+    // same prices as the 7 decimal feed, quoted with 14 decimals
+    let oracle = create_mock_oracle(&e);
+    oracle.set_decimals(&14);
+    oracle.set_price(&t1, &100_000_000_000_000);
+    oracle.set_price(&t2, &200_000_000_000_000);
+    oracle.set_price(&t3, &100_000_000_000_000);
+
+    let registry = create_registry(&e);
+    registry.initialize(&Address::generate(&e));
+    registry.set_oracle(&oracle.address, &300);
+    ct.set_registry(&registry.address);
+    assert_eq!(ct.get_nav(), 4000);
+
+    // and with 5 decimals
+    oracle.set_decimals(&5);
+    oracle.set_price(&t1, &100_000);
+    oracle.set_price(&t2, &200_000);
+    oracle.set_price(&t3, &100_000);
+    assert_eq!(ct.get_nav(), 4000);
+}
+
+#[test]
+fn token_oracle_overrides_registry_and_rejects_stale_prices() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (ct, oracle, _) = mint_priced_token(&e);
+
+    let registry = create_registry(&e);
+    registry.initialize(&Address::generate(&e));
+    registry.set_oracle(&create_mock_oracle(&e).address, &300);
+    ct.set_registry(&registry.address);
+    assert_eq!(
+        ct.try_set_oracle(&oracle.address, &0),
+        Err(Ok(Error::InvalidPriceAge))
+    );
+    ct.set_oracle(&oracle.address, &60);
+    assert_eq!(ct.get_oracle().unwrap().oracle_id, oracle.address);
+    assert_eq!(ct.get_nav(), 4000);
+
+    e.ledger().with_mut(|li| li.timestamp += 61);
+    assert_eq!(ct.try_get_nav(), Err(Ok(Error::StalePrice)));
+    assert_eq!(ct.try_get_weights(), Err(Ok(Error::StalePrice)));
+}
@@ -29,7 +29,7 @@ pub(crate) fn initialize_token<'a>(
     ct: ConstellationTokenClient<'a>,
   //  tokens: (Address, Address, Address),
 ) -> (ConstellationTokenClient<'a>, Address, Address, (TokenClient<'a>,TokenClient<'a>,TokenClient<'a> )) {
-    let amounts = vec![&e, 1, 1, 1];
+    let amounts = vec![&e, 1_000_000, 1_000_000, 1_000_000];
     let decimal: u32 = 6;
     let name: String = "c_token".into_val(e);
     let symbol: String = "token_symbol".into_val(e);
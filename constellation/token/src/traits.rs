@@ -1,10 +1,11 @@
 use soroban_sdk::auth::InvokerContractAuthEntry;
 use soroban_sdk::{contractclient, contractspecfn, token::Interface, Address, Env, String, Vec};
-use soroban_sdk::{Symbol, Val};
+use soroban_sdk::{Map, Symbol, Val};
 
 use crate::error::Error;
 use crate::storage::types::AllowanceValue;
-use crate::storage::types::Component;
+use crate::storage::types::{Component, ModuleScope, ModuleState};
+use constellation_lib::types::oracle::OracleConfig;
 
 pub use ConstellationTokenInterfaceClient as MyClient;
 
@@ -24,27 +25,48 @@ pub trait ConstellationTokenInterface {
     fn mint(e: Env, to: Address, amount: i128) -> Result<(), Error>;
 
     fn redeem(e: Env, from: Address, amount: i128) -> Result<(), Error>;
+
+    fn redeem_in_kind(e: Env, from: Address, to: Address, amount: i128) -> Result<(), Error>;
+
     fn set_manager(e: Env, new_manager: Address) -> Result<(), Error>;
 
     fn set_registry(e: Env, registry: Address) -> Result<(), Error>;
+
+    fn set_oracle(e: Env, oracle_id: Address, max_price_age: u64) -> Result<(), Error>;
+
+    fn get_oracle(e: Env) -> Option<OracleConfig>;
+
+    fn set_allowed_components(e: Env, components: Vec<Address>) -> Result<(), Error>;
+
+    fn get_allowed_components(e: Env) -> Vec<Address>;
+
+    fn get_nav(e: Env) -> Result<i128, Error>;
+
+    fn get_component_values(e: Env) -> Result<Map<Address, i128>, Error>;
+
+    fn get_weights(e: Env) -> Result<Map<Address, u32>, Error>;
+
     fn get_components(e: Env) -> Vec<Component>;
 
     fn get_component(e: Env, component_address: Address) -> Option<Component>;
 
     fn get_manager(e: Env) -> Option<Address>;
+
+    fn total_supply(e: Env) -> i128;
 }
 
 #[contractclient(name = "ConstellationTokenModuleInterface")]
 pub trait Module {
-    fn update_units(
-        e: Env,
-        token_a: (Address, i128),
-        token_b: (Address, i128),
-    ) -> Result<(), Error>;
-    fn add_module(e: Env, module: Address) -> Result<(), Error>;
+    fn snapshot_balances(e: Env, module: Address, tokens: Vec<Address>) -> Result<(), Error>;
+    fn update_units(e: Env, module: Address) -> Result<(), Error>;
+    fn mint_fee(e: Env, module: Address, to: Address, amount: i128) -> Result<(), Error>;
+    fn add_module(e: Env, module: Address, scope: Option<ModuleScope>) -> Result<(), Error>;
+    fn initialize_module(e: Env, module: Address) -> Result<(), Error>;
     fn remove_module(e: Env, module: Address) -> Result<(), Error>;
 
     fn is_registered_module(e: Env, module: Address) -> bool;
+    fn get_module_scope(e: Env, module: Address) -> Option<ModuleScope>;
+    fn get_modules(e: Env) -> Map<Address, ModuleState>;
     fn invoke(
         e: Env,
         caller_module_id: Address,